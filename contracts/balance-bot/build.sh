@@ -0,0 +1,9 @@
+#!/usr/bin/env bash
+# Builds the contract with cargo-contract and copies its code and metadata to the fixtures of the
+# node tests.
+set -euo pipefail
+
+cd "$(dirname "$0")"
+cargo contract build --release
+mkdir -p ../../node/tests/fixtures/balance-bot
+cp target/ink/balance_bot.wasm target/ink/metadata.json ../../node/tests/fixtures/balance-bot/
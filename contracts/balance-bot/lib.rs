@@ -0,0 +1,70 @@
+//! Trading bot fixture of the node tests: reads balances through the Polkadex chain extension.
+//!
+//! Built into `node/tests/fixtures/balance-bot` by `build.sh`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_env::{DefaultEnvironment, Environment};
+use ink_lang as ink;
+
+/// Functions of `PolkadexChainExtension` in the runtime.
+#[ink::chain_extension]
+pub trait Polkadex {
+	type ErrorCode = PolkadexError;
+
+	/// Free balance of `account`, in PDEX if `asset` is `None`.
+	#[ink(extension = 1, returns_result = false, handle_status = false)]
+	fn balance(asset: Option<u128>, account: ink_env::AccountId) -> u128;
+}
+
+/// The balance function does not fail, its status code is always 0.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PolkadexError {
+	Failed,
+}
+
+impl ink_env::chain_extension::FromStatusCode for PolkadexError {
+	fn from_status_code(status_code: u32) -> Result<(), Self> {
+		match status_code {
+			0 => Ok(()),
+			_ => Err(Self::Failed),
+		}
+	}
+}
+
+/// Environment of Polkadex contracts, the default one with the Polkadex chain extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PolkadexEnvironment {}
+
+impl Environment for PolkadexEnvironment {
+	const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+	type AccountId = <DefaultEnvironment as Environment>::AccountId;
+	type Balance = <DefaultEnvironment as Environment>::Balance;
+	type Hash = <DefaultEnvironment as Environment>::Hash;
+	type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+	type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+
+	type ChainExtension = Polkadex;
+}
+
+#[ink::contract(env = crate::PolkadexEnvironment)]
+mod balance_bot {
+	#[ink(storage)]
+	pub struct BalanceBot {}
+
+	impl BalanceBot {
+		#[ink(constructor)]
+		pub fn new() -> Self {
+			Self {}
+		}
+
+		/// Free balance of `account`, in PDEX if `asset` is `None`.
+		#[ink(message)]
+		pub fn balance(&self, asset: Option<u128>, account: AccountId) -> Balance {
+			self.env().extension().balance(asset, account)
+		}
+	}
+}
@@ -1,7 +1,4 @@
-use frame_support::PalletId;
 use grandpa_primitives::AuthorityId as GrandpaId;
-use hex_literal::hex;
-use itertools::Itertools;
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use polkadex_primitives::Block;
 pub use polkadex_primitives::{AccountId, Balance, Signature};
@@ -11,20 +8,24 @@ use sc_telemetry::TelemetryEndpoints;
 use serde::{Deserialize, Serialize};
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_consensus_babe::AuthorityId as BabeId;
-use sp_core::{crypto::UncheckedInto, sr25519, Pair, Public};
+use sp_core::{sr25519, Pair, Public};
 use sp_runtime::{
-	traits::{AccountIdConversion, IdentifyAccount, Verify},
-	Perbill,
+	traits::{Get, IdentifyAccount, Verify},
+	Perbill, Percent,
 };
+use std::str::FromStr;
 
 pub use node_polkadex_runtime::GenesisConfig;
 use node_polkadex_runtime::{
-	constants::currency::PDEX, wasm_binary_unwrap, AuthorityDiscoveryConfig, BabeConfig,
-	BalancesConfig, CouncilConfig, IndicesConfig, OrmlVestingConfig, PDEXMigrationConfig,
-	SessionConfig, SessionKeys, StakerStatus, StakingConfig, SudoConfig, SystemConfig,
+	constants::currency::PDEX, wasm_binary_unwrap, AssetRegistryConfig, AssetsConfig,
+	AuthorityDiscoveryConfig, BabeConfig, BalancesConfig, CouncilConfig, FeeDiscountsConfig,
+	FeelessConfig, IndicesConfig, MaxAuthorities, OrmlVestingConfig, PDEXMigrationConfig,
+	SessionConfig, SessionKeys, StakerStatus, StakingConfig, SystemConfig,
 	TechnicalCommitteeConfig,
 };
 
+use crate::genesis::{Allocations, AuthorityKeys, Network};
+
 type AccountPublic = <Signature as Verify>::Signer;
 
 const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
@@ -42,6 +43,82 @@ pub struct Extensions {
 	/// Known bad block hashes.
 	pub bad_blocks: sc_client_api::BadBlocks<Block>,
 	pub light_sync_state: sc_sync_state_rpc::LightSyncStateExtension,
+	/// GRANDPA parameters, the defaults if the chain spec has none.
+	#[serde(default)]
+	pub grandpa: GrandpaExtension,
+}
+
+/// GRANDPA parameters of the chain, read from the `grandpa` extension of the chain spec.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct GrandpaExtension {
+	/// Interval between the gossip of votes, in milliseconds.
+	pub gossip_duration: u64,
+	/// Number of blocks between the justifications stored and sent to the light clients.
+	pub justification_period: u32,
+	/// Whether the nodes which are not authorities run the GRANDPA observer instead of the voter.
+	pub observer: bool,
+	/// Rule restricting the blocks voted on by the authorities.
+	pub voting_rule: GrandpaVotingRule,
+}
+
+impl Default for GrandpaExtension {
+	fn default() -> Self {
+		GrandpaExtension {
+			gossip_duration: 333,
+			justification_period: 512,
+			observer: false,
+			voting_rule: GrandpaVotingRule::Default,
+		}
+	}
+}
+
+impl GrandpaExtension {
+	/// Checks that the parameters can run GRANDPA.
+	pub fn validate(&self) -> Result<(), String> {
+		if self.gossip_duration == 0 {
+			return Err("The GRANDPA gossip duration must be positive".into())
+		}
+		if self.justification_period == 0 {
+			return Err("The GRANDPA justification period must be positive".into())
+		}
+		if self.voting_rule == GrandpaVotingRule::BeforeBestBlockBy(0) {
+			return Err("Voting 0 blocks before the best block is the `best` voting rule".into())
+		}
+		Ok(())
+	}
+}
+
+/// Rule restricting the blocks voted on by GRANDPA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GrandpaVotingRule {
+	/// Vote 2 blocks before the best block, on at most three quarters of the unfinalized chain.
+	Default,
+	/// Vote on the best block.
+	Best,
+	/// Vote the given number of blocks before the best block.
+	BeforeBestBlockBy(u32),
+	/// Vote on at most three quarters of the unfinalized chain.
+	ThreeQuartersOfTheUnfinalizedChain,
+}
+
+impl FromStr for GrandpaVotingRule {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"default" => Ok(GrandpaVotingRule::Default),
+			"best" => Ok(GrandpaVotingRule::Best),
+			"three-quarters-of-the-unfinalized-chain" =>
+				Ok(GrandpaVotingRule::ThreeQuartersOfTheUnfinalizedChain),
+			_ => s
+				.strip_prefix("before-best-block-by=")
+				.and_then(|blocks| blocks.parse().ok())
+				.map(GrandpaVotingRule::BeforeBestBlockBy)
+				.ok_or_else(|| format!("Unknown voting rule {}", s)),
+		}
+	}
 }
 
 /// Specialized `ChainSpec`.
@@ -57,59 +134,7 @@ pub(crate) fn session_keys(
 }
 
 fn udon_testnet_config_genesis() -> GenesisConfig {
-	let initial_authorities: Vec<(
-		AccountId,
-		AccountId,
-		GrandpaId,
-		BabeId,
-		ImOnlineId,
-		AuthorityDiscoveryId,
-	)> = vec![
-		(
-			// 5Fbsd6WXDGiLTxunqeK5BATNiocfCqu9bS1yArVjCgeBLkVy
-			hex!["9c7a2ee14e565db0c69f78c7b4cd839fbf52b607d867e9e9c5a79042898a0d12"].into(),
-			// 5EnCiV7wSHeNhjW3FSUwiJNkcc2SBkPLn5Nj93FmbLtBjQUq
-			hex!["781ead1e2fa9ccb74b44c19d29cb2a7a4b5be3972927ae98cd3877523976a276"].into(),
-			// 5H4dmeetCqvLhFbXCQ9MrfHCu7RatJHrPYD71Jikdnt2VZeD
-			hex!["dd1f6928c778a52b605889acb99d627b3a9be9a42439c77bc00f1980d4f540ec"]
-				.unchecked_into(),
-			// 5EynamEisSmW3kUdGC7BSXQy1oR8rD1CWLjHh2LGz8bys3sg
-			hex!["80f461b74b90b4913e0354569e90c7cd11ca5dbce6e8b2a6fcbbe0761b877e06"]
-				.unchecked_into(),
-			// 5EynamEisSmW3kUdGC7BSXQy1oR8rD1CWLjHh2LGz8bys3sg
-			hex!["80f461b74b90b4913e0354569e90c7cd11ca5dbce6e8b2a6fcbbe0761b877e06"]
-				.unchecked_into(),
-			// 5EynamEisSmW3kUdGC7BSXQy1oR8rD1CWLjHh2LGz8bys3sg
-			hex!["80f461b74b90b4913e0354569e90c7cd11ca5dbce6e8b2a6fcbbe0761b877e06"]
-				.unchecked_into(),
-		),
-		(
-			// 5ERawXCzCWkjVq3xz1W5KGNtVx2VdefvZ62Bw1FEuZW4Vny2
-			hex!["68655684472b743e456907b398d3a44c113f189e56d1bbfd55e889e295dfde78"].into(),
-			// 5Gc4vr42hH1uDZc93Nayk5G7i687bAQdHHc9unLuyeawHipF
-			hex!["c8dc79e36b29395413399edaec3e20fcca7205fb19776ed8ddb25d6f427ec40e"].into(),
-			// 5H85GsLD6svD6PHtpenjiXVyHGcwCCYB8zbdrVDPWsuocDYB
-			hex!["dfbf0015a3b9e483606f595ea122b3f2355b46d9085fcb0639cb03f05467ab59"]
-				.unchecked_into(),
-			// 5GC5FgdZbCYkMnZ2Ez8o2zztvkdR3qn1Zymknbi97vUsk2vV
-			hex!["b68fae03e44288bde5c66fd89893d943baf88b8cffb33aa7f1dedf0d4a86ad3c"]
-				.unchecked_into(),
-			// 5GC5FgdZbCYkMnZ2Ez8o2zztvkdR3qn1Zymknbi97vUsk2vV
-			hex!["b68fae03e44288bde5c66fd89893d943baf88b8cffb33aa7f1dedf0d4a86ad3c"]
-				.unchecked_into(),
-			// 5GC5FgdZbCYkMnZ2Ez8o2zztvkdR3qn1Zymknbi97vUsk2vV
-			hex!["b68fae03e44288bde5c66fd89893d943baf88b8cffb33aa7f1dedf0d4a86ad3c"]
-				.unchecked_into(),
-		),
-	];
-
-	let root_key: AccountId = hex![
-		// 5Ggr5JRSxCSZvwTc9Xkjca5bWkkmG1btufW22uLm5tArfV9y
-		"cc816e946438b2b21b8a3073f983ce03ee0feb313ec494e2dec462cfb4e77502"
-	]
-	.into();
-
-	testnet_genesis(initial_authorities, vec![], root_key)
+	network_genesis(Network::udon())
 }
 
 /// Staging testnet config.
@@ -134,7 +159,12 @@ pub fn udon_testnet_config() -> ChainSpec {
 
 /// Helper function to generate a crypto pair from seed
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
-	TPublic::Pair::from_string(&format!("//{}", seed), None)
+	get_from_suri::<TPublic>(&format!("//{}", seed))
+}
+
+/// Helper function to generate a crypto pair from a secret URI, such as a secret phrase
+pub fn get_from_suri<TPublic: Public>(suri: &str) -> <TPublic::Pair as Pair>::Public {
+	TPublic::Pair::from_string(suri, None)
 		.expect("static values are valid; qed")
 		.public()
 }
@@ -151,13 +181,22 @@ where
 pub fn authority_keys_from_seed(
 	seed: &str,
 ) -> (AccountId, AccountId, GrandpaId, BabeId, ImOnlineId, AuthorityDiscoveryId) {
+	authority_keys_from_suri(&format!("//{}", seed))
+}
+
+/// Helper function to generate stash, controller and session key from a secret URI
+///
+/// The controller and the session keys are derived from `suri` itself, the stash from
+/// `suri//stash`.
+pub fn authority_keys_from_suri(suri: &str) -> AuthorityKeys {
 	(
-		get_account_id_from_seed::<sr25519::Public>(&format!("{}//stash", seed)),
-		get_account_id_from_seed::<sr25519::Public>(seed),
-		get_from_seed::<GrandpaId>(seed),
-		get_from_seed::<BabeId>(seed),
-		get_from_seed::<ImOnlineId>(seed),
-		get_from_seed::<AuthorityDiscoveryId>(seed),
+		AccountPublic::from(get_from_suri::<sr25519::Public>(&format!("{}//stash", suri)))
+			.into_account(),
+		AccountPublic::from(get_from_suri::<sr25519::Public>(suri)).into_account(),
+		get_from_suri::<GrandpaId>(suri),
+		get_from_suri::<BabeId>(suri),
+		get_from_suri::<ImOnlineId>(suri),
+		get_from_suri::<AuthorityDiscoveryId>(suri),
 	)
 }
 
@@ -165,7 +204,7 @@ fn development_config_genesis() -> GenesisConfig {
 	testnet_genesis(
 		vec![authority_keys_from_seed("Alice")],
 		vec![],
-		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
 	)
 }
 
@@ -189,7 +228,7 @@ fn soba_testnet_genesis() -> GenesisConfig {
 	testnet_genesis(
 		vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
 		vec![],
-		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
 	)
 }
 
@@ -209,54 +248,52 @@ pub fn soba_testnet_config() -> ChainSpec {
 	)
 }
 
+/// Seeds of the validators of the local testnet, beyond which validators are `ValidatorN`.
+const LOCAL_TESTNET_SEEDS: [&str; 6] = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+
+/// Maximum number of validators of a local testnet, the maximum number of BABE and GRANDPA
+/// authorities of the runtime.
+pub fn max_local_testnet_validators() -> usize {
+	MaxAuthorities::get() as usize
+}
+
+/// Number of validators of the `local-<n>` chain, if `id` is of this form, or an error if the
+/// local testnet can not have `n` validators.
+pub fn local_testnet_validators(id: &str) -> Option<Result<usize, String>> {
+	let validators = id.strip_prefix("local-")?.parse::<usize>().ok()?;
+	let max = max_local_testnet_validators();
+	Some(if validators == 0 || validators > max {
+		Err(format!("A local testnet has between 1 and {} validators, not {}", max, validators))
+	} else {
+		Ok(validators)
+	})
+}
+
+/// Local testnet config with `validators` well-known validators: Alice, Bob, Charlie, Dave, Eve
+/// and Ferdie, then `Validator7` and so on
+pub fn local_testnet_config(validators: usize) -> ChainSpec {
+	let initial_authorities = (0..validators)
+		.map(|i| match LOCAL_TESTNET_SEEDS.get(i) {
+			Some(seed) => authority_keys_from_seed(seed),
+			None => authority_keys_from_seed(&format!("Validator{}", i + 1)),
+		})
+		.collect::<Vec<_>>();
+	ChainSpec::from_genesis(
+		"Local Testnet",
+		"local_testnet",
+		ChainType::Local,
+		move || local_testnet_genesis(initial_authorities.clone()),
+		vec![],
+		None,
+		None,
+		None,
+		None,
+		Default::default(),
+	)
+}
+
 fn mainnet_genesis_constuctor() -> GenesisConfig {
-	let initial_authorities: Vec<(
-		AccountId,
-		AccountId,
-		GrandpaId,
-		BabeId,
-		ImOnlineId,
-		AuthorityDiscoveryId,
-	)> = vec![
-		(
-			// 5Fbsd6WXDGiLTxunqeK5BATNiocfCqu9bS1yArVjCgeBLkVy
-			hex!["9c7a2ee14e565db0c69f78c7b4cd839fbf52b607d867e9e9c5a79042898a0d12"].into(),
-			// 5EnCiV7wSHeNhjW3FSUwiJNkcc2SBkPLn5Nj93FmbLtBjQUq
-			hex!["781ead1e2fa9ccb74b44c19d29cb2a7a4b5be3972927ae98cd3877523976a276"].into(),
-			// 5H4dmeetCqvLhFbXCQ9MrfHCu7RatJHrPYD71Jikdnt2VZeD
-			hex!["dd1f6928c778a52b605889acb99d627b3a9be9a42439c77bc00f1980d4f540ec"]
-				.unchecked_into(),
-			// 5EynamEisSmW3kUdGC7BSXQy1oR8rD1CWLjHh2LGz8bys3sg
-			hex!["80f461b74b90b4913e0354569e90c7cd11ca5dbce6e8b2a6fcbbe0761b877e06"]
-				.unchecked_into(),
-			// 5EynamEisSmW3kUdGC7BSXQy1oR8rD1CWLjHh2LGz8bys3sg
-			hex!["80f461b74b90b4913e0354569e90c7cd11ca5dbce6e8b2a6fcbbe0761b877e06"]
-				.unchecked_into(),
-			// 5EynamEisSmW3kUdGC7BSXQy1oR8rD1CWLjHh2LGz8bys3sg
-			hex!["80f461b74b90b4913e0354569e90c7cd11ca5dbce6e8b2a6fcbbe0761b877e06"]
-				.unchecked_into(),
-		),
-		(
-			// 5ERawXCzCWkjVq3xz1W5KGNtVx2VdefvZ62Bw1FEuZW4Vny2
-			hex!["68655684472b743e456907b398d3a44c113f189e56d1bbfd55e889e295dfde78"].into(),
-			// 5Gc4vr42hH1uDZc93Nayk5G7i687bAQdHHc9unLuyeawHipF
-			hex!["c8dc79e36b29395413399edaec3e20fcca7205fb19776ed8ddb25d6f427ec40e"].into(),
-			// 5H85GsLD6svD6PHtpenjiXVyHGcwCCYB8zbdrVDPWsuocDYB
-			hex!["dfbf0015a3b9e483606f595ea122b3f2355b46d9085fcb0639cb03f05467ab59"]
-				.unchecked_into(),
-			// 5GC5FgdZbCYkMnZ2Ez8o2zztvkdR3qn1Zymknbi97vUsk2vV
-			hex!["b68fae03e44288bde5c66fd89893d943baf88b8cffb33aa7f1dedf0d4a86ad3c"]
-				.unchecked_into(),
-			// 5GC5FgdZbCYkMnZ2Ez8o2zztvkdR3qn1Zymknbi97vUsk2vV
-			hex!["b68fae03e44288bde5c66fd89893d943baf88b8cffb33aa7f1dedf0d4a86ad3c"]
-				.unchecked_into(),
-			// 5GC5FgdZbCYkMnZ2Ez8o2zztvkdR3qn1Zymknbi97vUsk2vV
-			hex!["b68fae03e44288bde5c66fd89893d943baf88b8cffb33aa7f1dedf0d4a86ad3c"]
-				.unchecked_into(),
-		),
-	];
-	let root_key = hex!["70a5f4e786b47baf52d5a34742bb8312139cfe1c747fbeb3912c197d38c53332"].into();
-	testnet_genesis(initial_authorities, vec![], root_key)
+	network_genesis(Network::mainnet())
 }
 
 pub fn mainnet_testnet_config() -> ChainSpec {
@@ -279,15 +316,11 @@ pub fn mainnet_testnet_config() -> ChainSpec {
 	)
 }
 
-fn adjust_treasury_balance_for_initial_validators(
-	initial_validators: usize,
-	endowment: u128,
-) -> u128 {
-	// The extra one is for root_key
-	(initial_validators + 1) as u128 * endowment
-}
-
 /// Helper function to create GenesisConfig for testing
+///
+/// The runtime has no sudo. The `root_key`, if given, is endowed and becomes the only initial
+/// member of the council and the technical committee, so that development and test chains can be
+/// governed by a single account. Named networks take their governance from [`Network`] instead.
 pub fn testnet_genesis(
 	initial_authorities: Vec<(
 		AccountId,
@@ -298,54 +331,39 @@ pub fn testnet_genesis(
 		AuthorityDiscoveryId,
 	)>,
 	_initial_nominators: Vec<AccountId>,
-	root_key: AccountId,
+	root_key: Option<AccountId>,
 ) -> GenesisConfig {
-	const ENDOWMENT: u128 = 100 * PDEX;
-	const STASH: u128 = 2 * PDEX;
-	// Total Supply in ERC20
-	const ERC20_PDEX_SUPPLY: u128 = 3_172_895 * PDEX;
-	// Total funds in treasury also includes 2_000_000 PDEX for parachain auctions
-	let mut treasury_funds: u128 = 10_200_000 * PDEX;
-	treasury_funds = treasury_funds -
-		adjust_treasury_balance_for_initial_validators(initial_authorities.len(), ENDOWMENT);
-
-	// Treasury Account Id
-	pub const TREASURY_PALLET_ID: PalletId = PalletId(*b"py/trsry");
-	let treasury_account: AccountId = TREASURY_PALLET_ID.into_account();
-
-	let mut inital_validators_endowment =
-		initial_authorities.iter().map(|k| (k.0.clone(), ENDOWMENT)).collect_vec();
-	let mut endowed_accounts = vec![
-		//      Root Key
-		(root_key.clone(), ENDOWMENT),
-		//     Treasury Funds
-		(treasury_account, treasury_funds),
-	];
-	// Get rest of the stake holders
-	let mut claims = get_stakeholder_tokens();
-
-	let mut total_claims: u128 = 0;
-	for (_, balance) in &claims {
-		total_claims = total_claims + balance;
-	}
-
-	assert_eq!(total_claims, 6_627_105 * PDEX, "Total claims is configured correctly");
+	let governance = root_key.iter().cloned().collect();
+	build_genesis(initial_authorities, root_key, vec![], governance)
+}
 
-	endowed_accounts.append(claims.as_mut());
-	// Endow to validators
-	endowed_accounts.append(&mut inital_validators_endowment);
+/// Helper function to create the GenesisConfig of a named network, whose council and technical
+/// committee start with the governance of the network, possibly empty.
+pub fn network_genesis(network: Network) -> GenesisConfig {
+	build_genesis(network.authorities, network.root_key, vec![], network.governance)
+}
 
-	let mut total_supply: u128 = 0;
-	for (_, balance) in &endowed_accounts {
-		total_supply = total_supply + balance.clone()
-	}
+/// Helper function to create the GenesisConfig of a local testnet
+///
+/// The controller of the first validator is the root key, the controllers of the other validators
+/// are endowed as well, so that every validator can send transactions.
+pub fn local_testnet_genesis(initial_authorities: Vec<AuthorityKeys>) -> GenesisConfig {
+	let root_key = initial_authorities.first().map(|keys| keys.1.clone());
+	let prefunded = initial_authorities.iter().skip(1).map(|keys| keys.1.clone()).collect();
+	let governance = root_key.iter().cloned().collect();
+	build_genesis(initial_authorities, root_key, prefunded, governance)
+}
 
-	assert_eq!(
-		total_supply + ERC20_PDEX_SUPPLY,
-		20_000_000 * PDEX,
-		"Total Supply Not equal to 20 million"
-	);
-	let vesting = get_vesting_terms();
+fn build_genesis(
+	initial_authorities: Vec<AuthorityKeys>,
+	root_key: Option<AccountId>,
+	prefunded: Vec<AccountId>,
+	governance_members: Vec<AccountId>,
+) -> GenesisConfig {
+	let allocations = Allocations::polkadex();
+	let endowed_accounts = allocations
+		.balances(&initial_authorities, root_key.as_ref(), &prefunded)
+		.unwrap_or_else(|e| panic!("{}", e));
 
 	GenesisConfig {
 		system: SystemConfig { code: wasm_binary_unwrap().to_vec() },
@@ -370,19 +388,26 @@ pub fn testnet_genesis(
 			invulnerables: initial_authorities.iter().map(|x| x.0.clone()).collect(),
 			stakers: initial_authorities
 				.iter()
-				.map(|x| (x.0.clone(), x.1.clone(), STASH, StakerStatus::Validator)) // stash, controller, balance, status
+				.map(|x| {
+					// stash, controller, balance, status
+					(
+						x.0.clone(),
+						x.1.clone(),
+						allocations.supply.validator_stake,
+						StakerStatus::Validator,
+					)
+				})
 				.collect(),
 			slash_reward_fraction: Perbill::from_percent(10),
 			..Default::default()
 		},
 		elections: Default::default(),
-		council: CouncilConfig { members: vec![], phantom: Default::default() },
+		council: CouncilConfig { members: governance_members.clone(), phantom: Default::default() },
 		technical_committee: TechnicalCommitteeConfig {
-			members: vec![],
+			members: governance_members,
 			phantom: Default::default(),
 		},
 		democracy: Default::default(),
-		sudo: SudoConfig { key: Some(root_key.clone()) },
 		babe: BabeConfig {
 			authorities: Default::default(),
 			epoch_config: Some(node_polkadex_runtime::BABE_GENESIS_EPOCH_CONFIG),
@@ -392,735 +417,36 @@ pub fn testnet_genesis(
 		grandpa: Default::default(),
 		technical_membership: Default::default(),
 		treasury: Default::default(),
-		orml_vesting: OrmlVestingConfig { vesting },
-		pdex_migration: PDEXMigrationConfig { max_tokens: ERC20_PDEX_SUPPLY, operational: false },
+		orml_vesting: OrmlVestingConfig { vesting: allocations.vesting_schedules() },
+		pdex_migration: PDEXMigrationConfig {
+			max_tokens: allocations.supply.erc20,
+			operational: allocations.bridge.operational,
+		},
+		assets: AssetsConfig { assets: vec![], metadata: vec![], accounts: vec![] },
+		asset_registry: AssetRegistryConfig { assets: vec![] },
+		feeless: FeelessConfig {
+			calls: vec![
+				(b"Orderbook".to_vec(), b"place_limit_order".to_vec()),
+				(b"Orderbook".to_vec(), b"cancel_order".to_vec()),
+				(b"PDEXMigration".to_vec(), b"mint".to_vec()),
+			],
+			registered: vec![],
+			minimum_stake: Some(100 * PDEX),
+		},
+		fee_discounts: FeeDiscountsConfig {
+			tiers: vec![
+				(1_000 * PDEX, Percent::from_percent(10)),
+				(10_000 * PDEX, Percent::from_percent(20)),
+			],
+		},
 	}
 }
 
-pub fn get_vesting_terms() -> Vec<(AccountId, u32, u32, u32, Balance)> {
-	// 3 months in terms of 12s blocks is 648,000 blocks, i.e. period = 648,000
-	const THREE_MONTHS: u32 = 648_000; // We are approximating a month to 30 days.
-	const OCT_16_2021: u32 = 144_000; // 20 days from block 0, implies 144_000 blocks
-	const JAN_16_2022: u32 = OCT_16_2021 + THREE_MONTHS;
-	const APR_16_2022: u32 = JAN_16_2022 + THREE_MONTHS;
-	const JUL_16_2022: u32 = APR_16_2022 + THREE_MONTHS;
-	const OCT_16_2022: u32 = JUL_16_2022 + THREE_MONTHS;
-
-	// who, start, period, period_count, per_period
-	vec![
-		// Oct 16 2021 Tranche
-		(
-			hex!["e4cdc8abc0405db44c1a6886a2f2c59012fa3b98c07b61d63cc7f9e437ba243e"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			6_000 * PDEX,
-		),
-		// (hex!["b26562a2e476fea86b26b2e47f12d279deb0ca7812bd1dad5b4fc8a909e10b22"].into(), 0,
-		// OCT_16_2021, 1, 80_000 * PDEX), // They will validate for us
-		(
-			hex!["e83adffb6338272e981cbc0c6cc03fd4e5e8447497b6b531b9436870c6079758"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			6_000 * PDEX,
-		),
-		(
-			hex!["e613dd948e7baacc02c97db737ad43af7024f5ae595d06f1611ce827c300b17f"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			120_000 * PDEX,
-		),
-		// (hex!["182400644f4780a65a43e00f9630152fe0ab2323d0dacd04e808ceccf462f416"].into(), 0,
-		// OCT_16_2021, 1, 35_000 * PDEX), // They will validate for us
-		(
-			hex!["b8779ddd7bc8dc00dc0e220b6b07b509553c3cdbdad3e384cc1ba2187cbca53f"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			1875 * PDEX,
-		),
-		(
-			hex!["62168680c9ed6e456fa59bd01525a53dd6fa991757e920482016e7db6caebd45"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			4375 * PDEX,
-		),
-		(
-			hex!["1c2eaec3bd844d93d29d442c1ecc431e8502ce7b13f950ae203ee63ff2a1750a"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			17500 * PDEX,
-		),
-		(
-			hex!["78d4caac9c5b562190901aafb9f2c74780c5831a89257254ca225729e755d919"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			17500 * PDEX,
-		),
-		(
-			hex!["1a8538e949213a4034bca131957bbfe8bc45107be4e93c86f92353fccff90039"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			17500 * PDEX,
-		),
-		(
-			hex!["fac6591fd5605154f1a77fc142d66d9c2f7b11f5c0bc61a3ac8ab46099e87e3a"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			5250 * PDEX,
-		),
-		// (hex!["ccb97ce4726461ad53c0ec9277b1ba3f7f88b0a11f847f1ca17d358e6e4d0a05"].into(), 0,
-		// OCT_16_2021, 1, 63750 * PDEX), // They will validate for us
-		// (hex!["08a1c86a2c789eeb1295c3b3ba63b2cde5d23fa6c80d8f87246c21a11fa3ba1d"].into(), 0,
-		// OCT_16_2021, 1, 35000 * PDEX), // They will validate for us
-		// (hex!["082cb53d6299dc033e467de007bfd5c4c0d24135aa85d2f1d983008ff78fbb66"].into(), 0,
-		// OCT_16_2021, 1, 42500 * PDEX), // They will validate for us
-		(
-			hex!["48cb52f3831917977aec38d9c3a3c73c8253b82523af35d44b7122e674677f05"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			17500 * PDEX,
-		),
-		(
-			hex!["0617b168a08acd31e3323ff63cb6e8e7682ba002ca0184a59a0ebc6dcf4e7f2b"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			17500 * PDEX,
-		),
-		// (hex!["0a1f6fa0345ceac40338c78bdfc68a211898921032d30e1b4492090c29962505"].into(), 0,
-		// OCT_16_2021, 1, 10625 * PDEX), // They will validate for us
-		(
-			hex!["b2fa882baef6358e3b4379c290fc989093da5f62b0c8cc57bb972fa7232efe10"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			8750 * PDEX,
-		),
-		(
-			hex!["ecd0a0fba2f97d02d81fa3408e7e1f4a40b36d58fb7b999f0d0f5e073b810d3d"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			31875 * PDEX,
-		),
-		(
-			hex!["0838d06bad89b000120bea3e2cbf59e342f518a3f76becfa8c35bfd386e79825"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			17500 * PDEX,
-		),
-		(
-			hex!["60285b86e8196e4e20565440e2ded16459a8f1e8b6c5ce8bacb4a5b11eee8b05"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			25250 * PDEX,
-		),
-		(
-			hex!["68732830b518f410592bfb6f623e9864e9c021bc4adfe4845916932024bf9119"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			2625 * PDEX,
-		),
-		(
-			hex!["bc13c9a902a524609f064014695f2b6548a17d7e8bb12a834220559bc38bbc5d"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			14000 * PDEX,
-		),
-		(
-			hex!["daeb89c994d06f7e996e2c3e9e1fe685765e40f083432fbcdcb7f77bc1f9a378"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			2625 * PDEX,
-		),
-		(
-			hex!["3ceab1c17a4302ac0471e943279bd993adf12af6d2010a4f73bbdf428fba914f"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			10000 * PDEX,
-		),
-		(
-			hex!["baf1346f012c29003aeb63ac2503fbfafcd0dc182e98053b34f8bb08510ca73f"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			15280 * PDEX,
-		),
-		// (hex!["969554a9c50959bc434b99051b9803cc911ba3cad6c0e1d2ab2b8bcbbd1f057e"].into(), 0,
-		// OCT_16_2021, 1, 20000 * PDEX), // They will validate for us
-		(
-			hex!["724513af8211cbaaeb17e7bbff8f2286718135d4ebe10e556c5b2076dbbd342d"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			20000 * PDEX,
-		),
-		(
-			hex!["cc0056b00683900613556f57c5324a2882fa9b5f50702e61ffade0b1102f0674"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			10000 * PDEX,
-		),
-		// (hex!["eab1d6b0efce910517067712d026e42ab5f84ffd068b80d3cd55cd7c95d4db68"].into(), 0,
-		// OCT_16_2021, 1, 20000 * PDEX), // They will validate for us
-		// (hex!["3ee90311650ce54b81d70f77537dc255c130ac9f5f5933cc6e2cedcb00ebdf5d"].into(), 0,
-		// OCT_16_2021, 1, 50000 * PDEX),// They will validate for us
-		// (hex!["a0cc2a61879f21b7924392cfea5c35b47781f795ca24d179188c6d3f2a67952b"].into(), 0,
-		// OCT_16_2021, 1, 20000 * PDEX),// They will validate for us
-		(
-			hex!["2c6ce334da34c1ffdfb9cfb9962afdc9decf8f36b8d5282c2dbdef7c7b1aee53"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			20000 * PDEX,
-		),
-		(
-			hex!["aa36b0d46767a839e11f18d8f15d373ed1f63abb33324edd87ebdc5fcfabd812"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			20000 * PDEX,
-		),
-		(
-			hex!["7a56462554bef5d4f946a3c2ea1798398303aaf49e2d80d272096fb04cd95d06"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			375 * PDEX,
-		),
-		(
-			hex!["9a82629aac0895e5998542537f6b5b3a1c2c6fd46e827d409de88aacf9755a0e"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			9375 * (PDEX / 10),
-		),
-		(
-			hex!["8039b9f35380bc3c20206d25c44006bd98e1252d7cb80acd6290b4f9c17bcd4c"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			5000 * PDEX,
-		),
-		(
-			hex!["ec3cfd6b94a36adf49492caae5c59005b04e88a936c6106c4feca1631b5d6025"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			5000 * PDEX,
-		),
-		(
-			hex!["8a442ebbcdb3aeace616292a957f36462e1e4c69e11de340527bfb617b01e068"].into(),
-			0,
-			OCT_16_2021,
-			1,
-			5000 * PDEX,
-		),
-		// (hex!["2c6789aa288e153564fe1ad4f824d8b760171db53d4e7500e2d3f9d51e979e03"].into(), 0,
-		// OCT_16_2021, 1, 40000 * PDEX), // They will validate for us
-		// (hex!["fa5d4dae5bd8de8f480a59c41a2c1a373be2a311ee84e01c6645b90716055c10"].into(), 0,
-		// OCT_16_2021, 1, 4669425 * (PDEX / 10)),
-
-		// After Oct 16 2021
-		(
-			hex!["e4cdc8abc0405db44c1a6886a2f2c59012fa3b98c07b61d63cc7f9e437ba243e"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			6_000 * PDEX,
-		),
-		(
-			hex!["b26562a2e476fea86b26b2e47f12d279deb0ca7812bd1dad5b4fc8a909e10b22"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			80_000 * PDEX,
-		),
-		(
-			hex!["e83adffb6338272e981cbc0c6cc03fd4e5e8447497b6b531b9436870c6079758"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			6_000 * PDEX,
-		),
-		(
-			hex!["e613dd948e7baacc02c97db737ad43af7024f5ae595d06f1611ce827c300b17f"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			120_000 * PDEX,
-		),
-		(
-			hex!["182400644f4780a65a43e00f9630152fe0ab2323d0dacd04e808ceccf462f416"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			35_000 * PDEX,
-		),
-		(
-			hex!["b8779ddd7bc8dc00dc0e220b6b07b509553c3cdbdad3e384cc1ba2187cbca53f"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			1875 * PDEX,
-		),
-		(
-			hex!["62168680c9ed6e456fa59bd01525a53dd6fa991757e920482016e7db6caebd45"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			4375 * PDEX,
-		),
-		(
-			hex!["1c2eaec3bd844d93d29d442c1ecc431e8502ce7b13f950ae203ee63ff2a1750a"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			17500 * PDEX,
-		),
-		(
-			hex!["78d4caac9c5b562190901aafb9f2c74780c5831a89257254ca225729e755d919"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			17500 * PDEX,
-		),
-		(
-			hex!["1a8538e949213a4034bca131957bbfe8bc45107be4e93c86f92353fccff90039"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			17500 * PDEX,
-		),
-		(
-			hex!["fac6591fd5605154f1a77fc142d66d9c2f7b11f5c0bc61a3ac8ab46099e87e3a"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			5250 * PDEX,
-		),
-		(
-			hex!["ccb97ce4726461ad53c0ec9277b1ba3f7f88b0a11f847f1ca17d358e6e4d0a05"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			63750 * PDEX,
-		),
-		(
-			hex!["08a1c86a2c789eeb1295c3b3ba63b2cde5d23fa6c80d8f87246c21a11fa3ba1d"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			35000 * PDEX,
-		),
-		(
-			hex!["082cb53d6299dc033e467de007bfd5c4c0d24135aa85d2f1d983008ff78fbb66"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			42500 * PDEX,
-		),
-		(
-			hex!["48cb52f3831917977aec38d9c3a3c73c8253b82523af35d44b7122e674677f05"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			17500 * PDEX,
-		),
-		(
-			hex!["0617b168a08acd31e3323ff63cb6e8e7682ba002ca0184a59a0ebc6dcf4e7f2b"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			17500 * PDEX,
-		),
-		(
-			hex!["0a1f6fa0345ceac40338c78bdfc68a211898921032d30e1b4492090c29962505"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			10625 * PDEX,
-		),
-		(
-			hex!["b2fa882baef6358e3b4379c290fc989093da5f62b0c8cc57bb972fa7232efe10"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			8750 * PDEX,
-		),
-		(
-			hex!["ecd0a0fba2f97d02d81fa3408e7e1f4a40b36d58fb7b999f0d0f5e073b810d3d"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			31875 * PDEX,
-		),
-		(
-			hex!["0838d06bad89b000120bea3e2cbf59e342f518a3f76becfa8c35bfd386e79825"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			17500 * PDEX,
-		),
-		(
-			hex!["60285b86e8196e4e20565440e2ded16459a8f1e8b6c5ce8bacb4a5b11eee8b05"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			25250 * PDEX,
-		),
-		(
-			hex!["68732830b518f410592bfb6f623e9864e9c021bc4adfe4845916932024bf9119"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			2625 * PDEX,
-		),
-		(
-			hex!["bc13c9a902a524609f064014695f2b6548a17d7e8bb12a834220559bc38bbc5d"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			14000 * PDEX,
-		),
-		(
-			hex!["daeb89c994d06f7e996e2c3e9e1fe685765e40f083432fbcdcb7f77bc1f9a378"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			2625 * PDEX,
-		),
-		(
-			hex!["3ceab1c17a4302ac0471e943279bd993adf12af6d2010a4f73bbdf428fba914f"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			10000 * PDEX,
-		),
-		(
-			hex!["baf1346f012c29003aeb63ac2503fbfafcd0dc182e98053b34f8bb08510ca73f"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			15280 * PDEX,
-		),
-		(
-			hex!["969554a9c50959bc434b99051b9803cc911ba3cad6c0e1d2ab2b8bcbbd1f057e"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			20000 * PDEX,
-		),
-		(
-			hex!["724513af8211cbaaeb17e7bbff8f2286718135d4ebe10e556c5b2076dbbd342d"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			20000 * PDEX,
-		),
-		(
-			hex!["cc0056b00683900613556f57c5324a2882fa9b5f50702e61ffade0b1102f0674"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			10000 * PDEX,
-		),
-		(
-			hex!["eab1d6b0efce910517067712d026e42ab5f84ffd068b80d3cd55cd7c95d4db68"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			20000 * PDEX,
-		),
-		(
-			hex!["3ee90311650ce54b81d70f77537dc255c130ac9f5f5933cc6e2cedcb00ebdf5d"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			50000 * PDEX,
-		),
-		(
-			hex!["a0cc2a61879f21b7924392cfea5c35b47781f795ca24d179188c6d3f2a67952b"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			20000 * PDEX,
-		),
-		(
-			hex!["2c6ce334da34c1ffdfb9cfb9962afdc9decf8f36b8d5282c2dbdef7c7b1aee53"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			20000 * PDEX,
-		),
-		(
-			hex!["aa36b0d46767a839e11f18d8f15d373ed1f63abb33324edd87ebdc5fcfabd812"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			20000 * PDEX,
-		),
-		(
-			hex!["7a56462554bef5d4f946a3c2ea1798398303aaf49e2d80d272096fb04cd95d06"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			375 * PDEX,
-		),
-		(
-			hex!["9a82629aac0895e5998542537f6b5b3a1c2c6fd46e827d409de88aacf9755a0e"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			9375 * (PDEX / 10),
-		),
-		(
-			hex!["8039b9f35380bc3c20206d25c44006bd98e1252d7cb80acd6290b4f9c17bcd4c"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			4,
-			11250 * PDEX,
-		),
-		(
-			hex!["ec3cfd6b94a36adf49492caae5c59005b04e88a936c6106c4feca1631b5d6025"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			4,
-			11250 * PDEX,
-		),
-		(
-			hex!["8a442ebbcdb3aeace616292a957f36462e1e4c69e11de340527bfb617b01e068"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			4,
-			11250 * PDEX,
-		),
-		(
-			hex!["2c6789aa288e153564fe1ad4f824d8b760171db53d4e7500e2d3f9d51e979e03"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			4,
-			90000 * PDEX,
-		),
-		(
-			hex!["fa5d4dae5bd8de8f480a59c41a2c1a373be2a311ee84e01c6645b90716055c10"].into(),
-			OCT_16_2021,
-			THREE_MONTHS,
-			2,
-			5481925 * (PDEX / 10),
-		),
-		(
-			hex!["fa5d4dae5bd8de8f480a59c41a2c1a373be2a311ee84e01c6645b90716055c10"].into(),
-			APR_16_2022,
-			THREE_MONTHS,
-			1,
-			146250 * PDEX,
-		),
-		(
-			hex!["fa5d4dae5bd8de8f480a59c41a2c1a373be2a311ee84e01c6645b90716055c10"].into(),
-			JUL_16_2022,
-			THREE_MONTHS,
-			1,
-			506250 * PDEX,
-		),
-		(
-			hex!["fa5d4dae5bd8de8f480a59c41a2c1a373be2a311ee84e01c6645b90716055c10"].into(),
-			OCT_16_2022,
-			THREE_MONTHS,
-			4,
-			360000 * PDEX,
-		),
-	]
-}
-
-pub fn get_stakeholder_tokens() -> Vec<(AccountId, Balance)> {
-	let claims = vec![
-		(
-			hex!["e4cdc8abc0405db44c1a6886a2f2c59012fa3b98c07b61d63cc7f9e437ba243e"].into(),
-			3 * 6_000 * PDEX,
-		),
-		(
-			hex!["b26562a2e476fea86b26b2e47f12d279deb0ca7812bd1dad5b4fc8a909e10b22"].into(),
-			3 * 80_000 * PDEX,
-		),
-		(
-			hex!["e83adffb6338272e981cbc0c6cc03fd4e5e8447497b6b531b9436870c6079758"].into(),
-			3 * 6_000 * PDEX,
-		),
-		(
-			hex!["e613dd948e7baacc02c97db737ad43af7024f5ae595d06f1611ce827c300b17f"].into(),
-			3 * 120_000 * PDEX,
-		),
-		(
-			hex!["182400644f4780a65a43e00f9630152fe0ab2323d0dacd04e808ceccf462f416"].into(),
-			3 * 35_000 * PDEX,
-		),
-		(
-			hex!["b8779ddd7bc8dc00dc0e220b6b07b509553c3cdbdad3e384cc1ba2187cbca53f"].into(),
-			3 * 1875 * PDEX,
-		),
-		(
-			hex!["62168680c9ed6e456fa59bd01525a53dd6fa991757e920482016e7db6caebd45"].into(),
-			3 * 4375 * PDEX,
-		),
-		(
-			hex!["1c2eaec3bd844d93d29d442c1ecc431e8502ce7b13f950ae203ee63ff2a1750a"].into(),
-			3 * 17500 * PDEX,
-		),
-		(
-			hex!["78d4caac9c5b562190901aafb9f2c74780c5831a89257254ca225729e755d919"].into(),
-			3 * 17500 * PDEX,
-		),
-		(
-			hex!["1a8538e949213a4034bca131957bbfe8bc45107be4e93c86f92353fccff90039"].into(),
-			3 * 17500 * PDEX,
-		),
-		(
-			hex!["fac6591fd5605154f1a77fc142d66d9c2f7b11f5c0bc61a3ac8ab46099e87e3a"].into(),
-			3 * 5250 * PDEX,
-		),
-		(
-			hex!["ccb97ce4726461ad53c0ec9277b1ba3f7f88b0a11f847f1ca17d358e6e4d0a05"].into(),
-			3 * 63750 * PDEX,
-		),
-		(
-			hex!["08a1c86a2c789eeb1295c3b3ba63b2cde5d23fa6c80d8f87246c21a11fa3ba1d"].into(),
-			3 * 35000 * PDEX,
-		),
-		(
-			hex!["082cb53d6299dc033e467de007bfd5c4c0d24135aa85d2f1d983008ff78fbb66"].into(),
-			3 * 42500 * PDEX,
-		),
-		(
-			hex!["48cb52f3831917977aec38d9c3a3c73c8253b82523af35d44b7122e674677f05"].into(),
-			3 * 17500 * PDEX,
-		),
-		(
-			hex!["0617b168a08acd31e3323ff63cb6e8e7682ba002ca0184a59a0ebc6dcf4e7f2b"].into(),
-			3 * 17500 * PDEX,
-		),
-		(
-			hex!["0a1f6fa0345ceac40338c78bdfc68a211898921032d30e1b4492090c29962505"].into(),
-			3 * 10625 * PDEX,
-		),
-		(
-			hex!["b2fa882baef6358e3b4379c290fc989093da5f62b0c8cc57bb972fa7232efe10"].into(),
-			3 * 8750 * PDEX,
-		),
-		(
-			hex!["ecd0a0fba2f97d02d81fa3408e7e1f4a40b36d58fb7b999f0d0f5e073b810d3d"].into(),
-			3 * 31875 * PDEX,
-		),
-		(
-			hex!["0838d06bad89b000120bea3e2cbf59e342f518a3f76becfa8c35bfd386e79825"].into(),
-			3 * 17500 * PDEX,
-		),
-		(
-			hex!["60285b86e8196e4e20565440e2ded16459a8f1e8b6c5ce8bacb4a5b11eee8b05"].into(),
-			3 * 25250 * PDEX,
-		),
-		(
-			hex!["68732830b518f410592bfb6f623e9864e9c021bc4adfe4845916932024bf9119"].into(),
-			3 * 2625 * PDEX,
-		),
-		(
-			hex!["bc13c9a902a524609f064014695f2b6548a17d7e8bb12a834220559bc38bbc5d"].into(),
-			3 * 14000 * PDEX,
-		),
-		(
-			hex!["daeb89c994d06f7e996e2c3e9e1fe685765e40f083432fbcdcb7f77bc1f9a378"].into(),
-			3 * 2625 * PDEX,
-		),
-		(
-			hex!["3ceab1c17a4302ac0471e943279bd993adf12af6d2010a4f73bbdf428fba914f"].into(),
-			3 * 10000 * PDEX,
-		),
-		(
-			hex!["baf1346f012c29003aeb63ac2503fbfafcd0dc182e98053b34f8bb08510ca73f"].into(),
-			3 * 15280 * PDEX,
-		),
-		(
-			hex!["969554a9c50959bc434b99051b9803cc911ba3cad6c0e1d2ab2b8bcbbd1f057e"].into(),
-			3 * 20000 * PDEX,
-		),
-		(
-			hex!["724513af8211cbaaeb17e7bbff8f2286718135d4ebe10e556c5b2076dbbd342d"].into(),
-			3 * 20000 * PDEX,
-		),
-		(
-			hex!["cc0056b00683900613556f57c5324a2882fa9b5f50702e61ffade0b1102f0674"].into(),
-			3 * 10000 * PDEX,
-		),
-		(
-			hex!["eab1d6b0efce910517067712d026e42ab5f84ffd068b80d3cd55cd7c95d4db68"].into(),
-			3 * 20000 * PDEX,
-		),
-		(
-			hex!["3ee90311650ce54b81d70f77537dc255c130ac9f5f5933cc6e2cedcb00ebdf5d"].into(),
-			3 * 50000 * PDEX,
-		),
-		(
-			hex!["a0cc2a61879f21b7924392cfea5c35b47781f795ca24d179188c6d3f2a67952b"].into(),
-			3 * 20000 * PDEX,
-		),
-		(
-			hex!["2c6ce334da34c1ffdfb9cfb9962afdc9decf8f36b8d5282c2dbdef7c7b1aee53"].into(),
-			3 * 20000 * PDEX,
-		),
-		(
-			hex!["aa36b0d46767a839e11f18d8f15d373ed1f63abb33324edd87ebdc5fcfabd812"].into(),
-			3 * 20000 * PDEX,
-		),
-		(
-			hex!["7a56462554bef5d4f946a3c2ea1798398303aaf49e2d80d272096fb04cd95d06"].into(),
-			3 * 375 * PDEX,
-		),
-		(
-			hex!["9a82629aac0895e5998542537f6b5b3a1c2c6fd46e827d409de88aacf9755a0e"].into(),
-			3 * 9375 * (PDEX / 10),
-		),
-		(
-			hex!["8039b9f35380bc3c20206d25c44006bd98e1252d7cb80acd6290b4f9c17bcd4c"].into(),
-			50000 * PDEX,
-		),
-		(
-			hex!["ec3cfd6b94a36adf49492caae5c59005b04e88a936c6106c4feca1631b5d6025"].into(),
-			50000 * PDEX,
-		),
-		(
-			hex!["8a442ebbcdb3aeace616292a957f36462e1e4c69e11de340527bfb617b01e068"].into(),
-			50000 * PDEX,
-		),
-		(
-			hex!["2c6789aa288e153564fe1ad4f824d8b760171db53d4e7500e2d3f9d51e979e03"].into(),
-			400000 * PDEX,
-		),
-		(
-			hex!["fa5d4dae5bd8de8f480a59c41a2c1a373be2a311ee84e01c6645b90716055c10"].into(),
-			36558275 * (PDEX / 10),
-		),
-	];
-	claims
-}
-
 #[cfg(test)]
 pub(crate) mod tests {
+	use codec::Encode;
+	use node_polkadex_runtime::{BagsList, Runtime};
+	use sp_io::hashing::twox_128;
 	use sp_runtime::BuildStorage;
 
 	use super::*;
@@ -1129,7 +455,7 @@ pub(crate) mod tests {
 		testnet_genesis(
 			vec![authority_keys_from_seed("Alice")],
 			vec![],
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
+			Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
 		)
 	}
 
@@ -1179,4 +505,152 @@ pub(crate) mod tests {
 	fn test_staging_test_net_chain_spec() {
 		assert!(!udon_testnet_config().build_storage().is_err());
 	}
+
+	#[test]
+	fn test_create_local_testnet_chain_spec() {
+		for validators in [1, 2, 8] {
+			let genesis = local_testnet_config(validators).build_storage().unwrap();
+			let session = sp_io::TestExternalities::new(genesis)
+				.execute_with(node_polkadex_runtime::Session::validators);
+			assert_eq!(session.len(), validators);
+		}
+	}
+
+	#[test]
+	fn test_local_testnet_validators_are_bounded() {
+		let max = max_local_testnet_validators();
+		assert_eq!(local_testnet_validators("local-4"), Some(Ok(4)));
+		assert_eq!(local_testnet_validators("local"), None);
+		assert_eq!(local_testnet_validators("local-four"), None);
+		assert!(matches!(local_testnet_validators("local-0"), Some(Err(_))));
+		assert!(matches!(local_testnet_validators(&format!("local-{}", max + 1)), Some(Err(_))));
+		assert!(local_testnet_config(max).build_storage().is_ok());
+	}
+
+	#[test]
+	fn test_authority_keys_from_seed_and_suri_match() {
+		assert_eq!(authority_keys_from_seed("Alice"), authority_keys_from_suri("//Alice"));
+		assert_eq!(
+			authority_keys_from_seed("Alice").0,
+			get_account_id_from_seed::<sr25519::Public>("Alice//stash")
+		);
+	}
+
+	#[test]
+	fn test_mainnet_chain_spec() {
+		assert!(!mainnet_testnet_config().build_storage().is_err());
+	}
+
+	#[test]
+	fn test_named_networks_do_not_give_governance_to_a_single_key() {
+		for (network, genesis) in [
+			(Network::mainnet(), mainnet_genesis_constuctor()),
+			(Network::udon(), udon_testnet_config_genesis()),
+		] {
+			for members in [genesis.council.members, genesis.technical_committee.members] {
+				assert_ne!(members.len(), 1);
+				assert_eq!(members, network.governance);
+				assert!(network.root_key.iter().all(|root_key| !members.contains(root_key)));
+			}
+		}
+	}
+
+	#[test]
+	fn test_migrations_apply_to_testnet_genesis() {
+		let storage = testnet_genesis(
+			vec![authority_keys_from_seed("Alice")],
+			vec![],
+			Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+		)
+		.build_storage()
+		.unwrap();
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			// The sudo key of a chain upgrading from a runtime with sudo.
+			let sudo = twox_128(b"Sudo");
+			sp_io::storage::set(
+				&[sudo, twox_128(b"Key")].concat(),
+				&get_account_id_from_seed::<sr25519::Public>("Alice").encode(),
+			);
+
+			// Also runs the pre and post upgrade checks of the migrations with try-runtime.
+			#[cfg(feature = "try-runtime")]
+			node_polkadex_runtime::Executive::try_runtime_upgrade().unwrap();
+			#[cfg(not(feature = "try-runtime"))]
+			node_polkadex_runtime::Executive::execute_on_runtime_upgrade();
+
+			assert!(sp_io::storage::next_key(&sudo).map_or(true, |key| !key.starts_with(&sudo)));
+			let voters = pallet_staking::Nominators::<Runtime>::count() +
+				pallet_staking::Validators::<Runtime>::count();
+			assert_eq!(voters, 1);
+			assert_eq!(BagsList::count(), voters);
+		});
+	}
+
+	#[test]
+	fn test_grandpa_extension_json_round_trip() {
+		let mut spec = development_config();
+		spec.extensions_mut().grandpa = GrandpaExtension {
+			gossip_duration: 1_000,
+			justification_period: 64,
+			observer: true,
+			voting_rule: GrandpaVotingRule::BeforeBestBlockBy(4),
+		};
+		let json = spec.as_json(false).unwrap();
+		let extension = &serde_json::from_str::<serde_json::Value>(&json).unwrap()["grandpa"];
+		assert_eq!(
+			*extension,
+			serde_json::json!({
+				"gossipDuration": 1_000,
+				"justificationPeriod": 64,
+				"observer": true,
+				"votingRule": { "beforeBestBlockBy": 4 },
+			})
+		);
+		let decoded = ChainSpec::from_json_bytes(json.into_bytes()).unwrap();
+		assert_eq!(decoded.extensions().grandpa, spec.extensions().grandpa);
+	}
+
+	#[test]
+	fn test_grandpa_extension_defaults() {
+		let extensions: Extensions = serde_json::from_str(
+			r#"{ "forkBlocks": null, "badBlocks": null, "lightSyncState": null }"#,
+		)
+		.unwrap();
+		assert_eq!(extensions.grandpa, GrandpaExtension::default());
+		let extension: GrandpaExtension =
+			serde_json::from_str(r#"{ "votingRule": "threeQuartersOfTheUnfinalizedChain" }"#)
+				.unwrap();
+		assert_eq!(extension.gossip_duration, 333);
+		assert_eq!(extension.voting_rule, GrandpaVotingRule::ThreeQuartersOfTheUnfinalizedChain);
+		assert!(serde_json::from_str::<GrandpaExtension>(r#"{ "gossipDurationMs": 1 }"#).is_err());
+	}
+
+	#[test]
+	fn test_grandpa_extension_validation() {
+		assert!(GrandpaExtension::default().validate().is_ok());
+		let invalid = [
+			GrandpaExtension { gossip_duration: 0, ..Default::default() },
+			GrandpaExtension { justification_period: 0, ..Default::default() },
+			GrandpaExtension {
+				voting_rule: GrandpaVotingRule::BeforeBestBlockBy(0),
+				..Default::default()
+			},
+		];
+		for extension in invalid {
+			assert!(extension.validate().is_err(), "{:?}", extension);
+		}
+		assert_eq!(
+			"before-best-block-by=2".parse::<GrandpaVotingRule>(),
+			Ok(GrandpaVotingRule::BeforeBestBlockBy(2))
+		);
+		assert_eq!("best".parse::<GrandpaVotingRule>(), Ok(GrandpaVotingRule::Best));
+		assert!("before-best-block-by=two".parse::<GrandpaVotingRule>().is_err());
+	}
+
+	#[test]
+	fn test_genesis_without_root_key() {
+		let genesis = testnet_genesis(vec![authority_keys_from_seed("Alice")], vec![], None);
+		assert!(genesis.council.members.is_empty());
+		assert!(!genesis.build_storage().is_err());
+	}
 }
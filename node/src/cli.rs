@@ -16,6 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::service::{GrandpaOverrides, Sealing};
 use sc_cli::RunCmd;
 
 /// An overarching CLI command definition.
@@ -27,16 +28,25 @@ pub struct Cli {
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// Author blocks with manual seal instead of BABE and GRANDPA, for development chains.
+	///
+	/// `instant` authors a block for every transaction, `manual` only on the `engine_createBlock`
+	/// RPC and `interval=<ms>` at the given interval. Blocks can be authored with
+	/// `engine_createBlock` in every mode, and the other blocks are finalized at once.
+	#[clap(long, value_name = "instant|manual|interval=<ms>")]
+	pub sealing: Option<Sealing>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub grandpa: GrandpaOverrides,
 }
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Subcommand {
-	// /// The custom inspect subcommmand for decoding blocks and extrinsics.
-	// #[clap(
-	// name = "inspect",
-	// about = "Decode given block or extrinsic using current native runtime."
-	// )]
-	// Inspect(node_inspect::cli::InspectCmd),
+	/// Decode a block or an extrinsic with the native runtime.
+	Inspect(polkadex_node::inspect::InspectCmd),
+
 	/// The custom benchmark subcommmand benchmarking runtime pallets.
 	#[clap(subcommand)]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
@@ -65,6 +75,15 @@ pub enum Subcommand {
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
+	/// Audit the economics of the genesis of a chain specification.
+	GenesisCheck(polkadex_node::genesis_check::GenesisCheckCmd),
+
+	/// Generate the keys, the chain specification and the launch files of a testnet.
+	TestnetGen(polkadex_node::testnet_gen::TestnetGenCmd),
+
+	/// Submit transactions to a node at a target rate and report its throughput.
+	LoadTest(polkadex_node::load_test::LoadTestCmd),
+
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
@@ -56,6 +56,10 @@ impl SubstrateCli for Cli {
 	}
 
 	fn load_spec(&self, id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
+		// `local-<n>` is the local testnet with n validators.
+		if let Some(validators) = chain_spec::local_testnet_validators(id) {
+			return Ok(Box::new(chain_spec::local_testnet_config(validators?)))
+		}
 		let spec = match id {
 			"" =>
 				return Err(
@@ -66,6 +70,7 @@ impl SubstrateCli for Cli {
 			"udon" => Box::new(chain_spec::udon_testnet_config()),
 			"soba" => Box::new(chain_spec::soba_testnet_config()),
 			"mainnet" => Box::new(chain_spec::mainnet_testnet_config()),
+			"local" => Box::new(chain_spec::local_testnet_config(2)),
 			path =>
 				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
 		};
@@ -84,15 +89,20 @@ pub fn run() -> Result<()> {
 	match &cli.subcommand {
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			let sealing = cli.sealing;
+			let grandpa_overrides = cli.grandpa.clone();
 			runner.run_node_until_exit(|config| async move {
-				service::new_full(config).map_err(sc_cli::Error::Service)
+				service::new_full(config, sealing, &grandpa_overrides)
+					.map_err(sc_cli::Error::Service)
+			})
+		},
+		Some(Subcommand::Inspect(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, .. } = new_partial(&config)?;
+				cmd.run(client)
 			})
 		},
-		// Some(Subcommand::Inspect(cmd)) => {
-		//     let runner = cli.create_runner(cmd)?;
-		//
-		//     runner.sync_run(|config| cmd.run::<Block, RuntimeApi, ExecutorDispatch>(config))
-		// },
 		Some(Subcommand::Benchmark(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 
@@ -137,6 +147,15 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
 		},
+		Some(Subcommand::GenesisCheck(cmd)) => {
+			let spec = cli.load_spec(&cmd.shared_params.chain_id(cmd.shared_params.is_dev()))?;
+			cmd.run(spec)
+		},
+		Some(Subcommand::TestnetGen(cmd)) => cmd.run(),
+		Some(Subcommand::LoadTest(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| cmd.run(config))
+		},
 		Some(Subcommand::CheckBlock(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
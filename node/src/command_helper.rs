@@ -49,6 +49,7 @@ impl frame_benchmarking_cli::ExtrinsicBuilder for BenchmarkExtrinsicBuilder {
 			acc,
 			SystemCall::remark { remark: vec![] },
 			Some(nonce),
+			None,
 		)
 		.into();
 
@@ -0,0 +1,759 @@
+//! Genesis data of the Polkadex networks, read from the TOML files in `node/res/genesis`.
+//!
+//! `allocations.toml` holds the token allocations shared by every named chain spec: the supply,
+//! the claims of the stakeholders, their vesting schedules and the parameters of the ERC20 bridge.
+//! `udon.toml` and `mainnet.toml` hold the initial authorities, the root key and the initial
+//! governance of their network.
+//!
+//! Accounts and keys are SS58 addresses or 32 byte hex public keys, amounts are PDEX with up to 12
+//! decimals. The data is validated when it is loaded, so that a wrong allocation is reported with
+//! the entry it comes from instead of producing a genesis with the wrong supply.
+
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fmt,
+};
+
+use grandpa_primitives::AuthorityId as GrandpaId;
+use node_polkadex_runtime::{constants::currency::PDEX, TreasuryPalletId};
+use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
+use polkadex_primitives::{AccountId, Balance};
+use serde::Deserialize;
+use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
+use sp_consensus_babe::AuthorityId as BabeId;
+use sp_core::crypto::{Ss58Codec, UncheckedInto};
+use sp_runtime::traits::AccountIdConversion;
+
+const ALLOCATIONS: &str = include_str!("../res/genesis/allocations.toml");
+const UDON: &str = include_str!("../res/genesis/udon.toml");
+const MAINNET: &str = include_str!("../res/genesis/mainnet.toml");
+
+/// Number of decimals of PDEX.
+const DECIMALS: usize = 12;
+
+/// Stash, controller and session keys of an initial validator.
+pub type AuthorityKeys =
+	(AccountId, AccountId, GrandpaId, BabeId, ImOnlineId, AuthorityDiscoveryId);
+
+/// Vesting schedule as configured in the genesis of `orml_vesting`: account, start, period,
+/// period count and amount unlocked per period.
+pub type VestingSchedule = (AccountId, u32, u32, u32, Balance);
+
+/// Error of loading or validating genesis data.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+	/// The file is not valid TOML, or does not have the expected fields.
+	Parse(String),
+	/// An entry of the file has an invalid value.
+	Invalid { entry: String, reason: String },
+	/// The allocations break a supply invariant.
+	Supply(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::Parse(e) => write!(f, "Invalid genesis data: {}", e),
+			Error::Invalid { entry, reason } => write!(f, "Invalid `{}`: {}", entry, reason),
+			Error::Supply(e) => write!(f, "Invalid genesis supply: {}", e),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+/// Supply of PDEX at genesis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Supply {
+	/// Total supply of PDEX.
+	pub total: Balance,
+	/// PDEX still held as ERC20 tokens, minted on Polkadex through pdex-migration.
+	pub erc20: Balance,
+	/// Treasury funds, the endowments of the initial validators and of the root key included.
+	pub treasury: Balance,
+	/// Endowment of each initial validator and of the root key.
+	pub endowment: Balance,
+	/// Stake bonded by each initial validator.
+	pub validator_stake: Balance,
+}
+
+/// Parameters of the ERC20 bridge, pdex-migration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bridge {
+	/// Whether pdex-migration accepts mints from genesis.
+	pub operational: bool,
+}
+
+/// Tokens of a stakeholder, claimed at genesis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Claim {
+	pub account: AccountId,
+	pub amount: Balance,
+}
+
+/// Vesting schedule locking the claim of a stakeholder.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vesting {
+	pub account: AccountId,
+	pub start: u32,
+	pub period: u32,
+	pub period_count: u32,
+	pub per_period: Balance,
+}
+
+/// Token allocations of the genesis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Allocations {
+	pub supply: Supply,
+	pub bridge: Bridge,
+	pub claims: Vec<Claim>,
+	pub vesting: Vec<Vesting>,
+}
+
+impl Allocations {
+	/// Allocations of the Polkadex networks, from `node/res/genesis/allocations.toml`.
+	pub fn polkadex() -> Self {
+		Self::from_toml(ALLOCATIONS).unwrap_or_else(|e| panic!("{}", e))
+	}
+
+	/// Parses and validates allocations in TOML.
+	pub fn from_toml(contents: &str) -> Result<Self, Error> {
+		let raw: RawAllocations = parse_toml(contents)?;
+		let supply = Supply {
+			total: parse_amount("supply.total", &raw.supply.total)?,
+			erc20: parse_amount("supply.erc20", &raw.supply.erc20)?,
+			treasury: parse_amount("supply.treasury", &raw.supply.treasury)?,
+			endowment: parse_amount("supply.endowment", &raw.supply.endowment)?,
+			validator_stake: parse_amount("supply.validator_stake", &raw.supply.validator_stake)?,
+		};
+		let claims = raw
+			.claims
+			.iter()
+			.enumerate()
+			.map(|(i, claim)| {
+				Ok(Claim {
+					account: parse_account(&format!("claims[{}].account", i), &claim.account)?,
+					amount: parse_amount(&format!("claims[{}].amount", i), &claim.amount)?,
+				})
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+		let vesting = raw
+			.vesting
+			.iter()
+			.enumerate()
+			.map(|(i, vesting)| {
+				Ok(Vesting {
+					account: parse_account(&format!("vesting[{}].account", i), &vesting.account)?,
+					start: vesting.start,
+					period: vesting.period,
+					period_count: vesting.period_count,
+					per_period: parse_amount(
+						&format!("vesting[{}].per_period", i),
+						&vesting.per_period,
+					)?,
+				})
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+		let allocations = Self {
+			supply,
+			bridge: Bridge { operational: raw.bridge.operational },
+			claims,
+			vesting,
+		};
+		allocations.validate()?;
+		Ok(allocations)
+	}
+
+	/// Checks that the treasury, the claims and the ERC20 supply add up to the total supply, and
+	/// that vesting schedules only lock claimed tokens.
+	pub fn validate(&self) -> Result<(), Error> {
+		if self.supply.validator_stake > self.supply.endowment {
+			return Err(Error::Supply(format!(
+				"validators bond {} PDEX but are only endowed with {} PDEX",
+				format_amount(self.supply.validator_stake),
+				format_amount(self.supply.endowment),
+			)))
+		}
+
+		let mut claimed = BTreeMap::new();
+		for (i, claim) in self.claims.iter().enumerate() {
+			if claimed.insert(claim.account.clone(), claim.amount).is_some() {
+				return Err(Error::Invalid {
+					entry: format!("claims[{}].account", i),
+					reason: format!("{} already has a claim", claim.account),
+				})
+			}
+		}
+		let total_claims = self
+			.claims
+			.iter()
+			.try_fold(0, |total: Balance, claim| total.checked_add(claim.amount))
+			.ok_or_else(|| Error::Supply("claims overflow".into()))?;
+		let allocated = total_claims
+			.checked_add(self.supply.treasury)
+			.and_then(|allocated| allocated.checked_add(self.supply.erc20));
+		if allocated != Some(self.supply.total) {
+			return Err(Error::Supply(format!(
+				"treasury ({} PDEX), claims ({} PDEX) and ERC20 supply ({} PDEX) do not add up to \
+				 the total supply of {} PDEX",
+				format_amount(self.supply.treasury),
+				format_amount(total_claims),
+				format_amount(self.supply.erc20),
+				format_amount(self.supply.total),
+			)))
+		}
+
+		let mut vested = BTreeMap::<AccountId, Balance>::new();
+		for (i, vesting) in self.vesting.iter().enumerate() {
+			let entry = |field: &str| format!("vesting[{}].{}", i, field);
+			if vesting.period == 0 {
+				return Err(Error::Invalid {
+					entry: entry("period"),
+					reason: "must not be 0".into(),
+				})
+			}
+			if vesting.period_count == 0 {
+				return Err(Error::Invalid {
+					entry: entry("period_count"),
+					reason: "must not be 0".into(),
+				})
+			}
+			let locked = vesting
+				.per_period
+				.checked_mul(vesting.period_count.into())
+				.and_then(|locked| {
+					locked.checked_add(vested.get(&vesting.account).copied().unwrap_or_default())
+				})
+				.ok_or_else(|| Error::Invalid {
+					entry: entry("per_period"),
+					reason: "locks more than the total supply".into(),
+				})?;
+			let claim = claimed.get(&vesting.account).copied().unwrap_or_default();
+			if locked > claim {
+				return Err(Error::Invalid {
+					entry: entry("account"),
+					reason: format!(
+						"vesting schedules of {} lock {} PDEX but it only claims {} PDEX",
+						vesting.account,
+						format_amount(locked),
+						format_amount(claim),
+					),
+				})
+			}
+			vested.insert(vesting.account.clone(), locked);
+		}
+		Ok(())
+	}
+
+	/// Balances of the genesis: the treasury, the root key, the claims, the initial validators and
+	/// the `prefunded` accounts.
+	///
+	/// The endowments of the root key, of the validators and of the prefunded accounts are taken
+	/// from the treasury, so that the total supply does not depend on the number of authorities.
+	pub fn balances(
+		&self,
+		initial_authorities: &[AuthorityKeys],
+		root_key: Option<&AccountId>,
+		prefunded: &[AccountId],
+	) -> Result<Vec<(AccountId, Balance)>, Error> {
+		let endowed = initial_authorities.len() + root_key.iter().count() + prefunded.len();
+		let treasury = (endowed as Balance)
+			.checked_mul(self.supply.endowment)
+			.and_then(|endowments| self.supply.treasury.checked_sub(endowments))
+			.ok_or_else(|| {
+				Error::Supply(format!(
+					"treasury ({} PDEX) can not fund the endowments of {} accounts",
+					format_amount(self.supply.treasury),
+					endowed,
+				))
+			})?;
+
+		let mut balances = vec![(TreasuryPalletId::get().into_account(), treasury)];
+		balances.extend(root_key.map(|key| (key.clone(), self.supply.endowment)));
+		balances.extend(self.claims.iter().map(|claim| (claim.account.clone(), claim.amount)));
+		balances
+			.extend(initial_authorities.iter().map(|keys| (keys.0.clone(), self.supply.endowment)));
+		balances.extend(prefunded.iter().map(|account| (account.clone(), self.supply.endowment)));
+
+		let mut accounts = BTreeSet::new();
+		for (account, _) in &balances {
+			if !accounts.insert(account) {
+				return Err(Error::Supply(format!("{} is endowed more than once", account)))
+			}
+		}
+		Ok(balances)
+	}
+
+	/// Vesting schedules in the layout of the `orml_vesting` genesis.
+	pub fn vesting_schedules(&self) -> Vec<VestingSchedule> {
+		self.vesting
+			.iter()
+			.map(|vesting| {
+				(
+					vesting.account.clone(),
+					vesting.start,
+					vesting.period,
+					vesting.period_count,
+					vesting.per_period,
+				)
+			})
+			.collect()
+	}
+}
+
+/// Minimum number of initial members of the council and of the technical committee, unless
+/// there are none and governance is bootstrapped through democracy and the council elections.
+pub const MIN_GOVERNANCE_MEMBERS: usize = 3;
+
+/// Initial authorities, root key and governance of a network.
+#[derive(Clone, Debug)]
+pub struct Network {
+	/// Endowed.
+	pub root_key: Option<AccountId>,
+	pub authorities: Vec<AuthorityKeys>,
+	/// Initial members of the council and of the technical committee.
+	pub governance: Vec<AccountId>,
+}
+
+impl Network {
+	/// Udon test network, from `node/res/genesis/udon.toml`.
+	pub fn udon() -> Self {
+		Self::from_toml(UDON).unwrap_or_else(|e| panic!("{}", e))
+	}
+
+	/// Polkadex main network, from `node/res/genesis/mainnet.toml`.
+	pub fn mainnet() -> Self {
+		Self::from_toml(MAINNET).unwrap_or_else(|e| panic!("{}", e))
+	}
+
+	/// Parses a network in TOML.
+	pub fn from_toml(contents: &str) -> Result<Self, Error> {
+		let raw: RawNetwork = parse_toml(contents)?;
+		let root_key = raw.root_key.map(|key| parse_account("root_key", &key)).transpose()?;
+		let authorities = raw
+			.authorities
+			.iter()
+			.enumerate()
+			.map(|(i, authority)| {
+				let entry = |field: &str| format!("authorities[{}].{}", i, field);
+				Ok((
+					parse_account(&entry("stash"), &authority.stash)?,
+					parse_account(&entry("controller"), &authority.controller)?,
+					parse_key(&entry("grandpa"), &authority.grandpa)?.unchecked_into(),
+					parse_key(&entry("babe"), &authority.babe)?.unchecked_into(),
+					parse_key(&entry("im_online"), &authority.im_online)?.unchecked_into(),
+					parse_key(&entry("authority_discovery"), &authority.authority_discovery)?
+						.unchecked_into(),
+				))
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+		if authorities.is_empty() {
+			return Err(Error::Invalid {
+				entry: "authorities".into(),
+				reason: "at least one authority is required".into(),
+			})
+		}
+		let governance = raw
+			.governance
+			.iter()
+			.enumerate()
+			.map(|(i, member)| parse_account(&format!("governance[{}]", i), member))
+			.collect::<Result<Vec<_>, Error>>()?;
+		if !governance.is_empty() && governance.len() < MIN_GOVERNANCE_MEMBERS {
+			return Err(Error::Invalid {
+				entry: "governance".into(),
+				reason: format!(
+					"at least {} members are required, or none to bootstrap governance through \
+					 democracy",
+					MIN_GOVERNANCE_MEMBERS
+				),
+			})
+		}
+		if governance.iter().collect::<BTreeSet<_>>().len() != governance.len() {
+			return Err(Error::Invalid {
+				entry: "governance".into(),
+				reason: "members must be distinct".into(),
+			})
+		}
+		Ok(Self { root_key, authorities, governance })
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawAllocations {
+	supply: RawSupply,
+	bridge: RawBridge,
+	#[serde(default)]
+	claims: Vec<RawClaim>,
+	#[serde(default)]
+	vesting: Vec<RawVesting>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawSupply {
+	total: String,
+	erc20: String,
+	treasury: String,
+	endowment: String,
+	validator_stake: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawBridge {
+	operational: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawClaim {
+	account: String,
+	amount: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawVesting {
+	account: String,
+	start: u32,
+	period: u32,
+	period_count: u32,
+	per_period: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawNetwork {
+	root_key: Option<String>,
+	authorities: Vec<RawAuthority>,
+	#[serde(default)]
+	governance: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawAuthority {
+	stash: String,
+	controller: String,
+	grandpa: String,
+	babe: String,
+	im_online: String,
+	authority_discovery: String,
+}
+
+fn parse_toml<T: serde::de::DeserializeOwned>(contents: &str) -> Result<T, Error> {
+	toml::from_str(contents).map_err(|e| Error::Parse(e.to_string()))
+}
+
+/// Parses a 32 byte public key, given in hex with a `0x` prefix or as an SS58 address.
+fn parse_key(entry: &str, value: &str) -> Result<[u8; 32], Error> {
+	let invalid = |reason: String| Error::Invalid { entry: entry.into(), reason };
+	if value.starts_with("0x") {
+		let bytes = sp_core::bytes::from_hex(value)
+			.map_err(|e| invalid(format!("`{}` is not valid hex: {}", value, e)))?;
+		let len = bytes.len();
+		bytes
+			.try_into()
+			.map_err(|_| invalid(format!("`{}` has {} bytes instead of 32", value, len)))
+	} else {
+		AccountId::from_ss58check_with_version(value)
+			.map(|(account, _)| account.into())
+			.map_err(|e| invalid(format!("`{}` is not a valid SS58 address: {:?}", value, e)))
+	}
+}
+
+fn parse_account(entry: &str, value: &str) -> Result<AccountId, Error> {
+	parse_key(entry, value).map(AccountId::from)
+}
+
+/// Parses an amount of PDEX with up to 12 decimals, such as `"3655827.5"`.
+pub fn parse_amount(entry: &str, value: &str) -> Result<Balance, Error> {
+	let invalid = |reason: &str| Error::Invalid {
+		entry: entry.into(),
+		reason: format!("`{}` {}", value, reason),
+	};
+	let (units, decimals) = value.split_once('.').unwrap_or((value, ""));
+	let is_number = |digits: &str| digits.bytes().all(|digit| digit.is_ascii_digit());
+	if units.is_empty() || !is_number(units) || !is_number(decimals) {
+		return Err(invalid("is not an amount of PDEX"))
+	}
+	if decimals.len() > DECIMALS {
+		return Err(invalid("has more than 12 decimals"))
+	}
+	let units: Balance = units.parse().map_err(|_| invalid("is too large"))?;
+	let decimals: Balance = format!("{:0<width$}", decimals, width = DECIMALS)
+		.parse()
+		.expect("at most 12 digits; qed");
+	units
+		.checked_mul(PDEX)
+		.and_then(|amount| amount.checked_add(decimals))
+		.ok_or_else(|| invalid("is too large"))
+}
+
+/// Formats an amount of PDEX with its decimals, the inverse of `parse_amount`.
+pub fn format_amount(amount: Balance) -> String {
+	let decimals = amount % PDEX;
+	if decimals == 0 {
+		return format!("{}", amount / PDEX)
+	}
+	let decimals = format!("{:0>width$}", decimals, width = DECIMALS);
+	format!("{}.{}", amount / PDEX, decimals.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+	use codec::Encode;
+	use hex_literal::hex;
+	use sp_core::hashing::blake2_256;
+
+	use super::*;
+
+	const EXAMPLE: &str = r#"
+		[supply]
+		total = "1000"
+		erc20 = "100"
+		treasury = "600"
+		endowment = "10"
+		validator_stake = "2"
+
+		[bridge]
+		operational = false
+
+		[[claims]]
+		account = "0xe4cdc8abc0405db44c1a6886a2f2c59012fa3b98c07b61d63cc7f9e437ba243e"
+		amount = "299.5"
+
+		[[claims]]
+		account = "5Fbsd6WXDGiLTxunqeK5BATNiocfCqu9bS1yArVjCgeBLkVy"
+		amount = "0.5"
+
+		[[vesting]]
+		account = "0xe4cdc8abc0405db44c1a6886a2f2c59012fa3b98c07b61d63cc7f9e437ba243e"
+		start = 0
+		period = 10
+		period_count = 2
+		per_period = "100"
+	"#;
+
+	fn error_of(allocations: &str) -> Error {
+		Allocations::from_toml(allocations).unwrap_err()
+	}
+
+	#[test]
+	fn decimals_match_pdex() {
+		assert_eq!(10u128.pow(DECIMALS as u32), PDEX);
+	}
+
+	#[test]
+	fn polkadex_allocations_are_valid() {
+		let allocations = Allocations::polkadex();
+		assert_eq!(allocations.supply.total, 20_000_000 * PDEX);
+		assert_eq!(allocations.supply.erc20, 3_172_895 * PDEX);
+		assert_eq!(
+			allocations.claims.iter().map(|claim| claim.amount).sum::<Balance>(),
+			6_627_105 * PDEX
+		);
+		assert_eq!(allocations.claims.len(), 41);
+		assert_eq!(allocations.vesting.len(), 73);
+		assert!(!allocations.bridge.operational);
+	}
+
+	#[test]
+	fn polkadex_allocations_match_the_former_chain_spec() {
+		// Digests of the claims and of the vesting schedules hardcoded in the chain spec before
+		// they moved to `res/genesis/allocations.toml`.
+		let allocations = Allocations::polkadex();
+		let claims: Vec<_> =
+			allocations.claims.iter().map(|claim| (claim.account.clone(), claim.amount)).collect();
+		assert_eq!(
+			blake2_256(&claims.encode()),
+			hex!["ed068afe84b87397b85c224a787edd0145409ba0510290cfc71488e1f37af7db"]
+		);
+		let vesting = allocations.vesting_schedules();
+		assert_eq!(
+			blake2_256(&vesting.encode()),
+			hex!["05573f25029f8f6302e2ba94de0c5d2dda3b79fe19554b33c516aa020040ddbe"]
+		);
+		assert_eq!(
+			vesting
+				.iter()
+				.map(|(_, _, _, period_count, per_period)| *period_count as Balance * per_period)
+				.sum::<Balance>(),
+			5_743_287 * PDEX + PDEX / 2
+		);
+	}
+
+	#[test]
+	fn polkadex_balances_add_up_to_supply_without_erc20() {
+		let allocations = Allocations::polkadex();
+		let network = Network::udon();
+		let balances = allocations
+			.balances(&network.authorities, network.root_key.as_ref(), &[])
+			.unwrap();
+		assert_eq!(
+			balances.iter().map(|(_, balance)| balance).sum::<Balance>(),
+			20_000_000 * PDEX - 3_172_895 * PDEX
+		);
+		assert_eq!(balances[0], (TreasuryPalletId::get().into_account(), 10_199_700 * PDEX));
+	}
+
+	#[test]
+	fn networks_are_valid() {
+		let udon = Network::udon();
+		assert_eq!(udon.authorities.len(), 2);
+		assert_eq!(
+			udon.root_key,
+			Some(
+				AccountId::from_ss58check("5Ggr5JRSxCSZvwTc9Xkjca5bWkkmG1btufW22uLm5tArfV9y")
+					.unwrap()
+			)
+		);
+		let mainnet = Network::mainnet();
+		assert_eq!(mainnet.authorities.len(), 2);
+		assert_ne!(mainnet.root_key, udon.root_key);
+	}
+
+	#[test]
+	fn governance_is_empty_or_has_several_members() {
+		let with_governance = |members: &[&str]| {
+			let members =
+				members.iter().map(|member| format!("\"{}\"", member)).collect::<Vec<_>>();
+			UDON.replace("governance = []", &format!("governance = [{}]", members.join(", ")))
+		};
+		let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+		let bob = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+		let charlie = "5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y";
+
+		assert!(Network::udon().governance.is_empty());
+		assert!(Network::mainnet().governance.is_empty());
+		let network = Network::from_toml(&with_governance(&[alice, bob, charlie])).unwrap();
+		assert_eq!(network.governance.len(), 3);
+		assert!(matches!(
+			Network::from_toml(&with_governance(&[alice])).unwrap_err(),
+			Error::Invalid { entry, .. } if entry == "governance"
+		));
+		assert!(matches!(
+			Network::from_toml(&with_governance(&[alice, bob, alice])).unwrap_err(),
+			Error::Invalid { entry, .. } if entry == "governance"
+		));
+	}
+
+	#[test]
+	fn hex_and_ss58_accounts_are_parsed() {
+		let allocations = Allocations::from_toml(EXAMPLE).unwrap();
+		assert_eq!(
+			allocations.claims[1].account,
+			parse_account("", "0x9c7a2ee14e565db0c69f78c7b4cd839fbf52b607d867e9e9c5a79042898a0d12")
+				.unwrap()
+		);
+		assert_eq!(allocations.claims[0].amount, 299 * PDEX + PDEX / 2);
+		assert_eq!(
+			allocations.vesting_schedules(),
+			vec![(allocations.claims[0].account.clone(), 0, 10, 2, 100 * PDEX)]
+		);
+	}
+
+	#[test]
+	fn amounts_are_parsed_and_formatted() {
+		assert_eq!(parse_amount("", "3655827.5"), Ok(36558275 * (PDEX / 10)));
+		assert_eq!(parse_amount("", "0.000000000001"), Ok(1));
+		assert_eq!(format_amount(36558275 * (PDEX / 10)), "3655827.5");
+		assert_eq!(format_amount(20_000_000 * PDEX), "20000000");
+		assert_eq!(format_amount(1), "0.000000000001");
+		assert_eq!(
+			parse_amount("supply.total", "0.0000000000001"),
+			Err(Error::Invalid {
+				entry: "supply.total".into(),
+				reason: "`0.0000000000001` has more than 12 decimals".into()
+			})
+		);
+		assert!(parse_amount("", "1e6").is_err());
+		assert!(parse_amount("", ".5").is_err());
+		assert!(parse_amount("", "-1").is_err());
+		assert!(parse_amount("", "340282366920938463463374607431768211455").is_err());
+	}
+
+	#[test]
+	fn invalid_accounts_are_reported() {
+		assert!(matches!(
+			error_of(&EXAMPLE.replace("0xe4cdc8ab", "0xe4cd")),
+			Error::Invalid { entry, .. } if entry == "claims[0].account"
+		));
+		assert!(matches!(
+			error_of(&EXAMPLE.replace("0xe4cdc8ab", "0xg4cdc8ab")),
+			Error::Invalid { entry, .. } if entry == "claims[0].account"
+		));
+		assert!(matches!(
+			error_of(&EXAMPLE.replace("5Fbsd6WX", "5Fbsd6WY")),
+			Error::Invalid { entry, .. } if entry == "claims[1].account"
+		));
+	}
+
+	#[test]
+	fn unknown_fields_are_rejected() {
+		assert!(matches!(
+			error_of(&EXAMPLE.replace("validator_stake", "validator_stakes")),
+			Error::Parse(_)
+		));
+	}
+
+	#[test]
+	fn supply_must_add_up() {
+		assert_eq!(
+			error_of(&EXAMPLE.replace("erc20 = \"100\"", "erc20 = \"99\"")),
+			Error::Supply(
+				"treasury (600 PDEX), claims (300 PDEX) and ERC20 supply (99 PDEX) do not add \
+				 up to the total supply of 1000 PDEX"
+					.into()
+			)
+		);
+	}
+
+	#[test]
+	fn vesting_must_not_exceed_claims() {
+		assert_eq!(
+			error_of(&EXAMPLE.replace("period_count = 2", "period_count = 3")),
+			Error::Invalid {
+				entry: "vesting[0].account".into(),
+				reason: format!(
+					"vesting schedules of {} lock 300 PDEX but it only claims 299.5 PDEX",
+					Allocations::from_toml(EXAMPLE).unwrap().claims[0].account
+				),
+			}
+		);
+		assert!(matches!(
+			error_of(&EXAMPLE.replace("period = 10", "period = 0")),
+			Error::Invalid { entry, .. } if entry == "vesting[0].period"
+		));
+	}
+
+	#[test]
+	fn accounts_are_endowed_once() {
+		let allocations = Allocations::from_toml(EXAMPLE).unwrap();
+		let authorities = vec![crate::chain_spec::authority_keys_from_seed("Alice")];
+		assert_eq!(
+			allocations.balances(&authorities, None, &[authorities[0].0.clone()]),
+			Err(Error::Supply(format!("{} is endowed more than once", authorities[0].0)))
+		);
+	}
+
+	#[test]
+	fn treasury_must_fund_endowments() {
+		let allocations = Allocations::from_toml(EXAMPLE).unwrap();
+		let authorities = (0..60)
+			.map(|i| crate::chain_spec::authority_keys_from_seed(&i.to_string()))
+			.collect::<Vec<_>>();
+		assert!(allocations.balances(&authorities[..60], None, &[]).is_ok());
+		assert!(allocations
+			.balances(&authorities[..59], None, &[authorities[0].1.clone()])
+			.is_ok());
+		assert_eq!(
+			allocations.balances(&authorities, Some(&authorities[0].1), &[]),
+			Err(Error::Supply(
+				"treasury (600 PDEX) can not fund the endowments of 61 accounts".into()
+			))
+		);
+	}
+}
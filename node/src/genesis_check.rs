@@ -0,0 +1,455 @@
+//! The `genesis-check` subcommand, which audits the economics of the genesis of a chain spec.
+//!
+//! The genesis storage is built from the chain spec, raw or not, and read back through the
+//! runtime pallets, so that the report describes the state the chain actually starts from.
+
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fmt,
+};
+
+use frame_support::BasicExternalities;
+use node_polkadex_runtime::{
+	Balances, Council, PDEXMigration, Runtime, TechnicalCommittee, Treasury,
+};
+use polkadex_primitives::{AccountId, Balance};
+use sc_cli::SharedParams;
+use serde::{Serialize, Serializer};
+use sp_core::storage::Storage;
+use sp_runtime::BuildStorage;
+
+use crate::genesis::{format_amount, parse_amount, Allocations};
+
+/// The `genesis-check` command.
+#[derive(Debug, clap::Parser)]
+pub struct GenesisCheckCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	/// Print the report as JSON.
+	#[clap(long)]
+	pub json: bool,
+
+	/// Expected total supply in PDEX, including the ERC20 tokens that are still to be minted.
+	/// Defaults to the total supply of `node/res/genesis/allocations.toml`.
+	#[clap(long, value_name = "PDEX", parse(try_from_str = parse_pdex))]
+	pub total_supply: Option<Balance>,
+
+	/// Expected ERC20 supply in PDEX, mintable through pdex-migration. Defaults to the ERC20
+	/// supply of `node/res/genesis/allocations.toml`.
+	#[clap(long, value_name = "PDEX", parse(try_from_str = parse_pdex))]
+	pub erc20_supply: Option<Balance>,
+}
+
+pub(crate) fn parse_pdex(value: &str) -> Result<Balance, String> {
+	parse_amount("PDEX", value).map_err(|e| e.to_string())
+}
+
+impl GenesisCheckCmd {
+	/// Prints the report of the genesis of `spec`, and fails if any invariant is violated.
+	pub fn run(&self, spec: Box<dyn sc_service::ChainSpec>) -> sc_cli::Result<()> {
+		let storage = spec.as_storage_builder().build_storage()?;
+		let supply = Allocations::polkadex().supply;
+		let report = Report::new(
+			storage,
+			self.total_supply.unwrap_or(supply.total),
+			self.erc20_supply.unwrap_or(supply.erc20),
+		);
+		if self.json {
+			let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+			println!("{}", json);
+		} else {
+			print!("{}", report);
+		}
+		match report.violations.len() {
+			0 => Ok(()),
+			violations => Err(format!(
+				"{} invariants are violated by the genesis of {}",
+				violations,
+				spec.id()
+			)
+			.into()),
+		}
+	}
+}
+
+/// Amount of PDEX, serialized as a decimal string such as `"3655827.5"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Amount(pub Balance);
+
+impl Serialize for Amount {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&format_amount(self.0))
+	}
+}
+
+impl fmt::Display for Amount {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} PDEX", format_amount(self.0))
+	}
+}
+
+/// Category of the accounts endowed at genesis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Category {
+	/// The treasury account.
+	Treasury,
+	/// Stashes and controllers of the validators.
+	Validators,
+	/// Members of the council and of the technical committee.
+	Governance,
+	/// Accounts with vesting schedules.
+	Vested,
+	/// Any other account.
+	Other,
+}
+
+impl fmt::Display for Category {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let category = match self {
+			Category::Treasury => "treasury",
+			Category::Validators => "validators",
+			Category::Governance => "governance",
+			Category::Vested => "vested",
+			Category::Other => "other",
+		};
+		write!(f, "{}", category)
+	}
+}
+
+/// Balances of the accounts of a category.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Allocation {
+	pub category: Category,
+	pub accounts: u32,
+	pub amount: Amount,
+}
+
+/// Vesting schedules with the same start, period and period count.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tranche {
+	pub start: u32,
+	pub period: u32,
+	pub period_count: u32,
+	pub schedules: u32,
+	pub locked: Amount,
+}
+
+/// Stake bonded by a validator.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorStake {
+	pub stash: AccountId,
+	pub controller: AccountId,
+	pub bonded: Amount,
+}
+
+/// Economics of a genesis.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+	pub total_issuance: Amount,
+	/// ERC20 tokens that pdex-migration can still mint.
+	pub mintable_tokens: Amount,
+	pub bridge_operational: bool,
+	pub treasury: Amount,
+	pub allocations: Vec<Allocation>,
+	pub vesting: Vec<Tranche>,
+	pub validators: Vec<ValidatorStake>,
+	/// Invariants violated by the genesis, empty if it is sound.
+	pub violations: Vec<String>,
+}
+
+impl Report {
+	/// Reports on the genesis `storage`, expecting `total_supply` PDEX of which `erc20_supply` are
+	/// still to be minted by pdex-migration.
+	pub fn new(storage: Storage, total_supply: Balance, erc20_supply: Balance) -> Self {
+		BasicExternalities::new(storage)
+			.execute_with(|| Self::from_state(total_supply, erc20_supply))
+	}
+
+	fn from_state(total_supply: Balance, erc20_supply: Balance) -> Self {
+		let mut violations = vec![];
+
+		let treasury_account = Treasury::account_id();
+		let validators = pallet_staking::Ledger::<Runtime>::iter()
+			.map(|(controller, ledger)| ValidatorStake {
+				stash: ledger.stash,
+				controller,
+				bonded: Amount(ledger.total),
+			})
+			.collect::<Vec<_>>();
+		let validator_accounts = validators
+			.iter()
+			.flat_map(|validator| [validator.stash.clone(), validator.controller.clone()])
+			.collect::<BTreeSet<_>>();
+		let governance = Council::members()
+			.into_iter()
+			.chain(TechnicalCommittee::members())
+			.collect::<BTreeSet<_>>();
+
+		let mut tranches = BTreeMap::<(u32, u32, u32), (u32, Balance)>::new();
+		let mut vested = BTreeSet::new();
+		for (who, schedules) in orml_vesting::VestingSchedules::<Runtime>::iter() {
+			let mut locked: Balance = 0;
+			for schedule in schedules {
+				let amount = schedule.per_period.saturating_mul(schedule.period_count.into());
+				let tranche = tranches
+					.entry((schedule.start, schedule.period, schedule.period_count))
+					.or_default();
+				tranche.0 += 1;
+				tranche.1 = tranche.1.saturating_add(amount);
+				locked = locked.saturating_add(amount);
+			}
+			let free = Balances::free_balance(&who);
+			if locked > free {
+				violations.push(format!(
+					"vesting schedules of {} lock {} but it only has {}",
+					who,
+					Amount(locked),
+					Amount(free)
+				));
+			}
+			vested.insert(who);
+		}
+
+		let mut allocations = BTreeMap::<Category, (u32, Balance)>::new();
+		let mut held: Balance = 0;
+		for (who, account) in frame_system::Account::<Runtime>::iter() {
+			let balance = account.data.free.saturating_add(account.data.reserved);
+			let category = if who == treasury_account {
+				Category::Treasury
+			} else if validator_accounts.contains(&who) {
+				Category::Validators
+			} else if governance.contains(&who) {
+				Category::Governance
+			} else if vested.contains(&who) {
+				Category::Vested
+			} else {
+				Category::Other
+			};
+			let allocation = allocations.entry(category).or_default();
+			allocation.0 += 1;
+			allocation.1 = allocation.1.saturating_add(balance);
+			held = held.saturating_add(balance);
+		}
+
+		if validators.is_empty() {
+			violations.push("no validator is bonded".into());
+		}
+		for validator in &validators {
+			let free = Balances::free_balance(&validator.stash);
+			if validator.bonded.0 > free {
+				violations.push(format!(
+					"validator {} bonds {} but only has {}",
+					validator.stash,
+					validator.bonded,
+					Amount(free)
+				));
+			}
+		}
+
+		let total_issuance = Balances::total_issuance();
+		let mintable_tokens = PDEXMigration::mintable_tokens();
+		if total_issuance != held {
+			violations.push(format!(
+				"total issuance of {} differs from the {} held by accounts",
+				Amount(total_issuance),
+				Amount(held)
+			));
+		}
+		if total_issuance.checked_add(mintable_tokens) != Some(total_supply) {
+			violations.push(format!(
+				"total issuance of {} and {} mintable by pdex-migration do not add up to the \
+				 total supply of {}",
+				Amount(total_issuance),
+				Amount(mintable_tokens),
+				Amount(total_supply)
+			));
+		}
+		if mintable_tokens != erc20_supply {
+			violations.push(format!(
+				"pdex-migration can mint {} instead of the ERC20 supply of {}",
+				Amount(mintable_tokens),
+				Amount(erc20_supply)
+			));
+		}
+
+		Self {
+			total_issuance: Amount(total_issuance),
+			mintable_tokens: Amount(mintable_tokens),
+			bridge_operational: PDEXMigration::operational(),
+			treasury: Amount(Balances::free_balance(&treasury_account)),
+			allocations: allocations
+				.into_iter()
+				.map(|(category, (accounts, amount))| Allocation {
+					category,
+					accounts,
+					amount: Amount(amount),
+				})
+				.collect(),
+			vesting: tranches
+				.into_iter()
+				.map(|((start, period, period_count), (schedules, locked))| Tranche {
+					start,
+					period,
+					period_count,
+					schedules,
+					locked: Amount(locked),
+				})
+				.collect(),
+			validators,
+			violations,
+		}
+	}
+}
+
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "Total issuance: {}", self.total_issuance)?;
+		writeln!(
+			f,
+			"Mintable by pdex-migration: {} (bridge {})",
+			self.mintable_tokens,
+			if self.bridge_operational { "operational" } else { "not operational" }
+		)?;
+		writeln!(f, "Treasury: {}", self.treasury)?;
+
+		writeln!(f, "\nAllocations:")?;
+		for allocation in &self.allocations {
+			writeln!(
+				f,
+				"  {:<12} {:>4} accounts  {}",
+				allocation.category.to_string(),
+				allocation.accounts,
+				allocation.amount
+			)?;
+		}
+
+		writeln!(f, "\nVesting tranches:")?;
+		for tranche in &self.vesting {
+			writeln!(
+				f,
+				"  from block {:>8}, {} x {} blocks  {:>4} schedules  {}",
+				tranche.start,
+				tranche.period_count,
+				tranche.period,
+				tranche.schedules,
+				tranche.locked
+			)?;
+		}
+
+		writeln!(f, "\nValidator stakes:")?;
+		for validator in &self.validators {
+			writeln!(
+				f,
+				"  {} (controller {})  {}",
+				validator.stash, validator.controller, validator.bonded
+			)?;
+		}
+
+		writeln!(f, "\nInvariants:")?;
+		if self.violations.is_empty() {
+			writeln!(f, "  all invariants hold")?;
+		}
+		for violation in &self.violations {
+			writeln!(f, "  violated: {}", violation)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use node_polkadex_runtime::constants::currency::PDEX;
+	use sc_service::ChainSpec;
+	use sp_core::sr25519;
+
+	use super::*;
+	use crate::chain_spec::{
+		self, authority_keys_from_seed, get_account_id_from_seed, testnet_genesis,
+	};
+
+	const TOTAL_SUPPLY: Balance = 20_000_000 * PDEX;
+	const ERC20_SUPPLY: Balance = 3_172_895 * PDEX;
+
+	fn genesis() -> node_polkadex_runtime::GenesisConfig {
+		testnet_genesis(
+			vec![authority_keys_from_seed("Alice")],
+			vec![],
+			Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+		)
+	}
+
+	#[test]
+	fn testnet_genesis_is_sound() {
+		let report = Report::new(genesis().build_storage().unwrap(), TOTAL_SUPPLY, ERC20_SUPPLY);
+		assert_eq!(report.violations, Vec::<String>::new());
+		assert_eq!(report.total_issuance, Amount(TOTAL_SUPPLY - ERC20_SUPPLY));
+		assert_eq!(report.mintable_tokens, Amount(ERC20_SUPPLY));
+		assert_eq!(report.treasury, Amount(10_200_000 * PDEX - 2 * 100 * PDEX));
+		assert_eq!(report.validators.len(), 1);
+		assert_eq!(report.validators[0].bonded, Amount(2 * PDEX));
+		assert_eq!(
+			report.vesting.iter().map(|tranche| tranche.locked.0).sum::<Balance>(),
+			Allocations::polkadex()
+				.vesting
+				.iter()
+				.map(|vesting| vesting.per_period * vesting.period_count as Balance)
+				.sum::<Balance>()
+		);
+		assert_eq!(
+			report.allocations.iter().map(|allocation| allocation.amount.0).sum::<Balance>(),
+			TOTAL_SUPPLY - ERC20_SUPPLY
+		);
+	}
+
+	#[test]
+	fn named_specs_are_sound() {
+		for spec in [
+			chain_spec::development_config(),
+			chain_spec::soba_testnet_config(),
+			chain_spec::udon_testnet_config(),
+			chain_spec::mainnet_testnet_config(),
+		] {
+			let report = Report::new(spec.build_storage().unwrap(), TOTAL_SUPPLY, ERC20_SUPPLY);
+			assert_eq!(report.violations, Vec::<String>::new(), "{}", spec.id());
+		}
+	}
+
+	#[test]
+	fn violations_are_reported() {
+		let mut genesis = genesis();
+		genesis.pdex_migration.max_tokens = ERC20_SUPPLY - PDEX;
+		let report = Report::new(genesis.build_storage().unwrap(), TOTAL_SUPPLY, ERC20_SUPPLY);
+		assert_eq!(
+			report.violations,
+			vec![
+				"total issuance of 16827105 PDEX and 3172894 PDEX mintable by pdex-migration do \
+				 not add up to the total supply of 20000000 PDEX",
+				"pdex-migration can mint 3172894 PDEX instead of the ERC20 supply of 3172895 PDEX",
+			]
+		);
+	}
+
+	#[test]
+	fn expected_supply_is_configurable() {
+		let report =
+			Report::new(genesis().build_storage().unwrap(), 21_000_000 * PDEX, ERC20_SUPPLY);
+		assert_eq!(report.violations.len(), 1);
+	}
+
+	#[test]
+	fn report_is_serialized_to_json() {
+		let report = Report::new(genesis().build_storage().unwrap(), TOTAL_SUPPLY, ERC20_SUPPLY);
+		let json = serde_json::to_value(&report).unwrap();
+		assert_eq!(json["totalIssuance"], "16827105");
+		assert_eq!(json["mintableTokens"], "3172895");
+		assert_eq!(json["allocations"][0]["category"], "treasury");
+		assert_eq!(json["violations"], serde_json::json!([]));
+	}
+}
@@ -0,0 +1,474 @@
+//! The `inspect` subcommand, which decodes blocks and extrinsics with the native runtime.
+//!
+//! Blocks are read from the local database by number or hash, or decoded from their hex encoded
+//! SCALE encoding. Extrinsics are addressed by their block and index, or decoded from hex as well.
+
+use std::{fmt, str::FromStr, sync::Arc};
+
+use codec::{Compact, Decode, Encode};
+use node_polkadex_runtime::{Block, BlockNumber, Event, Hash, Index, UncheckedExtrinsic};
+use polkadex_primitives::Balance;
+use sc_cli::{CliConfiguration, ImportParams, SharedParams};
+use sc_client_api::{BlockBackend, HeaderBackend, StorageProvider};
+use serde::Serialize;
+use sp_core::{hashing::twox_128, storage::StorageKey, H256};
+use sp_runtime::{
+	generic::{BlockId, Era},
+	traits::{BlakeTwo256, Block as BlockT, Hash as HashT, Header as HeaderT},
+	MultiAddress,
+};
+
+use crate::{genesis_check::Amount, service::FullClient};
+
+/// The `inspect` command.
+#[derive(Debug, clap::Parser)]
+pub struct InspectCmd {
+	#[allow(missing_docs)]
+	#[clap(subcommand)]
+	pub command: InspectSubCmd,
+
+	/// Print the decoded blocks and extrinsics as JSON.
+	#[clap(long)]
+	pub json: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: ImportParams,
+}
+
+/// What to decode.
+#[derive(Debug, clap::Subcommand)]
+pub enum InspectSubCmd {
+	/// Decode a block with the native runtime and print its extrinsics.
+	Block {
+		/// Number or hash of a block in the database, or a hex encoded SCALE encoded block.
+		input: String,
+
+		/// Also print the events emitted by the block, which must be in the database.
+		#[clap(long)]
+		events: bool,
+	},
+	/// Decode an extrinsic with the native runtime.
+	Extrinsic {
+		/// `<block>:<index>` of an extrinsic in the database, `<block>` being a number or a hash,
+		/// or a hex encoded SCALE encoded extrinsic.
+		input: String,
+	},
+}
+
+impl CliConfiguration for InspectCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
+
+impl InspectCmd {
+	/// Decodes and prints the block or the extrinsic of the command.
+	pub fn run(&self, client: Arc<FullClient>) -> sc_cli::Result<()> {
+		let inspector = Inspector { client };
+		match &self.command {
+			InspectSubCmd::Block { input, events } => {
+				let block = inspector.block(input.parse()?, *events)?;
+				self.print(&block)
+			},
+			InspectSubCmd::Extrinsic { input } => {
+				let extrinsic = inspector.extrinsic(input.parse()?)?;
+				self.print(&extrinsic)
+			},
+		}
+	}
+
+	fn print<T: Serialize + fmt::Display>(&self, value: &T) -> sc_cli::Result<()> {
+		if self.json {
+			let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+			println!("{}", json);
+		} else {
+			print!("{}", value);
+		}
+		Ok(())
+	}
+}
+
+/// Block in the database, or encoded block.
+#[derive(Debug, PartialEq)]
+pub enum BlockAddress {
+	Number(BlockNumber),
+	Hash(Hash),
+	Bytes(Vec<u8>),
+}
+
+impl FromStr for BlockAddress {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Ok(number) = s.parse() {
+			return Ok(BlockAddress::Number(number))
+		}
+		let bytes = sp_core::bytes::from_hex(s)
+			.map_err(|e| format!("`{}` is neither a block number nor hex: {}", s, e))?;
+		if bytes.len() == Hash::len_bytes() {
+			Ok(BlockAddress::Hash(Hash::from_slice(&bytes)))
+		} else {
+			Ok(BlockAddress::Bytes(bytes))
+		}
+	}
+}
+
+/// Extrinsic of a block in the database, or encoded extrinsic.
+#[derive(Debug, PartialEq)]
+pub enum ExtrinsicAddress {
+	Block(BlockAddress, usize),
+	Bytes(Vec<u8>),
+}
+
+impl FromStr for ExtrinsicAddress {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some((block, index)) = s.split_once(':') {
+			let index = index
+				.parse()
+				.map_err(|e| format!("`{}` is not an extrinsic index: {}", index, e))?;
+			return Ok(ExtrinsicAddress::Block(block.parse()?, index))
+		}
+		sp_core::bytes::from_hex(s)
+			.map(ExtrinsicAddress::Bytes)
+			.map_err(|e| format!("`{}` is neither `<block>:<index>` nor hex: {}", s, e))
+	}
+}
+
+/// Decoded block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockInfo {
+	pub number: BlockNumber,
+	pub hash: Hash,
+	pub parent_hash: Hash,
+	pub state_root: Hash,
+	pub extrinsics_root: Hash,
+	pub extrinsics: Vec<ExtrinsicInfo>,
+	/// Events emitted by the block, if they were asked for.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub events: Option<Vec<EventInfo>>,
+}
+
+/// Decoded extrinsic.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtrinsicInfo {
+	/// Index of the extrinsic in its block, if it was read from the database.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub index: Option<usize>,
+	pub hash: Hash,
+	/// Signer, nonce, tip and era of a signed extrinsic.
+	pub signed: Option<Signed>,
+	/// The call, as formatted by its `Debug` implementation.
+	pub call: String,
+}
+
+/// Signed extension data of an extrinsic.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Signed {
+	/// SS58 address of the signer, or its index.
+	pub signer: String,
+	pub nonce: Index,
+	pub tip: Amount,
+	pub era: Era,
+}
+
+/// Decoded event.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventInfo {
+	/// Phase of the block in which the event was emitted, such as `ApplyExtrinsic(1)`.
+	pub phase: String,
+	/// The event, as formatted by its `Debug` implementation.
+	pub event: String,
+	pub topics: Vec<Hash>,
+}
+
+impl BlockInfo {
+	/// Decodes the header and the extrinsics of `block`.
+	pub fn new(block: &Block, events: Option<Vec<EventInfo>>) -> Self {
+		let header = block.header();
+		Self {
+			number: *header.number(),
+			hash: header.hash(),
+			parent_hash: *header.parent_hash(),
+			state_root: *header.state_root(),
+			extrinsics_root: *header.extrinsics_root(),
+			extrinsics: block
+				.extrinsics()
+				.iter()
+				.enumerate()
+				.map(|(index, extrinsic)| ExtrinsicInfo::new(Some(index), extrinsic))
+				.collect(),
+			events,
+		}
+	}
+}
+
+impl ExtrinsicInfo {
+	/// Decodes the signed extension data of `extrinsic`.
+	pub fn new(index: Option<usize>, extrinsic: &UncheckedExtrinsic) -> Self {
+		let signed = extrinsic.signature.as_ref().map(|(address, _, extra)| {
+			// Tip and era are private fields of their signed extensions, but lead their encoding.
+			let tip = Compact::<Balance>::decode(&mut &extra.7.encode()[..])
+				.map(|tip| tip.0)
+				.unwrap_or_default();
+			let era = Era::decode(&mut &extra.3.encode()[..]).unwrap_or(Era::Immortal);
+			Signed {
+				signer: match address {
+					MultiAddress::Id(account) => account.to_string(),
+					MultiAddress::Index(index) => format!("index {}", index),
+					address => format!("{:?}", address),
+				},
+				nonce: extra.4 .0,
+				tip: Amount(tip),
+				era,
+			}
+		});
+		Self {
+			index,
+			hash: BlakeTwo256::hash_of(extrinsic),
+			signed,
+			call: format!("{:?}", extrinsic.function),
+		}
+	}
+}
+
+struct Inspector {
+	client: Arc<FullClient>,
+}
+
+impl Inspector {
+	fn block(&self, address: BlockAddress, events: bool) -> sc_cli::Result<BlockInfo> {
+		let (block, hash) = match address {
+			BlockAddress::Bytes(bytes) => {
+				if events {
+					return Err("Events are only available for blocks in the database".into())
+				}
+				let block = Block::decode(&mut &bytes[..])
+					.map_err(|e| format!("Invalid encoded block: {}", e))?;
+				(block, None)
+			},
+			address => {
+				let hash = self.hash(address)?;
+				(self.stored_block(hash)?, Some(hash))
+			},
+		};
+		let events = match hash {
+			Some(hash) if events => Some(self.events(hash)?),
+			_ => None,
+		};
+		Ok(BlockInfo::new(&block, events))
+	}
+
+	fn extrinsic(&self, address: ExtrinsicAddress) -> sc_cli::Result<ExtrinsicInfo> {
+		match address {
+			ExtrinsicAddress::Bytes(bytes) => {
+				let extrinsic = UncheckedExtrinsic::decode(&mut &bytes[..])
+					.map_err(|e| format!("Invalid encoded extrinsic: {}", e))?;
+				Ok(ExtrinsicInfo::new(None, &extrinsic))
+			},
+			ExtrinsicAddress::Block(address, index) => {
+				let block = match address {
+					BlockAddress::Bytes(bytes) => Block::decode(&mut &bytes[..])
+						.map_err(|e| format!("Invalid encoded block: {}", e))?,
+					address => self.stored_block(self.hash(address)?)?,
+				};
+				let extrinsic = block.extrinsics().get(index).ok_or_else(|| {
+					format!(
+						"Block #{} has {} extrinsics, there is no extrinsic {}",
+						block.header().number(),
+						block.extrinsics().len(),
+						index
+					)
+				})?;
+				Ok(ExtrinsicInfo::new(Some(index), extrinsic))
+			},
+		}
+	}
+
+	fn hash(&self, address: BlockAddress) -> sc_cli::Result<Hash> {
+		match address {
+			BlockAddress::Hash(hash) => Ok(hash),
+			BlockAddress::Number(number) => self
+				.client
+				.hash(number)?
+				.ok_or_else(|| format!("Block #{} is not in the database", number).into()),
+			BlockAddress::Bytes(_) => Err("Encoded blocks have no hash in the database".into()),
+		}
+	}
+
+	/// Block `hash` of the database, decoded with the runtime types.
+	fn stored_block(&self, hash: Hash) -> sc_cli::Result<Block> {
+		let block = self
+			.client
+			.block(&BlockId::Hash(hash))?
+			.ok_or_else(|| format!("Block {:?} is not in the database", hash))?
+			.block;
+		// The database holds opaque extrinsics, whose encoding is the one of the runtime.
+		Block::decode(&mut &block.encode()[..])
+			.map_err(|e| format!("Block {:?} can not be decoded: {}", hash, e).into())
+	}
+
+	/// Events emitted by block `hash`, read from the state after the block.
+	fn events(&self, hash: Hash) -> sc_cli::Result<Vec<EventInfo>> {
+		let key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+		let events = match self.client.storage(&BlockId::Hash(hash), &key)? {
+			Some(data) => {
+				Vec::<frame_system::EventRecord<Event, H256>>::decode(&mut &data.0[..])
+					.map_err(|e| format!("Events of block {:?} can not be decoded: {}", hash, e))?
+			},
+			None => vec![],
+		};
+		Ok(events
+			.into_iter()
+			.map(|record| EventInfo {
+				phase: format!("{:?}", record.phase),
+				event: format!("{:?}", record.event),
+				topics: record.topics,
+			})
+			.collect())
+	}
+}
+
+impl fmt::Display for BlockInfo {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "Block #{} {:?}", self.number, self.hash)?;
+		writeln!(f, "  parent hash: {:?}", self.parent_hash)?;
+		writeln!(f, "  state root: {:?}", self.state_root)?;
+		writeln!(f, "  extrinsics root: {:?}", self.extrinsics_root)?;
+		writeln!(f, "\nExtrinsics:")?;
+		for extrinsic in &self.extrinsics {
+			write!(f, "{}", extrinsic)?;
+		}
+		if let Some(events) = &self.events {
+			writeln!(f, "\nEvents:")?;
+			for event in events {
+				writeln!(f, "  {}: {}", event.phase, event.event)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl fmt::Display for ExtrinsicInfo {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if let Some(index) = self.index {
+			write!(f, "  #{} ", index)?;
+		} else {
+			write!(f, "  ")?;
+		}
+		write!(f, "{:?}", self.hash)?;
+		match &self.signed {
+			Some(signed) => writeln!(
+				f,
+				" signed by {}, nonce {}, tip {}, era {:?}",
+				signed.signer, signed.nonce, signed.tip, signed.era
+			)?,
+			None => writeln!(f, " unsigned")?,
+		}
+		writeln!(f, "    {}", self.call)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use node_polkadex_runtime::{
+		constants::currency::PDEX, Call, Header, Runtime, SignedExtra, SystemCall,
+	};
+	use sp_core::crypto::AccountId32;
+	use sp_runtime::{Digest, MultiSignature};
+
+	use super::*;
+
+	fn signed_extrinsic() -> UncheckedExtrinsic {
+		let extra: SignedExtra = (
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckMortality::<Runtime>::from(Era::mortal(64, 12)),
+			frame_system::CheckNonce::<Runtime>::from(7),
+			frame_system::CheckWeight::<Runtime>::new(),
+			feeless::CheckFeeless::<Runtime>::new(),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(PDEX / 2, None),
+		);
+		UncheckedExtrinsic::new_signed(
+			Call::System(SystemCall::remark { remark: vec![1, 2, 3] }),
+			MultiAddress::Id(AccountId32::new([1; 32])),
+			MultiSignature::Sr25519(sp_core::sr25519::Signature([0; 64])),
+			extra,
+		)
+	}
+
+	#[test]
+	fn block_addresses_are_parsed() {
+		assert_eq!("42".parse(), Ok(BlockAddress::Number(42)));
+		assert_eq!(
+			format!("0x{}", "ab".repeat(32)).parse(),
+			Ok(BlockAddress::Hash(Hash::repeat_byte(0xab)))
+		);
+		assert_eq!("0x0102".parse(), Ok(BlockAddress::Bytes(vec![1, 2])));
+		assert!("latest".parse::<BlockAddress>().is_err());
+	}
+
+	#[test]
+	fn extrinsic_addresses_are_parsed() {
+		assert_eq!("42:1".parse(), Ok(ExtrinsicAddress::Block(BlockAddress::Number(42), 1)));
+		assert_eq!(
+			format!("0x{}:0", "ab".repeat(32)).parse(),
+			Ok(ExtrinsicAddress::Block(BlockAddress::Hash(Hash::repeat_byte(0xab)), 0))
+		);
+		assert_eq!("0x0102".parse(), Ok(ExtrinsicAddress::Bytes(vec![1, 2])));
+		assert!("42:first".parse::<ExtrinsicAddress>().is_err());
+	}
+
+	#[test]
+	fn signed_extrinsics_are_decoded() {
+		let extrinsic = signed_extrinsic();
+		let info = ExtrinsicInfo::new(None, &extrinsic);
+		let signed = info.signed.unwrap();
+		assert_eq!(signed.signer, AccountId32::new([1; 32]).to_string());
+		assert_eq!(signed.nonce, 7);
+		assert_eq!(signed.tip, Amount(PDEX / 2));
+		assert_eq!(signed.era, Era::mortal(64, 12));
+		assert_eq!(info.hash, BlakeTwo256::hash_of(&extrinsic));
+		assert!(info.call.contains("remark"));
+	}
+
+	#[test]
+	fn encoded_blocks_are_decoded() {
+		let block = Block::new(
+			Header::new(
+				3,
+				Default::default(),
+				Default::default(),
+				Hash::repeat_byte(1),
+				Digest::default(),
+			),
+			vec![signed_extrinsic()],
+		);
+		let info = BlockInfo::new(&Block::decode(&mut &block.encode()[..]).unwrap(), None);
+		assert_eq!(info.number, 3);
+		assert_eq!(info.parent_hash, Hash::repeat_byte(1));
+		assert_eq!(info.extrinsics.len(), 1);
+		assert_eq!(info.extrinsics[0].index, Some(0));
+		assert!(info.events.is_none());
+
+		let json = serde_json::to_value(&info).unwrap();
+		assert_eq!(json["extrinsics"][0]["signed"]["nonce"], 7);
+		assert_eq!(json["extrinsics"][0]["signed"]["tip"], "0.5");
+		assert!(json.get("events").is_none());
+	}
+}
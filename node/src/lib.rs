@@ -1,4 +1,12 @@
 pub mod chain_spec;
 pub mod command_helper;
+pub mod genesis;
+pub mod genesis_check;
+pub mod inspect;
+pub mod load_test;
+pub mod metrics;
 pub mod rpc;
 pub mod service;
+#[cfg(test)]
+mod test_support;
+pub mod testnet_gen;
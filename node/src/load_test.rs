@@ -0,0 +1,960 @@
+//! The `load-test` subcommand, which measures the transaction throughput of a node.
+//!
+//! Sender accounts derived from `//LoadTest//<n>` are funded by batches of transfers of the
+//! funder, once the fees quoted by the node show that the funder can pay the endowments and that
+//! the endowments pay for the submissions of the senders. The senders then submit signed
+//! extrinsics of the configured call mix at the target rate, in turns.
+//! The node is either reached over WebSocket RPC or started in process with manual sealing. New
+//! best blocks are followed as they are imported to measure when the extrinsics are included and
+//! how full the blocks are.
+
+use std::{
+	cell::{Cell, RefCell},
+	collections::{BTreeMap, HashMap, HashSet},
+	fmt,
+	future::Future,
+	pin::Pin,
+	str::FromStr,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use codec::{Decode, Encode};
+use frame_support::weights::{DispatchClass, PerDispatchClass, Weight};
+use futures::{future, stream, FutureExt, StreamExt};
+use jsonrpsee::{
+	core::client::{ClientT, SubscriptionClientT},
+	rpc_params,
+	ws_client::{WsClient, WsClientBuilder},
+};
+use node_polkadex_runtime::{
+	constants::currency::PDEX, BalancesCall, Call, ExistentialDeposit, RuntimeBlockWeights,
+	SignedExtra, SignedPayload, SystemCall, UncheckedExtrinsic,
+};
+use pallet_transaction_payment::RuntimeDispatchInfo;
+use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi;
+use polkadex_primitives::{AccountId, Balance, Block, Hash, Header, Index};
+use sc_cli::{CliConfiguration, SharedParams};
+use sc_client_api::{BlockBackend, BlockchainEvents, HeaderBackend, StorageProvider};
+use sc_service::{BasePath, Configuration, TaskManager};
+use sc_transaction_pool::FullPool;
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_core::{
+	hashing::{blake2_256, twox_128},
+	sr25519,
+	storage::{StorageData, StorageKey},
+	Bytes, Pair,
+};
+use sp_runtime::{
+	generic::{BlockId, Era, SignedBlock},
+	traits::Header as HeaderT,
+	OpaqueExtrinsic,
+};
+use substrate_frame_rpc_system::AccountNonceApi;
+
+use crate::{
+	genesis_check::{parse_pdex, Amount},
+	service::{new_manual_seal, FullClient, NewFullBase, Sealing},
+};
+
+/// Transfers of the funder batched in one extrinsic.
+const FUNDING_BATCH: usize = 100;
+/// Extrinsics submitted and not answered yet, beyond which submissions wait.
+const MAX_IN_FLIGHT: usize = 256;
+/// Transfers in a `batch` call.
+const BATCH_TRANSFERS: usize = 10;
+/// PDEX of a transfer between senders.
+const TRANSFER_VALUE: Balance = PDEX / 1_000;
+/// Time after which a node importing no block fails the load test.
+const BLOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The `load-test` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct LoadTestCmd {
+	/// WebSocket RPC endpoint of the node to load, e.g. `ws://127.0.0.1:9944`. A node of the
+	/// `--chain`, `dev` by default, is started in process if not given.
+	#[clap(long)]
+	pub url: Option<String>,
+
+	/// Sealing of the in-process node, `interval=<ms>`, `instant` or `manual`.
+	#[clap(long, default_value = "interval=12000")]
+	pub sealing: Sealing,
+
+	/// Number of sender accounts, which submit in turns.
+	#[clap(long, default_value = "4")]
+	pub senders: u32,
+
+	/// Target rate of submissions, in extrinsics per second.
+	#[clap(long, default_value = "4")]
+	pub rate: u32,
+
+	/// Duration of the submissions, in seconds.
+	#[clap(long, default_value = "10")]
+	pub duration: u64,
+
+	/// Calls submitted, as `<call>=<weight>` pairs separated by commas, of `transfer`, `remark`
+	/// and `batch` (a batch of 10 transfers).
+	#[clap(long, default_value = "transfer=1")]
+	pub mix: CallMix,
+
+	/// Secret URI of the account funding the senders.
+	#[clap(long, default_value = "//Alice")]
+	pub funder: String,
+
+	/// PDEX transferred by the funder to every sender, which pays the fees of its submissions.
+	#[clap(long, value_name = "PDEX", default_value = "20", parse(try_from_str = parse_pdex))]
+	pub endowment: Balance,
+
+	/// Time to wait for the inclusion of the pending extrinsics after the submissions, in
+	/// seconds.
+	#[clap(long, default_value = "30")]
+	pub drain: u64,
+
+	/// Print the report as JSON.
+	#[clap(long)]
+	pub json: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+}
+
+impl CliConfiguration for LoadTestCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn chain_id(&self, _is_dev: bool) -> sc_cli::Result<String> {
+		Ok(self.shared_params.chain.clone().unwrap_or_else(|| "dev".into()))
+	}
+
+	// The in-process node starts from genesis in a temporary directory, unless told otherwise.
+	fn base_path(&self) -> sc_cli::Result<Option<BasePath>> {
+		match self.shared_params.base_path() {
+			Some(base_path) => Ok(Some(base_path)),
+			None => Ok(Some(BasePath::new_temp_dir()?)),
+		}
+	}
+}
+
+/// Kind of call submitted by the senders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CallKind {
+	/// Transfer to the next sender.
+	Transfer,
+	/// Remark of 32 bytes.
+	Remark,
+	/// Batch of transfers to the next senders.
+	Batch,
+}
+
+impl FromStr for CallKind {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"transfer" => Ok(CallKind::Transfer),
+			"remark" => Ok(CallKind::Remark),
+			"batch" => Ok(CallKind::Batch),
+			_ => Err(format!("Unknown call {}, expected transfer, remark or batch", s)),
+		}
+	}
+}
+
+impl CallKind {
+	/// Call of the sender `sender` among `senders`.
+	fn call(self, sender: usize, senders: &[AccountId]) -> Call {
+		let transfer = |offset: usize| {
+			Call::Balances(BalancesCall::transfer_keep_alive {
+				dest: senders[(sender + offset) % senders.len()].clone().into(),
+				value: TRANSFER_VALUE,
+			})
+		};
+		match self {
+			CallKind::Transfer => transfer(1),
+			CallKind::Remark => Call::System(SystemCall::remark { remark: vec![0; 32] }),
+			CallKind::Batch => Call::Utility(pallet_utility::Call::batch {
+				calls: (1..=BATCH_TRANSFERS).map(transfer).collect(),
+			}),
+		}
+	}
+
+	/// PDEX transferred by the call.
+	fn value(self) -> Balance {
+		match self {
+			CallKind::Transfer => TRANSFER_VALUE,
+			CallKind::Remark => 0,
+			CallKind::Batch => TRANSFER_VALUE * BATCH_TRANSFERS as Balance,
+		}
+	}
+}
+
+/// Calls with their weights, submitted in turns of the sum of the weights.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallMix(Vec<(CallKind, u32)>);
+
+impl FromStr for CallMix {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.split(',')
+			.map(|pair| {
+				let (kind, weight) = pair
+					.split_once('=')
+					.ok_or_else(|| format!("Expected <call>=<weight>, got {}", pair))?;
+				let weight = weight
+					.parse()
+					.ok()
+					.filter(|weight| *weight > 0)
+					.ok_or_else(|| format!("Invalid weight of {}: {}", kind, weight))?;
+				Ok((kind.parse()?, weight))
+			})
+			.collect::<Result<_, String>>()
+			.map(CallMix)
+	}
+}
+
+impl CallMix {
+	/// Kind of the `index`th submitted call.
+	fn kind(&self, index: u64) -> CallKind {
+		let turn: u64 = self.0.iter().map(|(_, weight)| u64::from(*weight)).sum();
+		let mut position = index % turn;
+		for (kind, weight) in &self.0 {
+			if position < u64::from(*weight) {
+				return *kind
+			}
+			position -= u64::from(*weight);
+		}
+		unreachable!("position is below the sum of the weights; qed")
+	}
+}
+
+/// Versions and genesis of the chain, which signed extrinsics commit to.
+struct Chain {
+	genesis_hash: Hash,
+	spec_version: u32,
+	transaction_version: u32,
+}
+
+/// Signs an immortal extrinsic of `call` by `signer`.
+fn sign(chain: &Chain, signer: &sr25519::Pair, nonce: Index, call: Call) -> UncheckedExtrinsic {
+	let extra: SignedExtra = (
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckMortality::from(Era::Immortal),
+		frame_system::CheckNonce::from(nonce),
+		frame_system::CheckWeight::new(),
+		feeless::CheckFeeless::new(),
+		pallet_asset_tx_payment::ChargeAssetTxPayment::from(0, None),
+	);
+	let payload = SignedPayload::from_raw(
+		call,
+		extra,
+		(
+			chain.spec_version,
+			chain.transaction_version,
+			chain.genesis_hash,
+			chain.genesis_hash,
+			(),
+			(),
+			(),
+			(),
+		),
+	);
+	let signature = payload.using_encoded(|payload| signer.sign(payload));
+	let (call, extra, _) = payload.deconstruct();
+	UncheckedExtrinsic::new_signed(
+		call,
+		AccountId::from(signer.public()).into(),
+		signature.into(),
+		extra,
+	)
+}
+
+/// Hash of an extrinsic, as computed by the transaction pool.
+fn extrinsic_hash(extrinsic: &impl Encode) -> Hash {
+	blake2_256(&extrinsic.encode()).into()
+}
+
+/// Version of the runtime as returned over RPC.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcRuntimeVersion {
+	spec_version: u32,
+	transaction_version: u32,
+}
+
+/// The node under load.
+enum Target {
+	/// Node started by the load test.
+	Local { client: Arc<FullClient>, pool: Arc<FullPool<Block, FullClient>> },
+	/// Node reached over WebSocket RPC.
+	Remote(WsClient),
+}
+
+impl Target {
+	async fn connect(url: &str) -> sc_cli::Result<Self> {
+		let client = WsClientBuilder::default()
+			.max_request_body_size(u32::MAX)
+			.build(url)
+			.await
+			.map_err(|e| format!("Unable to connect to {}: {}", url, e))?;
+		Ok(Target::Remote(client))
+	}
+
+	async fn chain(&self) -> sc_cli::Result<Chain> {
+		match self {
+			Target::Local { client, .. } => {
+				let info = client.info();
+				let version = client.runtime_version_at(&BlockId::Hash(info.best_hash))?;
+				Ok(Chain {
+					genesis_hash: info.genesis_hash,
+					spec_version: version.spec_version,
+					transaction_version: version.transaction_version,
+				})
+			},
+			Target::Remote(client) => {
+				let genesis_hash = client
+					.request::<Option<Hash>>("chain_getBlockHash", rpc_params![0])
+					.await
+					.map_err(rpc_error)?
+					.ok_or("The node has no genesis")?;
+				let version = client
+					.request::<RpcRuntimeVersion>("state_getRuntimeVersion", rpc_params![])
+					.await
+					.map_err(rpc_error)?;
+				Ok(Chain {
+					genesis_hash,
+					spec_version: version.spec_version,
+					transaction_version: version.transaction_version,
+				})
+			},
+		}
+	}
+
+	async fn best(&self) -> sc_cli::Result<Hash> {
+		match self {
+			Target::Local { client, .. } => Ok(client.info().best_hash),
+			Target::Remote(client) => Ok(client
+				.request::<Option<Hash>>("chain_getBlockHash", rpc_params![])
+				.await
+				.map_err(rpc_error)?
+				.ok_or("The node has no best block")?),
+		}
+	}
+
+	async fn nonce(&self, who: &AccountId) -> sc_cli::Result<Index> {
+		match self {
+			Target::Local { client, .. } => Ok(client
+				.runtime_api()
+				.account_nonce(&BlockId::Hash(client.info().best_hash), who.clone())
+				.map_err(|e| format!("Unable to query the nonce of {}: {}", who, e))?),
+			Target::Remote(client) => client
+				.request("system_accountNextIndex", rpc_params![who])
+				.await
+				.map_err(rpc_error),
+		}
+	}
+
+	/// Fee the node quotes for `extrinsic`.
+	async fn fee(&self, extrinsic: &UncheckedExtrinsic) -> sc_cli::Result<Balance> {
+		let info: RuntimeDispatchInfo<Balance> = match self {
+			Target::Local { client, .. } => client
+				.runtime_api()
+				.query_info(
+					&BlockId::Hash(client.info().best_hash),
+					OpaqueExtrinsic::from(extrinsic.clone()),
+					extrinsic.encoded_size() as u32,
+				)
+				.map_err(|e| format!("Unable to query the fee of an extrinsic: {}", e))?,
+			Target::Remote(client) => client
+				.request("payment_queryInfo", rpc_params![Bytes(extrinsic.encode())])
+				.await
+				.map_err(rpc_error)?,
+		};
+		Ok(info.partial_fee)
+	}
+
+	/// Submits `extrinsic`, returning why the node rejected it if it did.
+	async fn submit(&self, extrinsic: &UncheckedExtrinsic) -> Result<(), String> {
+		match self {
+			Target::Local { client, pool } => pool
+				.submit_one(
+					&BlockId::Hash(client.info().best_hash),
+					TransactionSource::External,
+					OpaqueExtrinsic::from(extrinsic.clone()),
+				)
+				.await
+				.map(drop)
+				.map_err(|e| e.to_string()),
+			Target::Remote(client) => client
+				.request::<Hash>("author_submitExtrinsic", rpc_params![Bytes(extrinsic.encode())])
+				.await
+				.map(drop)
+				.map_err(|e| e.to_string()),
+		}
+	}
+
+	/// Hashes of the new best blocks.
+	async fn new_blocks(&self) -> sc_cli::Result<stream::BoxStream<'static, Hash>> {
+		match self {
+			Target::Local { client, .. } => Ok(client
+				.import_notification_stream()
+				.filter_map(|block| future::ready(block.is_new_best.then(|| block.hash)))
+				.boxed()),
+			Target::Remote(client) => Ok(client
+				.subscribe::<Header>(
+					"chain_subscribeNewHeads",
+					rpc_params![],
+					"chain_unsubscribeNewHeads",
+				)
+				.await
+				.map_err(rpc_error)?
+				.filter_map(|header| future::ready(header.ok().map(|header| header.hash())))
+				.boxed()),
+		}
+	}
+
+	async fn extrinsics(&self, block: Hash) -> sc_cli::Result<Vec<OpaqueExtrinsic>> {
+		let extrinsics = match self {
+			Target::Local { client, .. } => client.block_body(&BlockId::Hash(block))?,
+			Target::Remote(client) => client
+				.request::<Option<SignedBlock<Block>>>("chain_getBlock", rpc_params![block])
+				.await
+				.map_err(rpc_error)?
+				.map(|block| block.block.extrinsics),
+		};
+		extrinsics.ok_or_else(|| format!("Block {:?} has no body", block).into())
+	}
+
+	async fn storage<T: Decode>(&self, block: Hash, key: Vec<u8>) -> sc_cli::Result<Option<T>> {
+		let key = StorageKey(key);
+		let data = match self {
+			Target::Local { client, .. } => client.storage(&BlockId::Hash(block), &key)?,
+			Target::Remote(client) => client
+				.request::<Option<StorageData>>("state_getStorage", rpc_params![key, block])
+				.await
+				.map_err(rpc_error)?,
+		};
+		data.map(|data| T::decode(&mut &data.0[..]))
+			.transpose()
+			.map_err(|e| format!("Unable to decode the storage of block {:?}: {}", block, e).into())
+	}
+
+	async fn free_balance(&self, block: Hash, who: &AccountId) -> sc_cli::Result<Balance> {
+		let account = self
+			.storage::<frame_system::AccountInfo<Index, pallet_balances::AccountData<Balance>>>(
+				block,
+				frame_system::Account::<node_polkadex_runtime::Runtime>::hashed_key_for(who),
+			)
+			.await?;
+		Ok(account.map(|account| account.data.free).unwrap_or_default())
+	}
+}
+
+fn rpc_error(e: jsonrpsee::core::Error) -> sc_cli::Error {
+	format!("RPC request failed: {}", e).into()
+}
+
+/// Waits for the next block of `blocks`, for at most `timeout`.
+async fn next_block(
+	blocks: &mut stream::BoxStream<'static, Hash>,
+	timeout: Duration,
+) -> sc_cli::Result<Option<Hash>> {
+	match future::select(blocks.next(), futures_timer::Delay::new(timeout)).await {
+		future::Either::Left((Some(block), _)) => Ok(Some(block)),
+		future::Either::Left((None, _)) => Err("The node stopped sending blocks".into()),
+		future::Either::Right(_) => Ok(None),
+	}
+}
+
+/// Inclusion latency percentiles, in milliseconds.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Latency {
+	pub p50: u128,
+	pub p90: u128,
+	pub p99: u128,
+	pub max: u128,
+}
+
+impl Latency {
+	fn new(mut latencies: Vec<Duration>) -> Option<Self> {
+		latencies.sort();
+		let percentile = |p: usize| latencies[(latencies.len() - 1) * p / 100].as_millis();
+		latencies.last().map(|max| Latency {
+			p50: percentile(50),
+			p90: percentile(90),
+			p99: percentile(99),
+			max: max.as_millis(),
+		})
+	}
+}
+
+/// Results of a load test.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+	pub submitted: u64,
+	pub included: u64,
+	pub rejected: u64,
+	/// Extrinsics neither rejected nor included by the end of the drain.
+	pub pending: u64,
+	/// Rejections by reason.
+	pub rejections: BTreeMap<String, u64>,
+	pub target_rate: u32,
+	/// Included extrinsics per second, between the first submission and the last inclusion.
+	pub included_rate: f64,
+	pub blocks: u32,
+	/// Average share of the normal dispatch class weight used by the blocks, in percent.
+	pub average_fullness: f64,
+	/// Highest share of the normal dispatch class weight used by a block, in percent.
+	pub max_fullness: f64,
+	pub latency: Option<Latency>,
+}
+
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(
+			f,
+			"Submitted {} extrinsics at {} tx/s: {} included, {} rejected, {} pending",
+			self.submitted, self.target_rate, self.included, self.rejected, self.pending
+		)?;
+		for (reason, count) in &self.rejections {
+			writeln!(f, "  {} rejected: {}", count, reason)?;
+		}
+		writeln!(f, "Included rate: {:.1} tx/s", self.included_rate)?;
+		writeln!(
+			f,
+			"Blocks: {}, fullness {:.1}% on average, {:.1}% at most",
+			self.blocks, self.average_fullness, self.max_fullness
+		)?;
+		match &self.latency {
+			Some(latency) => writeln!(
+				f,
+				"Inclusion latency: p50 {} ms, p90 {} ms, p99 {} ms, max {} ms",
+				latency.p50, latency.p90, latency.p99, latency.max
+			),
+			None => writeln!(f, "Inclusion latency: nothing was included"),
+		}
+	}
+}
+
+/// Progress of the submissions and of their inclusion, shared by the submitting and the block
+/// following halves of the load test.
+#[derive(Default)]
+struct Progress {
+	/// Submission time of the extrinsics which are neither rejected nor included yet.
+	pending: RefCell<HashMap<Hash, Instant>>,
+	rejections: RefCell<BTreeMap<String, u64>>,
+	/// End of the drain, once every extrinsic is submitted.
+	drain_end: Cell<Option<Instant>>,
+}
+
+impl LoadTestCmd {
+	/// Starts the in-process node if there is no `--url`, and returns the load test to run with
+	/// the task manager of the node.
+	pub fn run(
+		&self,
+		config: Configuration,
+	) -> sc_cli::Result<(Pin<Box<dyn Future<Output = sc_cli::Result<()>>>>, TaskManager)> {
+		let cmd = self.clone();
+		match &self.url {
+			Some(url) => {
+				let task_manager = TaskManager::new(config.tokio_handle.clone(), None)
+					.map_err(|e| sc_cli::Error::Service(sc_service::Error::Prometheus(e)))?;
+				let url = url.clone();
+				let load_test = async move { cmd.report(Target::connect(&url).await?).await };
+				Ok((load_test.boxed_local(), task_manager))
+			},
+			None => {
+				let NewFullBase { task_manager, client, transaction_pool, .. } =
+					new_manual_seal(config, self.sealing)?;
+				let target = Target::Local { client, pool: transaction_pool };
+				Ok((cmd.report(target).boxed_local(), task_manager))
+			},
+		}
+	}
+
+	/// Runs the load test on `target` and prints its report.
+	async fn report(self, target: Target) -> sc_cli::Result<()> {
+		let report = self.load(&target).await?;
+		if self.json {
+			println!("{}", serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?);
+		} else {
+			print!("{}", report);
+		}
+		Ok(())
+	}
+
+	/// Submissions of the load test.
+	fn submissions(&self) -> u64 {
+		u64::from(self.rate) * self.duration
+	}
+
+	async fn load(&self, target: &Target) -> sc_cli::Result<Report> {
+		if self.senders == 0 || self.rate == 0 {
+			return Err("The load test needs senders and a rate".into())
+		}
+		let chain = target.chain().await?;
+		let funder = sr25519::Pair::from_string(&self.funder, None)
+			.map_err(|e| format!("Invalid funder {}: {:?}", self.funder, e))?;
+		let senders = (0..self.senders)
+			.map(|index| {
+				sr25519::Pair::from_string(&format!("//LoadTest//{}", index), None)
+					.expect("Derivations of a development phrase are valid; qed")
+			})
+			.collect::<Vec<_>>();
+		let accounts = senders
+			.iter()
+			.map(|sender| AccountId::from(sender.public()))
+			.collect::<Vec<_>>();
+
+		let nonce = target.nonce(&funder.public().into()).await?;
+		let funding = accounts
+			.chunks(FUNDING_BATCH)
+			.zip(nonce..)
+			.map(|(chunk, nonce)| {
+				let calls = chunk
+					.iter()
+					.map(|account| {
+						Call::Balances(BalancesCall::transfer {
+							dest: account.clone().into(),
+							value: self.endowment,
+						})
+					})
+					.collect();
+				let call = Call::Utility(pallet_utility::Call::batch_all { calls });
+				sign(&chain, &funder, nonce, call)
+			})
+			.collect::<Vec<_>>();
+		self.check_funds(target, &chain, &funder, &funding, &senders, &accounts).await?;
+
+		self.fund(target, &funding, &accounts).await?;
+		let mut nonces = Vec::with_capacity(accounts.len());
+		for account in &accounts {
+			nonces.push(target.nonce(account).await?);
+		}
+		self.submit(target, &chain, &senders, &accounts, &nonces).await
+	}
+
+	/// Checks that `funder` can pay the `funding` of the senders, and that the endowment of every
+	/// sender pays for its submissions, at the fees quoted by the node.
+	async fn check_funds(
+		&self,
+		target: &Target,
+		chain: &Chain,
+		funder: &sr25519::Pair,
+		funding: &[UncheckedExtrinsic],
+		senders: &[sr25519::Pair],
+		accounts: &[AccountId],
+	) -> sc_cli::Result<()> {
+		let existential_deposit = ExistentialDeposit::get();
+		let mut funding_fees: Balance = 0;
+		for extrinsic in funding {
+			funding_fees = funding_fees.saturating_add(target.fee(extrinsic).await?);
+		}
+		// Fees are withdrawn from the funder while keeping it alive.
+		let needed = self
+			.endowment
+			.saturating_mul(accounts.len() as Balance)
+			.saturating_add(funding_fees)
+			.saturating_add(existential_deposit);
+		let free = target.free_balance(target.best().await?, &funder.public().into()).await?;
+		if free < needed {
+			return Err(format!(
+				"{} has {} but needs {} to endow {} senders with {}, pay {} of fees and stay \
+				 alive: lower --senders or --endowment, or choose another --funder",
+				self.funder,
+				Amount(free),
+				Amount(needed),
+				accounts.len(),
+				Amount(self.endowment),
+				Amount(funding_fees),
+			)
+			.into())
+		}
+
+		// Every sender submits in turn, signing with nonces up to the number of its submissions.
+		let turns = self.submissions() / accounts.len() as u64 + 1;
+		// The transfers from the previous sender may be included after those to the next one.
+		let mut costs = HashMap::new();
+		for (kind, _) in &self.mix.0 {
+			let extrinsic = sign(chain, &senders[0], turns as Index, kind.call(0, accounts));
+			costs.insert(*kind, target.fee(&extrinsic).await?.saturating_add(kind.value()));
+		}
+		let mut spent: Vec<Balance> = vec![0; accounts.len()];
+		for index in 0..self.submissions() {
+			let sender = (index % accounts.len() as u64) as usize;
+			spent[sender] = spent[sender].saturating_add(costs[&self.mix.kind(index)]);
+		}
+		let needed =
+			spent.into_iter().max().unwrap_or_default().saturating_add(existential_deposit);
+		if self.endowment < needed {
+			return Err(format!(
+				"Every sender needs {} to pay for its submissions and stay alive, more than the \
+				 endowment of {}: raise --endowment, or lower --rate or --duration",
+				Amount(needed),
+				Amount(self.endowment),
+			)
+			.into())
+		}
+		Ok(())
+	}
+
+	/// Submits the `funding` of the senders, and waits for its inclusion.
+	async fn fund(
+		&self,
+		target: &Target,
+		funding: &[UncheckedExtrinsic],
+		accounts: &[AccountId],
+	) -> sc_cli::Result<()> {
+		let mut blocks = target.new_blocks().await?;
+		let mut pending = HashSet::new();
+		for extrinsic in funding {
+			target
+				.submit(extrinsic)
+				.await
+				.map_err(|e| format!("The funding of the senders was rejected: {}", e))?;
+			pending.insert(extrinsic_hash(extrinsic));
+		}
+
+		let mut last_block = None;
+		while !pending.is_empty() {
+			let block = next_block(&mut blocks, BLOCK_TIMEOUT)
+				.await?
+				.ok_or("The funding of the senders was not included")?;
+			for extrinsic in target.extrinsics(block).await? {
+				pending.remove(&extrinsic_hash(&extrinsic));
+			}
+			last_block = Some(block);
+		}
+		// The batches are atomic, so checking one sender of each of them is enough.
+		if let Some(block) = last_block {
+			for chunk in accounts.chunks(FUNDING_BATCH) {
+				if target.free_balance(block, &chunk[0]).await? < self.endowment {
+					return Err(format!(
+						"The funding of the senders failed, is {} endowed with {} for each of them?",
+						self.funder,
+						Amount(self.endowment)
+					)
+					.into())
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Submits the extrinsics of the senders at the target rate, and follows their inclusion.
+	async fn submit(
+		&self,
+		target: &Target,
+		chain: &Chain,
+		senders: &[sr25519::Pair],
+		accounts: &[AccountId],
+		nonces: &[Index],
+	) -> sc_cli::Result<Report> {
+		let total = self.submissions();
+		let period = Duration::from_secs(1) / self.rate;
+		let progress = Progress::default();
+		let mut blocks = target.new_blocks().await?;
+		let start = Instant::now();
+
+		let submissions = stream::iter(0..total)
+			.then(|index| async move {
+				let due = start + period * index as u32;
+				futures_timer::Delay::new(due.saturating_duration_since(Instant::now())).await;
+				index
+			})
+			.map(|index| {
+				let sender = (index % senders.len() as u64) as usize;
+				let nonce = nonces[sender] + (index / senders.len() as u64) as Index;
+				let call = self.mix.kind(index).call(sender, accounts);
+				let extrinsic = sign(chain, &senders[sender], nonce, call);
+				let hash = extrinsic_hash(&extrinsic);
+				progress.pending.borrow_mut().insert(hash, Instant::now());
+				let progress = &progress;
+				async move {
+					if let Err(reason) = target.submit(&extrinsic).await {
+						progress.pending.borrow_mut().remove(&hash);
+						*progress.rejections.borrow_mut().entry(reason).or_default() += 1;
+					}
+				}
+			})
+			.buffer_unordered(MAX_IN_FLIGHT)
+			.for_each(|()| future::ready(()))
+			.map(|()| {
+				progress.drain_end.set(Some(Instant::now() + Duration::from_secs(self.drain)))
+			});
+
+		let max_weight = {
+			let weights = RuntimeBlockWeights::get();
+			weights.get(DispatchClass::Normal).max_total.unwrap_or(weights.max_block)
+		};
+		let block_weight_key = [twox_128(b"System"), twox_128(b"BlockWeight")].concat();
+		let mut latencies = Vec::new();
+		let mut fullness = Vec::new();
+		let mut last_inclusion = start;
+		let inclusions = async {
+			loop {
+				let timeout = match progress.drain_end.get() {
+					Some(_) if progress.pending.borrow().is_empty() => break,
+					Some(drain_end) => drain_end.saturating_duration_since(Instant::now()),
+					None => BLOCK_TIMEOUT,
+				};
+				let block = match next_block(&mut blocks, timeout).await? {
+					Some(block) => block,
+					None if progress.drain_end.get().is_some() => break,
+					None => return Err(format!("No block was imported in {:?}", timeout).into()),
+				};
+				let imported = Instant::now();
+				for extrinsic in target.extrinsics(block).await? {
+					let submitted =
+						progress.pending.borrow_mut().remove(&extrinsic_hash(&extrinsic));
+					if let Some(submitted) = submitted {
+						latencies.push(imported.saturating_duration_since(submitted));
+						last_inclusion = imported;
+					}
+				}
+				let weight = target
+					.storage::<PerDispatchClass<Weight>>(block, block_weight_key.clone())
+					.await?
+					.unwrap_or_default();
+				fullness
+					.push(*weight.get(DispatchClass::Normal) as f64 * 100.0 / max_weight as f64);
+			}
+			Ok::<_, sc_cli::Error>(())
+		};
+		let ((), inclusions) = future::join(submissions, inclusions).await;
+		inclusions?;
+
+		let rejections = progress.rejections.into_inner();
+		let rejected = rejections.values().sum();
+		let included = latencies.len() as u64;
+		let elapsed = last_inclusion.saturating_duration_since(start).as_secs_f64();
+		Ok(Report {
+			submitted: total,
+			included,
+			rejected,
+			pending: progress.pending.into_inner().len() as u64,
+			rejections,
+			target_rate: self.rate,
+			included_rate: if elapsed > 0.0 { included as f64 / elapsed } else { 0.0 },
+			blocks: fullness.len() as u32,
+			average_fullness: if fullness.is_empty() {
+				0.0
+			} else {
+				fullness.iter().sum::<f64>() / fullness.len() as f64
+			},
+			max_fullness: fullness.iter().copied().fold(0.0, f64::max),
+			latency: Latency::new(latencies),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use clap::Parser;
+	use node_polkadex_runtime::Address;
+	use sp_keyring::AccountKeyring;
+
+	use super::*;
+	use crate::test_support::TestNode;
+
+	#[test]
+	fn call_mix_is_parsed() {
+		assert_eq!("transfer=1".parse(), Ok(CallMix(vec![(CallKind::Transfer, 1)])));
+		assert_eq!(
+			"transfer=3,remark=1,batch=2".parse(),
+			Ok(CallMix(vec![(CallKind::Transfer, 3), (CallKind::Remark, 1), (CallKind::Batch, 2)]))
+		);
+		assert!("transfer".parse::<CallMix>().is_err());
+		assert!("transfer=0".parse::<CallMix>().is_err());
+		assert!("stake=1".parse::<CallMix>().is_err());
+	}
+
+	#[test]
+	fn calls_follow_the_mix() {
+		let mix: CallMix = "transfer=3,remark=1".parse().unwrap();
+		let kinds = (0..8).map(|index| mix.kind(index)).collect::<Vec<_>>();
+		assert_eq!(
+			kinds,
+			[CallKind::Transfer, CallKind::Transfer, CallKind::Transfer, CallKind::Remark]
+				.repeat(2)
+		);
+	}
+
+	#[test]
+	fn transfers_go_to_the_next_senders() {
+		let accounts = (0..3u8).map(|index| AccountId::new([index; 32])).collect::<Vec<_>>();
+		match CallKind::Transfer.call(2, &accounts) {
+			Call::Balances(BalancesCall::transfer_keep_alive { dest, .. }) =>
+				assert_eq!(dest, Address::Id(accounts[0].clone())),
+			call => panic!("Unexpected call {:?}", call),
+		}
+		match CallKind::Batch.call(0, &accounts) {
+			Call::Utility(pallet_utility::Call::batch { calls }) =>
+				assert_eq!(calls.len(), BATCH_TRANSFERS),
+			call => panic!("Unexpected call {:?}", call),
+		}
+	}
+
+	#[test]
+	fn latency_percentiles() {
+		assert_eq!(Latency::new(vec![]), None);
+		let latencies = (1..=100).rev().map(Duration::from_millis).collect();
+		assert_eq!(Latency::new(latencies), Some(Latency { p50: 50, p90: 90, p99: 99, max: 100 }));
+	}
+
+	#[test]
+	fn signed_extrinsics_are_valid() {
+		let chain = Chain {
+			genesis_hash: Hash::repeat_byte(1),
+			spec_version: node_polkadex_runtime::VERSION.spec_version,
+			transaction_version: node_polkadex_runtime::VERSION.transaction_version,
+		};
+		let signer = sr25519::Pair::from_string("//LoadTest//0", None).unwrap();
+		let extrinsic =
+			sign(&chain, &signer, 7, Call::System(SystemCall::remark { remark: vec![] }));
+		let (address, _, extra) = extrinsic.signature.clone().unwrap();
+		assert_eq!(address, Address::Id(signer.public().into()));
+		assert_eq!(extra.4, frame_system::CheckNonce::from(7));
+
+		let opaque = OpaqueExtrinsic::from(extrinsic.clone());
+		assert_eq!(extrinsic_hash(&opaque), extrinsic_hash(&extrinsic));
+	}
+
+	#[test]
+	fn default_load_runs_on_the_test_node() {
+		let node = TestNode::new();
+		let target =
+			Target::Local { client: node.client.clone(), pool: node.transaction_pool.clone() };
+		let cmd = LoadTestCmd::parse_from(["load-test"]);
+		let report = node.block_on(cmd.load(&target)).expect("The default load is funded");
+		assert_eq!(report.submitted, cmd.submissions());
+		assert_eq!(report.included, report.submitted, "{}", report);
+		assert!(report.blocks > 0);
+	}
+
+	#[test]
+	fn unfundable_loads_are_rejected() {
+		let node = TestNode::new();
+		let target =
+			Target::Local { client: node.client.clone(), pool: node.transaction_pool.clone() };
+		let alice = AccountKeyring::Alice.to_account_id();
+		let balance = node.free_balance(&alice);
+
+		let cmd = LoadTestCmd::parse_from(["load-test", "--senders", "10"]);
+		let error = node.block_on(cmd.load(&target)).unwrap_err().to_string();
+		assert!(error.contains("lower --senders or --endowment"), "{}", error);
+
+		let cmd = LoadTestCmd::parse_from(["load-test", "--endowment", "2"]);
+		let error = node.block_on(cmd.load(&target)).unwrap_err().to_string();
+		assert!(error.contains("raise --endowment"), "{}", error);
+
+		// Nothing is submitted before the funds are checked.
+		assert_eq!(node.free_balance(&alice), balance);
+	}
+}
@@ -2,6 +2,8 @@
 #![warn(missing_docs)]
 
 mod chain_spec;
+mod genesis;
+mod metrics;
 #[macro_use]
 mod service;
 mod cli;
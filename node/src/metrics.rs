@@ -0,0 +1,690 @@
+//! Prometheus metrics of the Polkadex pallets.
+//!
+//! A task follows the blocks of the node, counting the events of the finalized blocks, so that
+//! retracted blocks are never counted, and reading the state of every new best block into gauges.
+
+use std::{
+	collections::{BTreeMap, HashSet},
+	sync::Arc,
+};
+
+use codec::{Decode, Encode};
+use futures::{future, stream, StreamExt};
+use node_polkadex_runtime::{
+	constants::currency::PDEX, Call, Event, Runtime, TreasuryPalletId, UncheckedExtrinsic,
+};
+use polkadex_primitives::{AccountId, Balance, BlockNumber, Hash, Header, Index};
+use prometheus_endpoint::{
+	register, Counter, CounterVec, Gauge, GaugeVec, Opts, PrometheusError, Registry, F64, U64,
+};
+use sc_client_api::{BlockBackend, BlockchainEvents, HeaderBackend, StorageProvider};
+use sp_core::{
+	crypto::Ss58Codec,
+	hashing::{blake2_128, twox_128},
+	hexdisplay::HexDisplay,
+	storage::{StorageData, StorageKey},
+};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{AccountIdConversion, Header as HeaderT},
+};
+
+use crate::service::FullClient;
+
+const LOG_TARGET: &str = "polkadex-metrics";
+/// Attestations of relayers minting the tokens of a burn transaction, as in pdex-migration.
+const MINT_APPROVALS: u16 = 3;
+
+/// Reads of the chain by the metrics task.
+pub trait ChainReader {
+	/// Header of `block`.
+	fn header(&self, block: Hash) -> Result<Option<Header>, String>;
+	/// Extrinsics of `block`.
+	fn extrinsics(&self, block: Hash) -> Result<Vec<UncheckedExtrinsic>, String>;
+	/// Value at `key` in the state after `block`.
+	fn storage(&self, block: Hash, key: &StorageKey) -> Result<Option<StorageData>, String>;
+	/// Keys starting with `prefix` in the state after `block`.
+	fn storage_keys(&self, block: Hash, prefix: &StorageKey) -> Result<Vec<StorageKey>, String>;
+}
+
+impl ChainReader for FullClient {
+	fn header(&self, block: Hash) -> Result<Option<Header>, String> {
+		HeaderBackend::header(self, BlockId::Hash(block)).map_err(|e| e.to_string())
+	}
+
+	fn extrinsics(&self, block: Hash) -> Result<Vec<UncheckedExtrinsic>, String> {
+		let extrinsics = self
+			.block_body(&BlockId::Hash(block))
+			.map_err(|e| e.to_string())?
+			.ok_or_else(|| format!("Block {:?} has no body", block))?;
+		// The database holds opaque extrinsics, whose encoding is the one of the runtime.
+		extrinsics
+			.iter()
+			.map(|extrinsic| UncheckedExtrinsic::decode(&mut &extrinsic.encode()[..]))
+			.collect::<Result<_, _>>()
+			.map_err(|e| format!("Extrinsics of block {:?} can not be decoded: {}", block, e))
+	}
+
+	fn storage(&self, block: Hash, key: &StorageKey) -> Result<Option<StorageData>, String> {
+		StorageProvider::storage(self, &BlockId::Hash(block), key).map_err(|e| e.to_string())
+	}
+
+	fn storage_keys(&self, block: Hash, prefix: &StorageKey) -> Result<Vec<StorageKey>, String> {
+		StorageProvider::storage_keys(self, &BlockId::Hash(block), prefix)
+			.map_err(|e| e.to_string())
+	}
+}
+
+/// Metrics of the Polkadex pallets.
+pub struct Metrics {
+	migration_mints: Counter<U64>,
+	migration_minted: Counter<F64>,
+	migration_unlocks: Counter<U64>,
+	migration_reverts: Counter<U64>,
+	migration_pending_attestations: GaugeVec<U64>,
+	migration_mintable: Gauge<F64>,
+	treasury_balance: Gauge<F64>,
+	treasury_deposits: Counter<F64>,
+	fees: Counter<F64>,
+	fee_shares: CounterVec<F64>,
+	asset_fees: CounterVec<F64>,
+}
+
+impl Metrics {
+	/// Registers the metrics in `registry`.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Metrics {
+			migration_mints: register(
+				Counter::new(
+					"polkadex_migration_mints_total",
+					"Burn transactions of Ethereum minted and locked by pdex-migration",
+				)?,
+				registry,
+			)?,
+			migration_minted: register(
+				Counter::new(
+					"polkadex_migration_minted_pdex_total",
+					"PDEX minted and locked by pdex-migration",
+				)?,
+				registry,
+			)?,
+			migration_unlocks: register(
+				Counter::new(
+					"polkadex_migration_unlocks_total",
+					"Successful unlocks of the tokens minted by pdex-migration",
+				)?,
+				registry,
+			)?,
+			migration_reverts: register(
+				Counter::new(
+					"polkadex_migration_reverts_total",
+					"Fraudulent mints of pdex-migration reverted",
+				)?,
+				registry,
+			)?,
+			migration_pending_attestations: register(
+				GaugeVec::new(
+					Opts::new(
+						"polkadex_migration_pending_attestations",
+						"Burn transactions attested by the relayer and waiting for the other \
+						 attestations to be minted",
+					),
+					&["relayer"],
+				)?,
+				registry,
+			)?,
+			migration_mintable: register(
+				Gauge::new(
+					"polkadex_migration_mintable_pdex",
+					"PDEX which can still be minted by pdex-migration",
+				)?,
+				registry,
+			)?,
+			treasury_balance: register(
+				Gauge::new(
+					"polkadex_treasury_balance_pdex",
+					"Free balance of the treasury in PDEX",
+				)?,
+				registry,
+			)?,
+			treasury_deposits: register(
+				Counter::new(
+					"polkadex_treasury_deposits_pdex_total",
+					"PDEX deposited into the treasury, its share of the transaction fees included",
+				)?,
+				registry,
+			)?,
+			fees: register(
+				Counter::new(
+					"polkadex_fees_pdex_total",
+					"Transaction fees and tips paid in PDEX and distributed by fee-distribution",
+				)?,
+				registry,
+			)?,
+			fee_shares: register(
+				CounterVec::new(
+					Opts::new(
+						"polkadex_fee_shares_pdex_total",
+						"Transaction fees and tips paid in PDEX sent to the treasury or the block \
+						 author, or burnt",
+					),
+					&["share"],
+				)?,
+				registry,
+			)?,
+			asset_fees: register(
+				CounterVec::new(
+					Opts::new(
+						"polkadex_asset_fees_total",
+						"Transaction fees and tips paid in an asset, in units of the asset",
+					),
+					&["asset"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// Block notified to the metrics task.
+enum Notification {
+	Best(Hash),
+	Finalized(Hash),
+}
+
+/// Task updating `metrics` as the blocks of `client` are imported and finalized.
+pub async fn run(client: Arc<FullClient>, metrics: Metrics) {
+	let info = client.info();
+	let mut follower =
+		Follower::new(client.clone(), metrics, info.finalized_hash, info.finalized_number);
+	let best = client.import_notification_stream().filter_map(|block| {
+		future::ready(block.is_new_best.then(|| Notification::Best(block.hash)))
+	});
+	let finalized = client
+		.finality_notification_stream()
+		.map(|block| Notification::Finalized(block.hash));
+	let mut notifications = stream::select(best, finalized);
+
+	let mut notification = Some(Notification::Best(info.best_hash));
+	while let Some(block) = notification {
+		let result = match block {
+			Notification::Best(hash) => follower.on_best_block(hash),
+			Notification::Finalized(hash) => follower.on_finalized_block(hash),
+		};
+		if let Err(e) = result {
+			log::warn!(target: LOG_TARGET, "Metrics not updated: {}", e);
+		}
+		notification = notifications.next().await;
+	}
+}
+
+/// Follower of the chain updating the metrics.
+struct Follower<R> {
+	reader: Arc<R>,
+	metrics: Metrics,
+	/// Last finalized block, whose events and the ones of its ancestors are counted.
+	finalized: (Hash, BlockNumber),
+	/// Last best block, whose state is in the gauges.
+	best: Option<Hash>,
+	/// Burn transactions attested by some relayers and not minted yet.
+	pending: HashSet<Hash>,
+	/// Relayers with a pending attestations gauge.
+	relayers: HashSet<String>,
+}
+
+impl<R: ChainReader> Follower<R> {
+	fn new(
+		reader: Arc<R>,
+		metrics: Metrics,
+		finalized_hash: Hash,
+		finalized_number: BlockNumber,
+	) -> Self {
+		Follower {
+			reader,
+			metrics,
+			finalized: (finalized_hash, finalized_number),
+			best: None,
+			pending: HashSet::new(),
+			relayers: HashSet::new(),
+		}
+	}
+
+	/// Reads the state of the new best block `block` into the gauges.
+	fn on_best_block(&mut self, block: Hash) -> Result<(), String> {
+		let header = self.header(block)?;
+		if self.best == Some(*header.parent_hash()) {
+			// Burn transactions attested for the first time are announced by an event.
+			for record in self.events(block)? {
+				if let Event::PDEXMigration(pdex_migration::pallet::Event::TokenBurnDetected(
+					eth_tx,
+					_,
+				)) = record.event
+				{
+					self.pending.insert(eth_tx);
+				}
+			}
+		} else {
+			// The events of the blocks between the last best block and this one are not known.
+			self.pending = self
+				.reader
+				.storage_keys(block, &storage_prefix(b"PDEXMigration", b"EthTxns"))?
+				.into_iter()
+				.filter_map(|key| Hash::decode(&mut &key.0[key.0.len().saturating_sub(32)..]).ok())
+				.collect();
+		}
+		self.best = Some(block);
+
+		let mut attestations = BTreeMap::<String, u64>::new();
+		for eth_tx in self.pending.clone() {
+			let key = eth_txns_key(&eth_tx);
+			// The fields of `BurnTxDetails`, approvals and approvers.
+			match self.storage::<(u16, Vec<AccountId>)>(block, key)? {
+				Some((approvals, approvers)) if approvals < MINT_APPROVALS =>
+					for relayer in approvers {
+						*attestations.entry(relayer.to_ss58check()).or_default() += 1;
+					},
+				_ => {
+					self.pending.remove(&eth_tx);
+				},
+			}
+		}
+		for relayer in self.relayers.difference(&attestations.keys().cloned().collect()) {
+			let _ = self
+				.metrics
+				.migration_pending_attestations
+				.remove_label_values(&[relayer.as_str()]);
+		}
+		for (relayer, count) in &attestations {
+			self.metrics
+				.migration_pending_attestations
+				.with_label_values(&[relayer.as_str()])
+				.set(*count);
+		}
+		self.relayers = attestations.into_keys().collect();
+
+		let mintable: Balance = self
+			.storage(block, storage_prefix(b"PDEXMigration", b"MintableTokens"))?
+			.unwrap_or_default();
+		self.metrics.migration_mintable.set(pdex(mintable));
+		let treasury: AccountId = TreasuryPalletId::get().into_account();
+		let treasury = self
+			.storage::<frame_system::AccountInfo<Index, pallet_balances::AccountData<Balance>>>(
+				block,
+				StorageKey(frame_system::Account::<Runtime>::hashed_key_for(treasury)),
+			)?
+			.unwrap_or_default();
+		self.metrics.treasury_balance.set(pdex(treasury.data.free));
+		Ok(())
+	}
+
+	/// Counts the events of the newly finalized blocks up to `block`.
+	fn on_finalized_block(&mut self, block: Hash) -> Result<(), String> {
+		// Finality notifications may skip blocks, whose events are counted as well.
+		let mut finalized = vec![];
+		let mut hash = block;
+		loop {
+			let header = self.header(hash)?;
+			if *header.number() <= self.finalized.1 {
+				break;
+			}
+			finalized.push(hash);
+			hash = *header.parent_hash();
+		}
+		for hash in finalized.into_iter().rev() {
+			self.count_events(hash)?;
+			self.finalized = (hash, self.finalized.1 + 1);
+		}
+		Ok(())
+	}
+
+	fn count_events(&self, block: Hash) -> Result<(), String> {
+		let mut extrinsics = None;
+		for record in self.events(block)? {
+			match record.event {
+				Event::PDEXMigration(pdex_migration::pallet::Event::NativePDEXMintedAndLocked(
+					_,
+					_,
+					amount,
+				)) => {
+					self.metrics.migration_mints.inc();
+					self.metrics.migration_minted.inc_by(pdex(amount));
+				},
+				Event::PDEXMigration(pdex_migration::pallet::Event::RevertedMintedTokens(_)) =>
+					self.metrics.migration_reverts.inc(),
+				Event::Treasury(pallet_treasury::Event::Deposit { value }) =>
+					self.metrics.treasury_deposits.inc_by(pdex(value)),
+				Event::FeeDistribution(fee_distribution::Event::Distributed {
+					total,
+					treasury,
+					author,
+					burnt,
+				}) => {
+					self.metrics.fees.inc_by(pdex(total));
+					for (share, amount) in
+						[("treasury", treasury), ("author", author), ("burnt", burnt)]
+					{
+						self.metrics.fee_shares.with_label_values(&[share]).inc_by(pdex(amount));
+					}
+				},
+				Event::AssetFees(asset_fees::Event::AssetFeePaid { asset, fee, tip, .. }) => self
+					.metrics
+					.asset_fees
+					.with_label_values(&[asset.to_string().as_str()])
+					.inc_by(fee.saturating_add(tip) as f64),
+				// Unlocks emit no event of their own.
+				Event::System(frame_system::Event::ExtrinsicSuccess { .. }) => {
+					let index = match record.phase {
+						frame_system::Phase::ApplyExtrinsic(index) => index as usize,
+						_ => continue,
+					};
+					if extrinsics.is_none() {
+						extrinsics = Some(self.reader.extrinsics(block)?);
+					}
+					let call = extrinsics.as_ref().and_then(|extrinsics| extrinsics.get(index));
+					if let Some(UncheckedExtrinsic {
+						function: Call::PDEXMigration(pdex_migration::pallet::Call::unlock {}),
+						..
+					}) = call
+					{
+						self.metrics.migration_unlocks.inc();
+					}
+				},
+				_ => (),
+			}
+		}
+		Ok(())
+	}
+
+	fn header(&self, block: Hash) -> Result<Header, String> {
+		self.reader
+			.header(block)?
+			.ok_or_else(|| format!("Block {:?} is unknown", block))
+	}
+
+	fn events(&self, block: Hash) -> Result<Vec<frame_system::EventRecord<Event, Hash>>, String> {
+		Ok(self.storage(block, storage_prefix(b"System", b"Events"))?.unwrap_or_default())
+	}
+
+	fn storage<T: Decode>(&self, block: Hash, key: StorageKey) -> Result<Option<T>, String> {
+		self.reader
+			.storage(block, &key)?
+			.map(|data| T::decode(&mut &data.0[..]))
+			.transpose()
+			.map_err(|e| {
+				format!("Storage {} of block {:?}: {}", HexDisplay::from(&key.0), block, e)
+			})
+	}
+}
+
+/// Key of a storage value, or prefix of the keys of a storage map.
+fn storage_prefix(pallet: &[u8], storage: &[u8]) -> StorageKey {
+	StorageKey([twox_128(pallet), twox_128(storage)].concat())
+}
+
+/// Key of the attestations of the Ethereum burn transaction `eth_tx` in pdex-migration.
+fn eth_txns_key(eth_tx: &Hash) -> StorageKey {
+	let mut key = storage_prefix(b"PDEXMigration", b"EthTxns").0;
+	key.extend(blake2_128(eth_tx.as_ref()));
+	key.extend(eth_tx.as_ref());
+	StorageKey(key)
+}
+
+fn pdex(amount: Balance) -> f64 {
+	amount as f64 / PDEX as f64
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+
+	use sp_keyring::AccountKeyring;
+	use sp_runtime::generic::Digest;
+
+	use super::*;
+
+	/// Chain of blocks whose state is made of the given storage values.
+	#[derive(Default)]
+	struct MockChain {
+		headers: HashMap<Hash, Header>,
+		extrinsics: HashMap<Hash, Vec<UncheckedExtrinsic>>,
+		storage: HashMap<Hash, BTreeMap<Vec<u8>, Vec<u8>>>,
+	}
+
+	impl MockChain {
+		/// Adds a block on top of `parent`, the genesis if `None`, and returns its hash.
+		fn push(
+			&mut self,
+			parent: Option<Hash>,
+			extrinsics: Vec<UncheckedExtrinsic>,
+			storage: Vec<(StorageKey, Vec<u8>)>,
+		) -> Hash {
+			let (parent_hash, number) = match parent {
+				Some(parent) => (parent, self.headers[&parent].number() + 1),
+				None => (Hash::default(), 0),
+			};
+			let header = Header::new(
+				number,
+				Default::default(),
+				Default::default(),
+				parent_hash,
+				Digest::default(),
+			);
+			let hash = header.hash();
+			self.headers.insert(hash, header);
+			self.extrinsics.insert(hash, extrinsics);
+			self.storage
+				.insert(hash, storage.into_iter().map(|(key, value)| (key.0, value)).collect());
+			hash
+		}
+	}
+
+	impl ChainReader for MockChain {
+		fn header(&self, block: Hash) -> Result<Option<Header>, String> {
+			Ok(self.headers.get(&block).cloned())
+		}
+
+		fn extrinsics(&self, block: Hash) -> Result<Vec<UncheckedExtrinsic>, String> {
+			Ok(self.extrinsics[&block].clone())
+		}
+
+		fn storage(&self, block: Hash, key: &StorageKey) -> Result<Option<StorageData>, String> {
+			Ok(self.storage[&block].get(&key.0).cloned().map(StorageData))
+		}
+
+		fn storage_keys(
+			&self,
+			block: Hash,
+			prefix: &StorageKey,
+		) -> Result<Vec<StorageKey>, String> {
+			Ok(self.storage[&block]
+				.keys()
+				.filter(|key| key.starts_with(&prefix.0))
+				.cloned()
+				.map(StorageKey)
+				.collect())
+		}
+	}
+
+	fn events(events: Vec<(frame_system::Phase, Event)>) -> (StorageKey, Vec<u8>) {
+		let records: Vec<_> = events
+			.into_iter()
+			.map(|(phase, event)| frame_system::EventRecord { phase, event, topics: vec![] })
+			.collect();
+		(storage_prefix(b"System", b"Events"), records.encode())
+	}
+
+	fn attestations(eth_tx: Hash, approvers: &[AccountKeyring]) -> (StorageKey, Vec<u8>) {
+		let approvers: Vec<AccountId> =
+			approvers.iter().map(|relayer| relayer.to_account_id()).collect();
+		(eth_txns_key(&eth_tx), (approvers.len() as u16, approvers).encode())
+	}
+
+	fn follower(chain: MockChain, genesis: Hash) -> Follower<MockChain> {
+		let metrics = Metrics::register(&Registry::new()).unwrap();
+		Follower::new(Arc::new(chain), metrics, genesis, 0)
+	}
+
+	fn pending(follower: &Follower<MockChain>, relayer: AccountKeyring) -> Option<u64> {
+		let relayer = relayer.to_account_id().to_ss58check();
+		follower.relayers.contains(&relayer).then(|| {
+			follower
+				.metrics
+				.migration_pending_attestations
+				.with_label_values(&[relayer.as_str()])
+				.get()
+		})
+	}
+
+	#[test]
+	fn finalized_events_are_counted_once() {
+		let mut chain = MockChain::default();
+		let genesis = chain.push(None, vec![], vec![]);
+		let beneficiary = AccountKeyring::Ferdie.to_account_id();
+		let mint = Event::PDEXMigration(pdex_migration::pallet::Event::NativePDEXMintedAndLocked(
+			AccountKeyring::Bob.to_account_id(),
+			beneficiary.clone(),
+			10 * PDEX,
+		));
+		let deposit = Event::Treasury(pallet_treasury::Event::Deposit { value: PDEX / 2 });
+		let fees = Event::FeeDistribution(fee_distribution::Event::Distributed {
+			total: PDEX,
+			treasury: PDEX / 2,
+			author: 3 * PDEX / 10,
+			burnt: PDEX / 5,
+		});
+		let asset_fees = Event::AssetFees(asset_fees::Event::AssetFeePaid {
+			who: AccountKeyring::Bob.to_account_id(),
+			asset: 1,
+			fee: 20,
+			tip: 2,
+		});
+		let first = chain.push(
+			Some(genesis),
+			vec![],
+			vec![events(vec![
+				(frame_system::Phase::ApplyExtrinsic(0), mint),
+				(frame_system::Phase::ApplyExtrinsic(0), deposit),
+				(frame_system::Phase::ApplyExtrinsic(0), fees),
+				(frame_system::Phase::ApplyExtrinsic(1), asset_fees),
+			])],
+		);
+		let unlock = UncheckedExtrinsic::new_unsigned(Call::PDEXMigration(
+			pdex_migration::pallet::Call::unlock {},
+		));
+		let remark = UncheckedExtrinsic::new_unsigned(Call::System(frame_system::Call::remark {
+			remark: vec![],
+		}));
+		let success = || {
+			Event::System(frame_system::Event::ExtrinsicSuccess {
+				dispatch_info: Default::default(),
+			})
+		};
+		let revert =
+			Event::PDEXMigration(pdex_migration::pallet::Event::RevertedMintedTokens(beneficiary));
+		let second = chain.push(
+			Some(first),
+			vec![remark, unlock],
+			vec![events(vec![
+				(frame_system::Phase::ApplyExtrinsic(0), success()),
+				(frame_system::Phase::ApplyExtrinsic(1), success()),
+				(frame_system::Phase::Finalization, revert),
+			])],
+		);
+		let mut follower = follower(chain, genesis);
+
+		// The first block is finalized along with the second one.
+		follower.on_finalized_block(second).unwrap();
+		follower.on_finalized_block(second).unwrap();
+		follower.on_finalized_block(first).unwrap();
+
+		let metrics = &follower.metrics;
+		assert_eq!(metrics.migration_mints.get(), 1);
+		assert_eq!(metrics.migration_minted.get(), 10.0);
+		assert_eq!(metrics.migration_unlocks.get(), 1);
+		assert_eq!(metrics.migration_reverts.get(), 1);
+		assert_eq!(metrics.treasury_deposits.get(), 0.5);
+		assert_eq!(metrics.fees.get(), 1.0);
+		assert_eq!(metrics.fee_shares.with_label_values(&["treasury"]).get(), 0.5);
+		assert_eq!(metrics.fee_shares.with_label_values(&["author"]).get(), 0.3);
+		assert_eq!(metrics.fee_shares.with_label_values(&["burnt"]).get(), 0.2);
+		assert_eq!(metrics.asset_fees.with_label_values(&["1"]).get(), 22.0);
+		assert_eq!(follower.finalized, (second, 2));
+	}
+
+	#[test]
+	fn best_block_state_is_read() {
+		use AccountKeyring::{Bob, Charlie, Dave};
+
+		let (first_tx, second_tx, minted_tx) =
+			(Hash::repeat_byte(1), Hash::repeat_byte(2), Hash::repeat_byte(3));
+		let treasury: AccountId = TreasuryPalletId::get().into_account();
+		let mut treasury_account =
+			frame_system::AccountInfo::<Index, pallet_balances::AccountData<Balance>>::default();
+		treasury_account.data.free = 42 * PDEX;
+		let mut chain = MockChain::default();
+		let genesis = chain.push(
+			None,
+			vec![],
+			vec![
+				attestations(first_tx, &[Bob]),
+				attestations(minted_tx, &[Bob, Charlie, Dave]),
+				(storage_prefix(b"PDEXMigration", b"MintableTokens"), (1_000 * PDEX).encode()),
+				(
+					StorageKey(frame_system::Account::<Runtime>::hashed_key_for(&treasury)),
+					treasury_account.encode(),
+				),
+			],
+		);
+		let burn_detected = Event::PDEXMigration(pdex_migration::pallet::Event::TokenBurnDetected(
+			second_tx,
+			Dave.to_account_id(),
+		));
+		let first = chain.push(
+			Some(genesis),
+			vec![],
+			vec![
+				attestations(first_tx, &[Bob, Charlie]),
+				attestations(second_tx, &[Dave]),
+				attestations(minted_tx, &[Bob, Charlie, Dave]),
+				events(vec![(frame_system::Phase::ApplyExtrinsic(0), burn_detected)]),
+			],
+		);
+		let second = chain.push(
+			Some(first),
+			vec![],
+			vec![
+				attestations(first_tx, &[Bob, Charlie, Dave]),
+				attestations(second_tx, &[Dave]),
+				attestations(minted_tx, &[Bob, Charlie, Dave]),
+			],
+		);
+		let mut follower = follower(chain, genesis);
+
+		// The attestations of the first best block are all read from the state.
+		follower.on_best_block(genesis).unwrap();
+		assert_eq!(follower.pending, HashSet::from([first_tx]));
+		assert_eq!(pending(&follower, Bob), Some(1));
+		assert_eq!(pending(&follower, Dave), None);
+		assert_eq!(follower.metrics.migration_mintable.get(), 1_000.0);
+		assert_eq!(follower.metrics.treasury_balance.get(), 42.0);
+
+		// New attestations are announced by events.
+		follower.on_best_block(first).unwrap();
+		assert_eq!(follower.pending, HashSet::from([first_tx, second_tx]));
+		assert_eq!(pending(&follower, Bob), Some(1));
+		assert_eq!(pending(&follower, Charlie), Some(1));
+		assert_eq!(pending(&follower, Dave), Some(1));
+
+		// Minted transactions are not pending anymore.
+		follower.on_best_block(second).unwrap();
+		assert_eq!(follower.pending, HashSet::from([second_tx]));
+		assert_eq!(pending(&follower, Bob), None);
+		assert_eq!(pending(&follower, Charlie), None);
+		assert_eq!(pending(&follower, Dave), Some(1));
+
+		// A reorg back to the genesis reads the state again.
+		follower.on_best_block(genesis).unwrap();
+		assert_eq!(follower.pending, HashSet::from([first_tx]));
+		assert_eq!(pending(&follower, Dave), None);
+	}
+}
@@ -30,13 +30,15 @@
 
 #![warn(missing_docs)]
 
+pub mod account_events;
+
 use std::sync::Arc;
 
 use grandpa::{
 	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
 };
 use polkadex_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Index};
-use sc_client_api::AuxStore;
+use sc_client_api::{AuxStore, BlockchainEvents, StorageProvider};
 use sc_consensus_babe::{Config, Epoch};
 use sc_consensus_babe_rpc::BabeRpcHandler;
 use sc_consensus_epochs::SharedEpochChanges;
@@ -104,13 +106,18 @@ where
 	C: ProvideRuntimeApi<Block>
 		+ HeaderBackend<Block>
 		+ AuxStore
+		+ BlockchainEvents<Block>
+		+ StorageProvider<Block, B>
 		+ HeaderMetadata<Block, Error = BlockChainError>
 		+ Sync
 		+ Send
 		+ 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
-	// C::Api: pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>,
+	C::Api: pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
+	C::Api: amm_rpc::AmmRuntimeApi<Block, u128, Balance>,
+	C::Api: vesting_rpc::VestingRuntimeApi<Block, AccountId, BlockNumber, Balance>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
@@ -119,8 +126,12 @@ where
 	B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
-	// use pallet_mmr_rpc::{MmrApi, Mmr};
+	use pallet_contracts_rpc::{Contracts, ContractsApi};
+	use pallet_mmr_rpc::{Mmr, MmrApi};
+	use amm_rpc::{Amm, AmmApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use vesting_rpc::{Vesting, VestingApi};
+	use account_events::{AccountEvents, AccountEventsApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
 	let FullDeps { client, pool, select_chain, chain_spec, deny_unsafe, babe, grandpa } = deps;
@@ -139,7 +150,16 @@ where
 	// more context: https://github.com/paritytech/substrate/pull/3480
 	// These RPCs should use an asynchronous caller instead.
 
+	// Generating proofs requires the node to run with `--enable-offchain-indexing true`.
+	io.extend_with(MmrApi::to_delegate(Mmr::new(client.clone())));
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
+	io.extend_with(AmmApi::to_delegate(Amm::new(client.clone())));
+	io.extend_with(ContractsApi::to_delegate(Contracts::new(client.clone())));
+	io.extend_with(VestingApi::to_delegate(Vesting::new(client.clone())));
+	io.extend_with(AccountEventsApi::to_delegate(AccountEvents::new(
+		client.clone(),
+		subscription_executor.clone(),
+	)));
 	io.extend_with(sc_consensus_babe_rpc::BabeApi::to_delegate(BabeRpcHandler::new(
 		client.clone(),
 		shared_epoch_changes.clone(),
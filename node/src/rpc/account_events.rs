@@ -0,0 +1,748 @@
+//! Subscription to the events involving some accounts, for trading bots and wallets.
+//!
+//! The events of the blocks entering the best chain are decoded with the types of the native
+//! runtime and matched against the subscribed accounts, so that clients do not have to follow
+//! `System::Events` and filter it themselves. An event involves an account if the account is in
+//! its topics or anywhere in its fields, which is found in its encoding. The blocks whose events
+//! were notified are notified again when they are finalized or retracted, by a reorg or by the
+//! finalization of another fork. A block whose events can not be read ends the subscription with
+//! an error, rather than silently leaving a gap in the notified blocks.
+
+use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+
+use codec::{Decode, Encode};
+use futures::{future, stream, FutureExt, SinkExt, StreamExt};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use node_polkadex_runtime::Event;
+use polkadex_primitives::{AccountId, Block, BlockNumber, Hash};
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::{HeaderBackend, TreeRoute};
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+
+/// Accounts a subscription can follow at most.
+const MAX_ACCOUNTS: usize = 100;
+/// Code of the error ending a subscription when the events of a block can not be read.
+const EVENTS_UNREADABLE: i64 = 1;
+
+/// Event of a block involving a subscribed account.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountEvent {
+	/// Index of the extrinsic which emitted the event, none for the initialization and the
+	/// finalization of the block.
+	pub extrinsic: Option<u32>,
+	/// Name of the pallet which emitted the event in the runtime.
+	pub pallet: String,
+	/// The event, formatted with the runtime types.
+	pub event: String,
+}
+
+/// Notification of the account events subscription.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum AccountEventsNotification {
+	/// Events of a block entering the best chain.
+	#[serde(rename_all = "camelCase")]
+	Events {
+		/// Hash of the block.
+		block_hash: Hash,
+		/// Number of the block.
+		block_number: BlockNumber,
+		/// Whether the block is already finalized, otherwise it is notified again once finalized
+		/// or retracted.
+		finalized: bool,
+		/// Events of the block involving the subscribed accounts.
+		events: Vec<AccountEvent>,
+	},
+	/// A block whose events were notified is finalized.
+	#[serde(rename_all = "camelCase")]
+	Finalized {
+		/// Hash of the block.
+		block_hash: Hash,
+		/// Number of the block.
+		block_number: BlockNumber,
+	},
+	/// A block whose events were notified left the best chain, and its events are void.
+	#[serde(rename_all = "camelCase")]
+	Retracted {
+		/// Hash of the block.
+		block_hash: Hash,
+		/// Number of the block.
+		block_number: BlockNumber,
+	},
+}
+
+/// Subscription to the events involving some accounts.
+#[rpc]
+pub trait AccountEventsApi<Notification, AccountId> {
+	/// RPC metadata
+	type Metadata;
+
+	/// Subscribes to the events involving `accounts`, of the given `pallets` only if any, in the
+	/// blocks entering the best chain, and to the finalization and retraction of these blocks.
+	#[pubsub(
+		subscription = "polkadex_accountEvents",
+		subscribe,
+		name = "polkadex_subscribeAccountEvents"
+	)]
+	fn subscribe_account_events(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<Notification>,
+		accounts: Vec<AccountId>,
+		pallets: Option<Vec<String>>,
+	);
+
+	/// Unsubscribes from the events of some accounts.
+	#[pubsub(
+		subscription = "polkadex_accountEvents",
+		unsubscribe,
+		name = "polkadex_unsubscribeAccountEvents"
+	)]
+	fn unsubscribe_account_events(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool>;
+}
+
+/// A struct that implements the [`AccountEventsApi`].
+pub struct AccountEvents<C, B> {
+	client: Arc<C>,
+	manager: SubscriptionManager,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> AccountEvents<C, B> {
+	/// Create new `AccountEvents` with the given reference to the client, running the
+	/// subscriptions on `executor`.
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, manager: SubscriptionManager::new(Arc::new(executor)), _marker: PhantomData }
+	}
+}
+
+impl<C, B> AccountEventsApi<AccountEventsNotification, AccountId> for AccountEvents<C, B>
+where
+	C: HeaderBackend<Block>
+		+ BlockchainEvents<Block>
+		+ StorageProvider<Block, B>
+		+ Send
+		+ Sync
+		+ 'static,
+	B: Backend<Block> + Send + Sync + 'static,
+{
+	type Metadata = sc_rpc::Metadata;
+
+	fn subscribe_account_events(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<AccountEventsNotification>,
+		accounts: Vec<AccountId>,
+		pallets: Option<Vec<String>>,
+	) {
+		if accounts.is_empty() || accounts.len() > MAX_ACCOUNTS {
+			let _ = subscriber.reject(RpcError::invalid_params(format!(
+				"Expected 1 to {} accounts, got {}",
+				MAX_ACCOUNTS,
+				accounts.len()
+			)));
+			return
+		}
+
+		let mut follower = Follower {
+			client: self.client.clone(),
+			filter: Filter { accounts, pallets },
+			notified: HashMap::new(),
+			_marker: PhantomData,
+		};
+		let best = self.client.import_notification_stream().filter_map(|block| {
+			future::ready(block.is_new_best.then(|| ChainEvent::Best {
+				hash: block.hash,
+				number: *block.header.number(),
+				tree_route: block.tree_route,
+			}))
+		});
+		let finalized = self
+			.client
+			.finality_notification_stream()
+			.map(|block| ChainEvent::Finalized { number: *block.header.number() });
+		let notifications = stream::select(best, finalized)
+			.flat_map(move |event| stream::iter(follower.follow(event)))
+			// Nothing is notified after an error, the subscription ends with it.
+			.scan(false, |failed, notification| {
+				let ended = *failed;
+				*failed = notification.is_err();
+				future::ready((!ended).then(|| notification))
+			})
+			.map(Ok::<_, ()>);
+
+		self.manager.add(subscriber, |sink| {
+			notifications
+				.forward(sink.sink_map_err(|e| {
+					log::warn!("Error sending account events notifications: {:?}", e)
+				}))
+				.map(drop)
+		});
+	}
+
+	fn unsubscribe_account_events(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
+
+/// Change of the chain followed by a subscription.
+enum ChainEvent {
+	/// New best block, with the route from the previous best block to its parent.
+	Best { hash: Hash, number: BlockNumber, tree_route: Option<Arc<TreeRoute<Block>>> },
+	Finalized { number: BlockNumber },
+}
+
+/// Events of interest to a subscription.
+struct Filter {
+	accounts: Vec<AccountId>,
+	pallets: Option<Vec<String>>,
+}
+
+impl Filter {
+	/// The event of `record` if it involves one of the accounts.
+	fn matches(&self, record: &frame_system::EventRecord<Event, Hash>) -> Option<AccountEvent> {
+		let encoded = record.event.encode();
+		let involved = self.accounts.iter().any(|account| {
+			let account: &[u8] = account.as_ref();
+			record.topics.iter().any(|topic| topic.as_bytes() == account) ||
+				encoded.windows(account.len()).any(|bytes| bytes == account)
+		});
+		if !involved {
+			return None
+		}
+		// The runtime event is a variant named after the pallet, around the event of the pallet.
+		let event = format!("{:?}", record.event);
+		let pallet = event.split('(').next().unwrap_or_default().to_string();
+		if let Some(pallets) = &self.pallets {
+			if !pallets.contains(&pallet) {
+				return None
+			}
+		}
+		let extrinsic = match record.phase {
+			frame_system::Phase::ApplyExtrinsic(index) => Some(index),
+			_ => None,
+		};
+		Some(AccountEvent { extrinsic, pallet, event })
+	}
+}
+
+/// Follower of the chain for a subscription.
+struct Follower<C, B> {
+	client: Arc<C>,
+	filter: Filter,
+	/// Blocks whose events were notified and which are not finalized yet.
+	notified: HashMap<Hash, BlockNumber>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Follower<C, B>
+where
+	C: HeaderBackend<Block> + StorageProvider<Block, B>,
+	B: Backend<Block>,
+{
+	/// Notifications of `event`, ending with an error if the events of a block can not be read.
+	fn follow(&mut self, event: ChainEvent) -> Vec<Result<AccountEventsNotification>> {
+		let mut notifications = vec![];
+		match event {
+			ChainEvent::Best { hash, number, tree_route } => {
+				if let Some(tree_route) = tree_route {
+					for block in tree_route.retracted() {
+						if self.notified.remove(&block.hash).is_some() {
+							notifications.push(Ok(AccountEventsNotification::Retracted {
+								block_hash: block.hash,
+								block_number: block.number,
+							}));
+						}
+					}
+					// Blocks of the new best chain imported while on another fork.
+					for block in tree_route.enacted() {
+						match self.events(block.hash, block.number) {
+							Ok(events) => notifications.extend(events.map(Ok)),
+							Err(e) => {
+								notifications.push(Err(e));
+								return notifications
+							},
+						}
+					}
+				}
+				notifications.extend(self.events(hash, number).transpose());
+			},
+			ChainEvent::Finalized { number } => {
+				let mut finalized: Vec<_> = self
+					.notified
+					.iter()
+					.filter(|(_, block_number)| **block_number <= number)
+					.map(|(hash, number)| (*hash, *number))
+					.collect();
+				finalized.sort_by_key(|(_, number)| *number);
+				for (block_hash, block_number) in finalized {
+					self.notified.remove(&block_hash);
+					// Blocks of the other forks are pruned at the finalization of this fork.
+					let canonical = self.client.hash(block_number).ok().flatten();
+					notifications.push(Ok(if canonical == Some(block_hash) {
+						AccountEventsNotification::Finalized { block_hash, block_number }
+					} else {
+						AccountEventsNotification::Retracted { block_hash, block_number }
+					}));
+				}
+			},
+		}
+		notifications
+	}
+
+	/// Notification of the events of block `hash` involving the accounts, if any.
+	fn events(
+		&mut self,
+		hash: Hash,
+		number: BlockNumber,
+	) -> Result<Option<AccountEventsNotification>> {
+		let key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+		let records = match self.client.storage(&BlockId::Hash(hash), &key) {
+			Ok(Some(data)) =>
+				Vec::<frame_system::EventRecord<Event, Hash>>::decode(&mut &data.0[..])
+					.map_err(|e| e.to_string()),
+			Ok(None) => Ok(vec![]),
+			Err(e) => Err(e.to_string()),
+		};
+		let records = records.map_err(|e| {
+			log::warn!("Events of block {:?} can not be read: {}", hash, e);
+			RpcError {
+				code: ErrorCode::ServerError(EVENTS_UNREADABLE),
+				message: format!("Unable to read the events of block {:?}.", hash),
+				data: Some(e.into()),
+			}
+		})?;
+		let events: Vec<_> =
+			records.iter().filter_map(|record| self.filter.matches(record)).collect();
+		if events.is_empty() {
+			return Ok(None)
+		}
+		let finalized = number <= self.client.info().finalized_number;
+		if !finalized {
+			self.notified.insert(hash, number);
+		}
+		Ok(Some(AccountEventsNotification::Events {
+			block_hash: hash,
+			block_number: number,
+			finalized,
+			events,
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use node_polkadex_runtime::constants::currency::PDEX;
+	use polkadex_primitives::Header;
+	use sc_client_api::{in_mem::Backend as InMemoryBackend, KeyIterator};
+	use sp_blockchain::{BlockStatus, CachedHeaderMetadata, HeaderMetadata, Info};
+	use sp_core::storage::{ChildInfo, StorageData};
+	use sp_keyring::AccountKeyring::{Alice, Bob, Charlie};
+	use sp_runtime::generic::Digest;
+
+	use super::*;
+
+	type State = <InMemoryBackend<Block> as Backend<Block>>::State;
+
+	fn transfer(from: AccountId, to: AccountId) -> frame_system::EventRecord<Event, Hash> {
+		frame_system::EventRecord {
+			phase: frame_system::Phase::ApplyExtrinsic(1),
+			event: Event::Balances(pallet_balances::Event::Transfer { from, to, amount: PDEX }),
+			topics: vec![],
+		}
+	}
+
+	/// Encoded events of a block, with a transfer to Bob if `to_bob`.
+	fn events(to_bob: bool) -> Vec<u8> {
+		let records = if to_bob {
+			vec![transfer(Alice.to_account_id(), Bob.to_account_id())]
+		} else {
+			vec![]
+		};
+		records.encode()
+	}
+
+	/// Tree of blocks with their encoded events, and the canonical chain chosen by the test.
+	#[derive(Default)]
+	struct MockChain {
+		headers: HashMap<Hash, Header>,
+		events: HashMap<Hash, Vec<u8>>,
+		canonical: HashMap<BlockNumber, Hash>,
+	}
+
+	impl MockChain {
+		/// Adds a block on top of `parent`, the genesis if `None`, and returns its hash.
+		fn push(&mut self, parent: Option<Hash>, events: Vec<u8>) -> Hash {
+			let (parent_hash, number) = match parent {
+				Some(parent) => (parent, self.headers[&parent].number() + 1),
+				None => (Hash::default(), 0),
+			};
+			// Blocks of different forks differ by their extrinsics root.
+			let header = Header::new(
+				number,
+				Hash::from_low_u64_be(self.headers.len() as u64),
+				Default::default(),
+				parent_hash,
+				Digest::default(),
+			);
+			let hash = header.hash();
+			self.headers.insert(hash, header);
+			self.events.insert(hash, events);
+			hash
+		}
+
+		/// Makes the chain ending at `block` canonical.
+		fn set_canonical(&mut self, block: Hash) {
+			let mut hash = block;
+			while let Some(header) = self.headers.get(&hash) {
+				self.canonical.insert(*header.number(), hash);
+				hash = *header.parent_hash();
+			}
+		}
+
+		fn number_of(&self, block: Hash) -> BlockNumber {
+			*self.headers[&block].number()
+		}
+	}
+
+	impl HeaderBackend<Block> for MockChain {
+		fn header(&self, id: BlockId<Block>) -> sp_blockchain::Result<Option<Header>> {
+			Ok(match id {
+				BlockId::Hash(hash) => self.headers.get(&hash).cloned(),
+				BlockId::Number(number) =>
+					self.canonical.get(&number).map(|hash| self.headers[hash].clone()),
+			})
+		}
+
+		fn info(&self) -> Info<Block> {
+			Info {
+				best_hash: Default::default(),
+				best_number: 0,
+				genesis_hash: Default::default(),
+				finalized_hash: Default::default(),
+				finalized_number: 0,
+				finalized_state: None,
+				number_leaves: 0,
+				block_gap: None,
+			}
+		}
+
+		fn status(&self, id: BlockId<Block>) -> sp_blockchain::Result<BlockStatus> {
+			Ok(match self.header(id)? {
+				Some(_) => BlockStatus::InChain,
+				None => BlockStatus::Unknown,
+			})
+		}
+
+		fn number(&self, hash: Hash) -> sp_blockchain::Result<Option<BlockNumber>> {
+			Ok(self.headers.get(&hash).map(|header| *header.number()))
+		}
+
+		fn hash(&self, number: BlockNumber) -> sp_blockchain::Result<Option<Hash>> {
+			Ok(self.canonical.get(&number).cloned())
+		}
+	}
+
+	impl HeaderMetadata<Block> for MockChain {
+		type Error = sp_blockchain::Error;
+
+		fn header_metadata(
+			&self,
+			hash: Hash,
+		) -> sp_blockchain::Result<CachedHeaderMetadata<Block>> {
+			self.headers
+				.get(&hash)
+				.map(CachedHeaderMetadata::from)
+				.ok_or_else(|| sp_blockchain::Error::UnknownBlock(format!("{:?}", hash)))
+		}
+
+		fn insert_header_metadata(&self, _: Hash, _: CachedHeaderMetadata<Block>) {}
+
+		fn remove_header_metadata(&self, _: Hash) {}
+	}
+
+	impl StorageProvider<Block, InMemoryBackend<Block>> for MockChain {
+		fn storage(
+			&self,
+			id: &BlockId<Block>,
+			key: &StorageKey,
+		) -> sp_blockchain::Result<Option<StorageData>> {
+			assert_eq!(key.0, [twox_128(b"System"), twox_128(b"Events")].concat());
+			let hash = match id {
+				BlockId::Hash(hash) => *hash,
+				BlockId::Number(_) => unimplemented!("Events are read by hash"),
+			};
+			Ok(self.events.get(&hash).cloned().map(StorageData))
+		}
+
+		fn storage_keys(
+			&self,
+			_: &BlockId<Block>,
+			_: &StorageKey,
+		) -> sp_blockchain::Result<Vec<StorageKey>> {
+			unimplemented!()
+		}
+
+		fn storage_hash(
+			&self,
+			_: &BlockId<Block>,
+			_: &StorageKey,
+		) -> sp_blockchain::Result<Option<Hash>> {
+			unimplemented!()
+		}
+
+		fn storage_pairs(
+			&self,
+			_: &BlockId<Block>,
+			_: &StorageKey,
+		) -> sp_blockchain::Result<Vec<(StorageKey, StorageData)>> {
+			unimplemented!()
+		}
+
+		fn storage_keys_iter<'a>(
+			&self,
+			_: &BlockId<Block>,
+			_: Option<&'a StorageKey>,
+			_: Option<&StorageKey>,
+		) -> sp_blockchain::Result<KeyIterator<'a, State, Block>> {
+			unimplemented!()
+		}
+
+		fn child_storage(
+			&self,
+			_: &BlockId<Block>,
+			_: &ChildInfo,
+			_: &StorageKey,
+		) -> sp_blockchain::Result<Option<StorageData>> {
+			unimplemented!()
+		}
+
+		fn child_storage_keys(
+			&self,
+			_: &BlockId<Block>,
+			_: &ChildInfo,
+			_: &StorageKey,
+		) -> sp_blockchain::Result<Vec<StorageKey>> {
+			unimplemented!()
+		}
+
+		fn child_storage_keys_iter<'a>(
+			&self,
+			_: &BlockId<Block>,
+			_: ChildInfo,
+			_: Option<&'a StorageKey>,
+			_: Option<&StorageKey>,
+		) -> sp_blockchain::Result<KeyIterator<'a, State, Block>> {
+			unimplemented!()
+		}
+
+		fn child_storage_hash(
+			&self,
+			_: &BlockId<Block>,
+			_: &ChildInfo,
+			_: &StorageKey,
+		) -> sp_blockchain::Result<Option<Hash>> {
+			unimplemented!()
+		}
+	}
+
+	fn follower(chain: MockChain) -> Follower<MockChain, InMemoryBackend<Block>> {
+		Follower {
+			client: Arc::new(chain),
+			filter: Filter { accounts: vec![Bob.to_account_id()], pallets: None },
+			notified: HashMap::new(),
+			_marker: PhantomData,
+		}
+	}
+
+	/// New best block `block`, previously best `previous_best` if it is on another fork.
+	fn best(
+		follower: &Follower<MockChain, InMemoryBackend<Block>>,
+		block: Hash,
+		previous_best: Option<Hash>,
+	) -> ChainEvent {
+		let chain = &follower.client;
+		let header = &chain.headers[&block];
+		let tree_route = previous_best.map(|previous_best| {
+			Arc::new(
+				sp_blockchain::tree_route(&**chain, previous_best, *header.parent_hash()).unwrap(),
+			)
+		});
+		ChainEvent::Best { hash: block, number: *header.number(), tree_route }
+	}
+
+	/// Kinds and blocks of `notifications`.
+	fn notified(
+		notifications: Vec<Result<AccountEventsNotification>>,
+	) -> Vec<(&'static str, Hash)> {
+		notifications
+			.into_iter()
+			.map(|notification| match notification.unwrap() {
+				AccountEventsNotification::Events { block_hash, finalized, .. } => {
+					assert!(!finalized);
+					("events", block_hash)
+				},
+				AccountEventsNotification::Finalized { block_hash, .. } =>
+					("finalized", block_hash),
+				AccountEventsNotification::Retracted { block_hash, .. } =>
+					("retracted", block_hash),
+			})
+			.collect()
+	}
+
+	#[test]
+	fn events_involving_the_accounts_match() {
+		let filter = Filter { accounts: vec![Bob.to_account_id()], pallets: None };
+		let event = filter.matches(&transfer(Alice.to_account_id(), Bob.to_account_id())).unwrap();
+		assert_eq!(event.extrinsic, Some(1));
+		assert_eq!(event.pallet, "Balances");
+		assert!(event.event.starts_with("Balances(Transfer {"), "{}", event.event);
+		assert!(filter
+			.matches(&transfer(Alice.to_account_id(), Charlie.to_account_id()))
+			.is_none());
+
+		let mut record = transfer(Alice.to_account_id(), Charlie.to_account_id());
+		record.phase = frame_system::Phase::Finalization;
+		record.topics = vec![Hash::from(Bob.to_raw_public())];
+		assert_eq!(filter.matches(&record).unwrap().extrinsic, None);
+	}
+
+	#[test]
+	fn events_of_other_pallets_do_not_match() {
+		let record = transfer(Alice.to_account_id(), Bob.to_account_id());
+		let filter = |pallets: &[&str]| Filter {
+			accounts: vec![Bob.to_account_id()],
+			pallets: Some(pallets.iter().map(|pallet| pallet.to_string()).collect()),
+		};
+		assert!(filter(&["Balances", "Assets"]).matches(&record).is_some());
+		assert!(filter(&["Assets"]).matches(&record).is_none());
+	}
+
+	#[test]
+	fn notifications_are_tagged_by_type() {
+		let notification = AccountEventsNotification::Retracted {
+			block_hash: Hash::repeat_byte(1),
+			block_number: 7,
+		};
+		assert_eq!(
+			serde_json::to_value(&notification).unwrap(),
+			serde_json::json!({
+				"type": "retracted",
+				"blockHash": format!("{:?}", Hash::repeat_byte(1)),
+				"blockNumber": 7,
+			})
+		);
+	}
+
+	#[test]
+	fn reorgs_retract_the_notified_blocks() {
+		let mut chain = MockChain::default();
+		let genesis = chain.push(None, events(false));
+		let a1 = chain.push(Some(genesis), events(true));
+		let a2 = chain.push(Some(a1), events(true));
+		let b1 = chain.push(Some(genesis), events(true));
+		let b2 = chain.push(Some(b1), events(false));
+		let b3 = chain.push(Some(b2), events(true));
+		chain.set_canonical(b3);
+		let mut follower = follower(chain);
+
+		let event = best(&follower, a1, None);
+		assert_eq!(notified(follower.follow(event)), [("events", a1)]);
+		let event = best(&follower, a2, None);
+		assert_eq!(notified(follower.follow(event)), [("events", a2)]);
+
+		// The fork of `b3` is enacted, its blocks imported while on the other fork included.
+		let event = best(&follower, b3, Some(a2));
+		assert_eq!(
+			notified(follower.follow(event)),
+			[("retracted", a2), ("retracted", a1), ("events", b1), ("events", b3)]
+		);
+		assert_eq!(follower.notified, HashMap::from([(b1, 1), (b3, 3)]));
+	}
+
+	#[test]
+	fn finality_skipping_blocks_settles_them_all() {
+		let mut chain = MockChain::default();
+		let genesis = chain.push(None, events(false));
+		let a1 = chain.push(Some(genesis), events(true));
+		let b1 = chain.push(Some(genesis), events(true));
+		let b2 = chain.push(Some(b1), events(true));
+		let b3 = chain.push(Some(b2), events(true));
+		chain.set_canonical(b3);
+		let mut follower = follower(chain);
+
+		for (block, previous_best) in [(a1, None), (b1, Some(a1)), (b2, None), (b3, None)] {
+			let event = best(&follower, block, previous_best);
+			follower.follow(event);
+		}
+		// `a1` was retracted by the reorg already.
+		assert_eq!(follower.notified.len(), 3);
+
+		// The finality of block 3 skips blocks 1 and 2, which are finalized along with it.
+		let number = follower.client.number_of(b3);
+		assert_eq!(
+			notified(follower.follow(ChainEvent::Finalized { number })),
+			[("finalized", b1), ("finalized", b2), ("finalized", b3)]
+		);
+		assert!(follower.notified.is_empty());
+	}
+
+	#[test]
+	fn finality_of_another_fork_retracts_the_notified_blocks() {
+		let mut chain = MockChain::default();
+		let genesis = chain.push(None, events(false));
+		let a1 = chain.push(Some(genesis), events(true));
+		let a2 = chain.push(Some(a1), events(true));
+		let b1 = chain.push(Some(genesis), events(false));
+		let b2 = chain.push(Some(b1), events(false));
+		chain.set_canonical(b2);
+		let mut follower = follower(chain);
+
+		for block in [a1, a2] {
+			let event = best(&follower, block, None);
+			follower.follow(event);
+		}
+
+		// The fork of `b2` is finalized without having been the best chain.
+		assert_eq!(
+			notified(follower.follow(ChainEvent::Finalized { number: 2 })),
+			[("retracted", a1), ("retracted", a2)]
+		);
+		assert!(follower.notified.is_empty());
+	}
+
+	#[test]
+	fn unreadable_events_end_the_notifications_with_an_error() {
+		let mut chain = MockChain::default();
+		let genesis = chain.push(None, events(false));
+		let a1 = chain.push(Some(genesis), events(true));
+		let b1 = chain.push(Some(genesis), events(true));
+		let b2 = chain.push(Some(b1), vec![1]);
+		let b3 = chain.push(Some(b2), events(true));
+		let mut follower = follower(chain);
+
+		let event = best(&follower, a1, None);
+		follower.follow(event);
+		let event = best(&follower, b3, Some(a1));
+		let mut notifications = follower.follow(event);
+		let error = notifications.pop().unwrap().unwrap_err();
+		assert_eq!(error.code, ErrorCode::ServerError(EVENTS_UNREADABLE));
+		assert!(error.message.contains(&format!("{:?}", b2)), "{}", error.message);
+		// Nothing is notified after the block whose events can not be read.
+		assert_eq!(notified(notifications), [("retracted", a1), ("events", b1)]);
+	}
+}
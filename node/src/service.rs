@@ -20,7 +20,10 @@
 
 //! Service implementation. Specialized wrapper over substrate service.
 
-use crate::rpc as node_rpc;
+use crate::{
+	chain_spec::{GrandpaExtension, GrandpaVotingRule},
+	rpc as node_rpc,
+};
 use futures::prelude::*;
 use node_executor::ExecutorDispatch;
 use node_polkadex_runtime::RuntimeApi;
@@ -29,16 +32,24 @@ use sc_client_api::{BlockBackend, ExecutorProvider};
 use sc_executor::NativeElseWasmExecutor;
 use sc_network::{Event, NetworkService};
 use sc_service::{
-	config::Configuration, error::Error as ServiceError, RpcExtensionBuilder, TaskManager,
+	config::Configuration, error::Error as ServiceError, ChainType, RpcExtensionBuilder,
+	RpcHandlers, TaskManager,
 };
 use sp_runtime::traits::Block as BlockT;
 use std::sync::Arc;
 
 use sc_consensus_babe::SlotProportion;
+use sc_consensus_manual_seal::{
+	consensus::babe::{BabeConsensusDataProvider, SlotTimestampProvider},
+	rpc::{ManualSeal, ManualSealApi},
+	EngineCommand, ManualSealParams,
+};
 use sc_telemetry::{Telemetry, TelemetryWorker};
+use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_core::Pair;
 use sp_runtime::{generic, SaturatedConversion};
+use std::{str::FromStr, time::Duration};
 
 pub type FullClient =
 	sc_service::TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<ExecutorDispatch>>;
@@ -62,7 +73,7 @@ pub fn fetch_nonce(client: &FullClient, account: sp_core::sr25519::Pair) -> u32
 /// Create a transaction using the given `call`.
 ///
 /// The transaction will be signed by `sender`. If `nonce` is `None` it will be fetched from the
-/// state of the best block.
+/// state of the best block. Fees are paid in `fee_asset`, or in PDEX if it is `None`.
 ///
 /// Note: Should only be used for tests.
 #[allow(dead_code)]
@@ -71,6 +82,7 @@ pub fn create_extrinsic(
 	sender: sp_core::sr25519::Pair,
 	function: impl Into<node_polkadex_runtime::Call>,
 	nonce: Option<u32>,
+	fee_asset: Option<u128>,
 ) -> node_polkadex_runtime::UncheckedExtrinsic {
 	let function = function.into();
 	let genesis_hash = client.block_hash(0).ok().flatten().expect("Genesis block exists; qed");
@@ -94,7 +106,10 @@ pub fn create_extrinsic(
 		)),
 		frame_system::CheckNonce::<node_polkadex_runtime::Runtime>::from(nonce),
 		frame_system::CheckWeight::<node_polkadex_runtime::Runtime>::new(),
-		pallet_transaction_payment::ChargeTransactionPayment::<node_polkadex_runtime::Runtime>::from(tip),
+		feeless::CheckFeeless::<node_polkadex_runtime::Runtime>::new(),
+		pallet_asset_tx_payment::ChargeAssetTxPayment::<node_polkadex_runtime::Runtime>::from(
+			tip, fee_asset,
+		),
 	);
 
 	let raw_payload = node_polkadex_runtime::SignedPayload::from_raw(
@@ -109,6 +124,7 @@ pub fn create_extrinsic(
 			(),
 			(),
 			(),
+			(),
 		),
 	);
 	use codec::Encode;
@@ -291,11 +307,13 @@ pub struct NewFullBase {
 	pub client: Arc<FullClient>,
 	pub network: Arc<NetworkService<Block, <Block as BlockT>::Hash>>,
 	pub transaction_pool: Arc<sc_transaction_pool::FullPool<Block, FullClient>>,
+	pub rpc_handlers: RpcHandlers,
 }
 
 /// Creates a full service from the configuration.
 pub fn new_full_base(
 	mut config: Configuration,
+	grandpa_overrides: &GrandpaOverrides,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<Block, FullClient, FullGrandpaBlockImport>,
 		&sc_consensus_babe::BabeLink<Block>,
@@ -312,6 +330,8 @@ pub fn new_full_base(
 		other: (rpc_extensions_builder, import_setup, rpc_setup, mut telemetry),
 	} = new_partial(&config)?;
 
+	let grandpa_extension =
+		grandpa_overrides.apply(&*config.chain_spec).map_err(ServiceError::Other)?;
 	let shared_voter_state = rpc_setup;
 	let auth_disc_publish_non_global_ips = config.network.allow_non_globals_in_dht;
 	let grandpa_protocol_name = grandpa::protocol_standard_name(
@@ -368,7 +388,16 @@ pub fn new_full_base(
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
-	let _rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+	if let Some(registry) = prometheus_registry.as_ref() {
+		let metrics = crate::metrics::Metrics::register(registry)?;
+		task_manager.spawn_handle().spawn(
+			"polkadex-metrics",
+			None,
+			crate::metrics::run(client.clone(), metrics),
+		);
+	}
+
+	let rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		config,
 		backend,
 		client: client.clone(),
@@ -479,31 +508,39 @@ pub fn new_full_base(
 	let keystore =
 		if role.is_authority() { Some(keystore_container.sync_keystore()) } else { None };
 
+	let run_observer = grandpa_extension.observer && !role.is_authority();
 	let config = grandpa::Config {
-		// FIXME #1578 make this available through chainspec
-		gossip_duration: std::time::Duration::from_millis(333),
-		justification_period: 512,
+		gossip_duration: Duration::from_millis(grandpa_extension.gossip_duration),
+		justification_period: grandpa_extension.justification_period,
 		name: Some(name),
-		observer_enabled: false,
+		observer_enabled: grandpa_extension.observer,
 		keystore,
 		telemetry: telemetry.as_ref().map(|x| x.handle()),
 		local_role: role,
 		protocol_name: grandpa_protocol_name,
 	};
 
-	if enable_grandpa {
+	if enable_grandpa && run_observer {
+		// the chain spec opted the non-authorities into the GRANDPA observer, which
+		// follows the finality of the authorities without voting.
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"grandpa-observer",
+			None,
+			grandpa::run_grandpa_observer(config, grandpa_link, network.clone())?,
+		);
+	} else if enable_grandpa {
 		// start the full GRANDPA voter
 		// NOTE: non-authorities could run the GRANDPA observer protocol, but at
 		// this point the full voter should provide better guarantees of block
 		// and vote data availability than the observer. The observer has not
 		// been tested extensively yet and having most nodes in a network run it
-		// could lead to finality stalls.
+		// could lead to finality stalls, so it is opt-in through the chain spec.
 		let grandpa_config = grandpa::GrandpaParams {
 			config,
 			link: grandpa_link,
 			network: network.clone(),
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
-			voting_rule: grandpa::VotingRulesBuilder::default().build(),
+			voting_rule: voting_rule(grandpa_extension.voting_rule).build(),
 			prometheus_registry,
 			shared_voter_state,
 		};
@@ -518,12 +555,268 @@ pub fn new_full_base(
 	}
 
 	network_starter.start_network();
-	Ok(NewFullBase { task_manager, client, network, transaction_pool })
+	Ok(NewFullBase { task_manager, client, network, transaction_pool, rpc_handlers })
+}
+
+/// Rules of the GRANDPA voter restricting the blocks voted on according to `rule`.
+fn voting_rule(rule: GrandpaVotingRule) -> grandpa::VotingRulesBuilder<Block, FullClient> {
+	match rule {
+		GrandpaVotingRule::Default => grandpa::VotingRulesBuilder::default(),
+		GrandpaVotingRule::Best => grandpa::VotingRulesBuilder::new(),
+		GrandpaVotingRule::BeforeBestBlockBy(blocks) =>
+			grandpa::VotingRulesBuilder::new().add(grandpa::BeforeBestBlockBy(blocks)),
+		GrandpaVotingRule::ThreeQuartersOfTheUnfinalizedChain =>
+			grandpa::VotingRulesBuilder::new().add(grandpa::ThreeQuartersOfTheUnfinalizedChain),
+	}
+}
+
+/// GRANDPA parameters given on the command line, overriding the `grandpa` extension of the chain
+/// spec.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct GrandpaOverrides {
+	/// Interval between the gossip of GRANDPA votes, in milliseconds.
+	#[clap(long = "grandpa-gossip-duration", value_name = "MS")]
+	pub gossip_duration: Option<u64>,
+
+	/// Number of blocks between the GRANDPA justifications stored and sent to the light clients.
+	#[clap(long = "grandpa-justification-period", value_name = "BLOCKS")]
+	pub justification_period: Option<u32>,
+
+	/// Whether the nodes which are not authorities run the GRANDPA observer instead of the voter.
+	#[clap(long = "grandpa-observer", value_name = "BOOL")]
+	pub observer: Option<bool>,
+
+	/// Rule restricting the blocks voted on by GRANDPA.
+	#[clap(
+		long = "grandpa-voting-rule",
+		value_name = "default|best|before-best-block-by=<n>|three-quarters-of-the-unfinalized-chain"
+	)]
+	pub voting_rule: Option<GrandpaVotingRule>,
+}
+
+impl GrandpaOverrides {
+	/// The validated `grandpa` extension of `chain_spec` with the overridden parameters.
+	pub fn apply(
+		&self,
+		chain_spec: &dyn sc_service::ChainSpec,
+	) -> Result<GrandpaExtension, String> {
+		let mut extension =
+			sc_chain_spec::get_extension::<GrandpaExtension>(chain_spec.extensions())
+				.cloned()
+				.unwrap_or_default();
+		if let Some(gossip_duration) = self.gossip_duration {
+			extension.gossip_duration = gossip_duration;
+		}
+		if let Some(justification_period) = self.justification_period {
+			extension.justification_period = justification_period;
+		}
+		if let Some(observer) = self.observer {
+			extension.observer = observer;
+		}
+		if let Some(voting_rule) = self.voting_rule {
+			extension.voting_rule = voting_rule;
+		}
+		extension.validate()?;
+		Ok(extension)
+	}
+}
+
+/// How a development node started with `--sealing` authors its blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sealing {
+	/// A block is authored as soon as a transaction enters the pool.
+	Instant,
+	/// Blocks are only authored on `engine_createBlock`.
+	Manual,
+	/// A block, possibly empty, is authored every given number of milliseconds.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Sealing::Instant),
+			"manual" => Ok(Sealing::Manual),
+			_ => s
+				.strip_prefix("interval=")
+				.and_then(|millis| millis.parse().ok())
+				.filter(|millis| *millis > 0)
+				.map(Sealing::Interval)
+				.ok_or_else(|| {
+					format!("Unknown sealing {}, expected instant, manual or interval=<ms>", s)
+				}),
+		}
+	}
+}
+
+/// Checks that blocks of `chain_spec` may be authored with manual seal, which is only meant for
+/// development and local chains.
+pub fn ensure_manual_seal_allowed(chain_spec: &dyn sc_service::ChainSpec) -> Result<(), String> {
+	match chain_spec.chain_type() {
+		ChainType::Development | ChainType::Local => Ok(()),
+		chain_type => Err(format!(
+			"--sealing is only available on development and local chains, {} is {:?}",
+			chain_spec.id(),
+			chain_type
+		)),
+	}
+}
+
+/// Command sealing a new block and finalizing it at once.
+fn seal_new_block(create_empty: bool) -> EngineCommand<<Block as BlockT>::Hash> {
+	EngineCommand::SealNewBlock { create_empty, finalize: true, parent_hash: None, sender: None }
+}
+
+/// Creates a full service authoring blocks with manual seal instead of BABE and GRANDPA.
+///
+/// Blocks are authored according to `sealing` and on the `engine_createBlock` RPC, and the blocks
+/// authored according to `sealing` are finalized as soon as they are imported. They still carry
+/// the BABE pre-digest and the BABE, timestamp and uncles inherents, with a slot advancing by one
+/// for every block, so they are built and imported by the same runtime as with [`new_full_base`].
+/// Fails unless the chain is a development or a local chain.
+pub fn new_manual_seal(
+	config: Configuration,
+	sealing: Sealing,
+) -> Result<NewFullBase, ServiceError> {
+	ensure_manual_seal_allowed(&*config.chain_spec).map_err(ServiceError::Other)?;
+	let sc_service::PartialComponents {
+		client,
+		backend,
+		mut task_manager,
+		import_queue,
+		keystore_container,
+		select_chain,
+		transaction_pool,
+		other: (rpc_extensions_builder, import_setup, _, mut telemetry),
+	} = new_partial(&config)?;
+
+	let (network, system_rpc_tx, network_starter) =
+		sc_service::build_network(sc_service::BuildNetworkParams {
+			config: &config,
+			client: client.clone(),
+			transaction_pool: transaction_pool.clone(),
+			spawn_handle: task_manager.spawn_handle(),
+			import_queue,
+			block_announce_validator_builder: None,
+			warp_sync: None,
+		})?;
+
+	if config.offchain_worker.enabled {
+		sc_service::build_offchain_workers(
+			&config,
+			task_manager.spawn_handle(),
+			client.clone(),
+			network.clone(),
+		);
+	}
+
+	let prometheus_registry = config.prometheus_registry().cloned();
+
+	let (command_sink, rpc_commands) = futures::channel::mpsc::channel(1024);
+	let rpc_extensions_builder = Box::new(move |deny_unsafe, subscription_executor| {
+		let mut io = rpc_extensions_builder.build(deny_unsafe, subscription_executor)?;
+		io.extend_with(ManualSealApi::to_delegate(ManualSeal::new(command_sink.clone())));
+		Ok(io)
+	});
+
+	let rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+		config,
+		backend,
+		client: client.clone(),
+		keystore: keystore_container.sync_keystore(),
+		network: network.clone(),
+		rpc_extensions_builder,
+		transaction_pool: transaction_pool.clone(),
+		task_manager: &mut task_manager,
+		system_rpc_tx,
+		telemetry: telemetry.as_mut(),
+	})?;
+
+	let (block_import, _, babe_link) = import_setup;
+
+	let proposer = sc_basic_authorship::ProposerFactory::new(
+		task_manager.spawn_handle(),
+		client.clone(),
+		transaction_pool.clone(),
+		prometheus_registry.as_ref(),
+		telemetry.as_ref().map(|x| x.handle()),
+	);
+
+	let consensus_data_provider = BabeConsensusDataProvider::new(
+		client.clone(),
+		keystore_container.sync_keystore(),
+		babe_link.epoch_changes().clone(),
+		babe_link.config().genesis_authorities.clone(),
+	)
+	.map_err(|e| ServiceError::Other(e.to_string()))?;
+
+	let commands_stream = match sealing {
+		Sealing::Instant => stream::select(
+			rpc_commands,
+			transaction_pool.import_notification_stream().map(|_| seal_new_block(false)),
+		)
+		.boxed(),
+		Sealing::Manual => rpc_commands.boxed(),
+		Sealing::Interval(millis) => stream::select(
+			rpc_commands,
+			stream::unfold((), move |()| async move {
+				futures_timer::Delay::new(Duration::from_millis(millis)).await;
+				Some((seal_new_block(true), ()))
+			}),
+		)
+		.boxed(),
+	};
+
+	let client_clone = client.clone();
+	let authorship = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+		block_import,
+		env: proposer,
+		client: client.clone(),
+		pool: transaction_pool.clone(),
+		commands_stream,
+		select_chain,
+		consensus_data_provider: Some(Box::new(consensus_data_provider)),
+		create_inherent_data_providers: move |parent, ()| {
+			let client_clone = client_clone.clone();
+			async move {
+				let uncles = sc_consensus_uncles::create_uncles_inherent_data_provider(
+					&*client_clone,
+					parent,
+				)?;
+
+				let timestamp = SlotTimestampProvider::new_babe(client_clone.clone())?;
+
+				let slot =
+					sp_consensus_babe::inherents::InherentDataProvider::new(timestamp.slot());
+
+				Ok((timestamp, slot, uncles))
+			}
+		},
+	});
+	task_manager.spawn_essential_handle().spawn_blocking(
+		"manual-seal",
+		Some("block-authoring"),
+		authorship,
+	);
+
+	network_starter.start_network();
+	Ok(NewFullBase { task_manager, client, network, transaction_pool, rpc_handlers })
 }
 
-/// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
-	new_full_base(config, |_, _| ()).map(|NewFullBase { task_manager, .. }| task_manager)
+/// Builds a new service for a full client, which authors blocks with manual seal if `sealing` is
+/// set.
+pub fn new_full(
+	config: Configuration,
+	sealing: Option<Sealing>,
+	grandpa_overrides: &GrandpaOverrides,
+) -> Result<TaskManager, ServiceError> {
+	match sealing {
+		Some(sealing) => new_manual_seal(config, sealing),
+		None => new_full_base(config, grandpa_overrides, |_, _| ()),
+	}
+	.map(|NewFullBase { task_manager, .. }| task_manager)
 }
 
 #[cfg(test)]
@@ -585,6 +878,7 @@ mod tests {
 				let NewFullBase { task_manager, client, network, transaction_pool, .. } =
 					new_full_base(
 						config,
+						&Default::default(),
 						|block_import: &sc_consensus_babe::BabeBlockImport<Block, _, _>,
 						 babe_link: &sc_consensus_babe::BabeLink<Block>| {
 							setup_handles = Some((block_import.clone(), babe_link.clone()));
@@ -730,12 +1024,13 @@ mod tests {
 					frame_system::CheckMortality::<node_polkadex_runtime::Runtime>::from(generic::Era::Immortal),
 					frame_system::CheckNonce::<node_polkadex_runtime::Runtime>::from(index),
 					frame_system::CheckWeight::<node_polkadex_runtime::Runtime>::new(),
-					pallet_transaction_payment::ChargeTransactionPayment::<node_polkadex_runtime::Runtime>::from(tip),
+					feeless::CheckFeeless::<node_polkadex_runtime::Runtime>::new(),
+					pallet_asset_tx_payment::ChargeAssetTxPayment::<node_polkadex_runtime::Runtime>::from(tip, None),
 				);
 				let raw_payload = SignedPayload::from_raw(
 					function,
 					extra,
-					(spec_version, transaction_version, genesis_hash, genesis_hash, (), (),()),
+					(spec_version, transaction_version, genesis_hash, genesis_hash, (), (), (), ()),
 				);
 				let signature = raw_payload.using_encoded(|payload| signer.sign(payload));
 				let (function, extra, _) = raw_payload.deconstruct();
@@ -746,6 +1041,55 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn sealing_is_parsed() {
+		use crate::service::Sealing;
+
+		assert_eq!("instant".parse(), Ok(Sealing::Instant));
+		assert_eq!("manual".parse(), Ok(Sealing::Manual));
+		assert_eq!("interval=500".parse(), Ok(Sealing::Interval(500)));
+		assert!("interval=0".parse::<Sealing>().is_err());
+		assert!("interval".parse::<Sealing>().is_err());
+		assert!("babe".parse::<Sealing>().is_err());
+	}
+
+	#[test]
+	fn manual_seal_is_limited_to_development_and_local_chains() {
+		use crate::{chain_spec, service::ensure_manual_seal_allowed};
+
+		assert!(ensure_manual_seal_allowed(&chain_spec::development_config()).is_ok());
+		assert!(ensure_manual_seal_allowed(&chain_spec::local_testnet_config(2)).is_ok());
+		assert!(ensure_manual_seal_allowed(&chain_spec::udon_testnet_config()).is_err());
+		assert!(ensure_manual_seal_allowed(&chain_spec::mainnet_testnet_config()).is_err());
+	}
+
+	#[test]
+	fn grandpa_overrides_apply_over_the_chain_spec() {
+		use crate::{
+			chain_spec::{development_config, GrandpaExtension, GrandpaVotingRule},
+			service::GrandpaOverrides,
+		};
+
+		let mut chain_spec = development_config();
+		chain_spec.extensions_mut().grandpa.justification_period = 64;
+		let overrides = GrandpaOverrides {
+			gossip_duration: Some(1_000),
+			voting_rule: Some(GrandpaVotingRule::Best),
+			..Default::default()
+		};
+		assert_eq!(
+			overrides.apply(&chain_spec),
+			Ok(GrandpaExtension {
+				gossip_duration: 1_000,
+				justification_period: 64,
+				observer: false,
+				voting_rule: GrandpaVotingRule::Best,
+			})
+		);
+		let invalid = GrandpaOverrides { justification_period: Some(0), ..Default::default() };
+		assert!(invalid.apply(&chain_spec).is_err());
+	}
+
 	#[test]
 	#[ignore]
 	fn test_consensus() {
@@ -755,7 +1099,7 @@ mod tests {
 			crate::chain_spec::tests::integration_test_config_with_two_authorities(),
 			|config| {
 				let NewFullBase { task_manager, client, network, transaction_pool, .. } =
-					new_full_base(config, |_, _| ())?;
+					new_full_base(config, &Default::default(), |_, _| ())?;
 				Ok(sc_service_test::TestNetComponents::new(
 					task_manager,
 					client,
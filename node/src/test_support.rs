@@ -0,0 +1,531 @@
+//! In-process node for end-to-end tests.
+//!
+//! [`TestNode`] runs the single authority integration test chain in a temporary directory, with
+//! instant sealing: every transaction is included in a new block which is finalized at once. Tests
+//! submit extrinsics signed by the development accounts and assert on the events they emitted and
+//! on the resulting state, without waiting for BABE slots or GRANDPA rounds.
+
+use std::{future::Future, sync::Arc, time::Duration};
+
+use clap::Parser;
+use codec::{Decode, Encode};
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use node_polkadex_runtime::{Call, Event, Runtime};
+use polkadex_primitives::{AccountId, Balance, Block, Hash, Index};
+use sc_cli::{ChainSpec, RunCmd, RuntimeVersion, SubstrateCli};
+use sc_client_api::{BlockBackend, HeaderBackend, StorageProvider};
+use sc_service::{RpcHandlers, TaskManager};
+use sc_transaction_pool::FullPool;
+use sc_transaction_pool_api::{TransactionPool, TransactionSource, TransactionStatus};
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_keyring::AccountKeyring;
+use sp_runtime::{generic::BlockId, DispatchError, OpaqueExtrinsic};
+
+use crate::{
+	chain_spec::tests::integration_test_config_with_single_authority,
+	service::{create_extrinsic, new_manual_seal, FullClient, NewFullBase, Sealing},
+};
+
+/// Time after which waiting for the node fails the test.
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Command line of the test node, whose only chain is the integration test chain.
+struct TestCli;
+
+impl SubstrateCli for TestCli {
+	fn impl_name() -> String {
+		"Polkadex Test Node".into()
+	}
+
+	fn impl_version() -> String {
+		env!("CARGO_PKG_VERSION").into()
+	}
+
+	fn description() -> String {
+		env!("CARGO_PKG_DESCRIPTION").into()
+	}
+
+	fn author() -> String {
+		env!("CARGO_PKG_AUTHORS").into()
+	}
+
+	fn support_url() -> String {
+		"business@polkadex.trade".into()
+	}
+
+	fn copyright_start_year() -> i32 {
+		2017
+	}
+
+	fn load_spec(&self, _: &str) -> Result<Box<dyn ChainSpec>, String> {
+		Ok(Box::new(integration_test_config_with_single_authority()))
+	}
+
+	fn native_runtime_version(_: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
+		&node_polkadex_runtime::VERSION
+	}
+}
+
+/// A block including an extrinsic, with the events emitted by the extrinsic.
+#[derive(Debug)]
+pub struct Included {
+	pub block: Hash,
+	pub events: Vec<Event>,
+}
+
+impl Included {
+	/// Error the extrinsic failed with, if it failed.
+	pub fn dispatch_error(&self) -> Option<DispatchError> {
+		self.events.iter().find_map(|event| match event {
+			Event::System(frame_system::Event::ExtrinsicFailed { dispatch_error, .. }) =>
+				Some(*dispatch_error),
+			_ => None,
+		})
+	}
+
+	/// Panics if the extrinsic failed.
+	pub fn assert_success(&self) -> &Self {
+		assert_eq!(self.dispatch_error(), None, "Extrinsic failed, events: {:?}", self.events);
+		self
+	}
+
+	/// Whether the extrinsic emitted `event`.
+	pub fn contains(&self, event: impl Into<Event>) -> bool {
+		self.events.contains(&event.into())
+	}
+}
+
+/// A node of the integration test chain, with Alice as its authority and the only member of the
+/// council and of the technical committee.
+pub struct TestNode {
+	// Keeps the tasks of the node alive.
+	_task_manager: TaskManager,
+	pub client: Arc<FullClient>,
+	pub transaction_pool: Arc<FullPool<Block, FullClient>>,
+	rpc_handlers: RpcHandlers,
+	// Dropped last, the tasks of the node run on it.
+	runtime: tokio::runtime::Runtime,
+}
+
+impl TestNode {
+	/// Starts a node in a temporary directory.
+	///
+	/// Tests run concurrently, so the node neither listens on the network nor serves RPC, which
+	/// is queried in process instead.
+	pub fn new() -> Self {
+		let runtime = tokio::runtime::Runtime::new().expect("Creates the tokio runtime; qed");
+		let _guard = runtime.enter();
+		let cmd = RunCmd::parse_from([
+			"polkadex-node",
+			"--tmp",
+			"--alice",
+			"--no-telemetry",
+			"--no-prometheus",
+			"--no-mdns",
+		]);
+		let mut config = TestCli
+			.create_configuration(&cmd, runtime.handle().clone())
+			.expect("Creates the configuration of the test node");
+		config.network.listen_addresses.clear();
+		config.rpc_http = None;
+		config.rpc_ws = None;
+		config.rpc_ipc = None;
+		// Stores the MMR leaves, which proofs are generated from.
+		config.offchain_worker.indexing_enabled = true;
+
+		let NewFullBase { task_manager, client, transaction_pool, rpc_handlers, .. } =
+			new_manual_seal(config, Sealing::Instant).expect("Starts the test node");
+		Self { _task_manager: task_manager, client, transaction_pool, rpc_handlers, runtime }
+	}
+
+	/// Signs `call` by `signer`, submits it and waits for the block including it.
+	pub fn submit(&self, signer: AccountKeyring, call: impl Into<Call>) -> Included {
+		self.submit_until(signer, call.into(), false)
+	}
+
+	/// Signs `call` by `signer`, submits it and waits for the block including it to be finalized.
+	pub fn submit_and_finalize(&self, signer: AccountKeyring, call: impl Into<Call>) -> Included {
+		self.submit_until(signer, call.into(), true)
+	}
+
+	fn submit_until(&self, signer: AccountKeyring, call: Call, finalized: bool) -> Included {
+		let extrinsic: OpaqueExtrinsic =
+			create_extrinsic(&self.client, signer.pair(), call, None, None).into();
+		let at = BlockId::Hash(self.client.info().best_hash);
+		let block = self.block_on(async {
+			let mut statuses = self
+				.transaction_pool
+				.submit_and_watch(&at, TransactionSource::External, extrinsic.clone())
+				.await
+				.unwrap_or_else(|e| panic!("Extrinsic was rejected by the pool: {}", e));
+			while let Some(status) = statuses.next().await {
+				match status {
+					TransactionStatus::InBlock(block) if !finalized => return block,
+					TransactionStatus::Finalized(block) => return block,
+					TransactionStatus::Invalid |
+					TransactionStatus::Dropped |
+					TransactionStatus::Usurped(_) => panic!("Extrinsic was not included: {:?}", status),
+					_ => (),
+				}
+			}
+			panic!("Extrinsic status stream ended before its inclusion")
+		});
+
+		let index = self
+			.client
+			.block_body(&BlockId::Hash(block))
+			.expect("Reads the block body")
+			.and_then(|extrinsics| extrinsics.iter().position(|included| *included == extrinsic))
+			.expect("Block includes the extrinsic") as u32;
+		let events = self
+			.events(block)
+			.into_iter()
+			.filter(|record| record.phase == frame_system::Phase::ApplyExtrinsic(index))
+			.map(|record| record.event)
+			.collect();
+		Included { block, events }
+	}
+
+	/// Dispatches `call` with the origin of the whole council, through a proposal of Alice, and
+	/// panics if the call failed.
+	pub fn council(&self, call: impl Into<Call>) -> Included {
+		let proposal: Call = call.into();
+		let length_bound = proposal.encoded_size() as u32;
+		let included = self.submit(
+			AccountKeyring::Alice,
+			Call::Council(pallet_collective::Call::propose {
+				threshold: 1,
+				proposal: Box::new(proposal),
+				length_bound,
+			}),
+		);
+		let result = included.events.iter().find_map(|event| match event {
+			Event::Council(pallet_collective::Event::Executed { result, .. }) => Some(*result),
+			_ => None,
+		});
+		assert_eq!(result, Some(Ok(())), "Proposal failed, events: {:?}", included.events);
+		included
+	}
+
+	/// Seals `count` blocks, possibly empty, with the `engine_createBlock` RPC.
+	pub fn create_blocks(&self, count: u32) {
+		for _ in 0..count {
+			self.rpc::<serde_json::Value>("engine_createBlock", serde_json::json!([true, true]));
+		}
+	}
+
+	/// Calls the RPC `method` with `params` and panics if it returned an error.
+	pub fn rpc<T: DeserializeOwned>(&self, method: &str, params: serde_json::Value) -> T {
+		let request = serde_json::json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"method": method,
+			"params": params,
+		});
+		let response = self
+			.block_on(self.rpc_handlers.rpc_query(&request.to_string()))
+			.unwrap_or_else(|| panic!("{} responds", method));
+		let mut response: serde_json::Value =
+			serde_json::from_str(&response).expect("Responses are JSON");
+		assert!(response.get("error").is_none(), "{} failed: {}", method, response);
+		serde_json::from_value(response["result"].take())
+			.unwrap_or_else(|e| panic!("Decodes the result of {}: {}", method, e))
+	}
+
+	/// Events emitted by `block`.
+	pub fn events(&self, block: Hash) -> Vec<frame_system::EventRecord<Event, Hash>> {
+		let key = [twox_128(b"System"), twox_128(b"Events")].concat();
+		self.storage_at(block, key).unwrap_or_default()
+	}
+
+	/// Free balance of `who` at the best block.
+	pub fn free_balance(&self, who: &AccountId) -> Balance {
+		self.storage::<frame_system::AccountInfo<Index, pallet_balances::AccountData<Balance>>>(
+			frame_system::Account::<Runtime>::hashed_key_for(who),
+		)
+		.map(|account| account.data.free)
+		.unwrap_or_default()
+	}
+
+	/// Locks on the balance of `who` at the best block.
+	pub fn locks(&self, who: &AccountId) -> Vec<pallet_balances::BalanceLock<Balance>> {
+		self.storage(pallet_balances::Locks::<Runtime>::hashed_key_for(who))
+			.unwrap_or_default()
+	}
+
+	/// Value at `key` in the state of the best block.
+	pub fn storage<T: Decode>(&self, key: Vec<u8>) -> Option<T> {
+		self.storage_at(self.client.info().best_hash, key)
+	}
+
+	fn storage_at<T: Decode>(&self, block: Hash, key: Vec<u8>) -> Option<T> {
+		self.client
+			.storage(&BlockId::Hash(block), &StorageKey(key))
+			.expect("Reads the state")
+			.map(|data| T::decode(&mut &data.0[..]).expect("Decodes the storage value"))
+	}
+
+	/// Runs `future` on the runtime of the node, and panics if it takes too long.
+	pub fn block_on<T>(&self, future: impl Future<Output = T>) -> T {
+		self.runtime.block_on(async {
+			tokio::time::timeout(TIMEOUT, future)
+				.await
+				.unwrap_or_else(|_| panic!("Test node did not respond in {:?}", TIMEOUT))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use frame_support::traits::Get;
+	use node_polkadex_runtime::{constants::currency::PDEX, BalancesCall, TreasuryPalletId};
+	use pallet_mmr_primitives::{EncodableOpaqueLeaf, Proof};
+	use pallet_mmr_rpc::{LeafProof, MmrRuntimeApi};
+	use sp_api::ProvideRuntimeApi;
+	use sp_core::{sr25519, Bytes};
+	use sp_runtime::traits::AccountIdConversion;
+
+	use super::*;
+	use crate::chain_spec::get_account_id_from_seed;
+
+	type MigrationCall = pdex_migration::pallet::Call<Runtime>;
+	type MigrationEvent = pdex_migration::pallet::Event<Runtime>;
+
+	const RELAYERS: [AccountKeyring; 3] =
+		[AccountKeyring::Bob, AccountKeyring::Charlie, AccountKeyring::Dave];
+
+	/// Registers the relayers, which relay the burn of `amount` for `beneficiary` on Ethereum.
+	fn mint_through_relayers(node: &TestNode, beneficiary: &AccountId, amount: Balance) {
+		node.council(MigrationCall::set_migration_operational_status { status: true });
+		for relayer in RELAYERS {
+			node.submit(
+				AccountKeyring::Alice,
+				BalancesCall::transfer { dest: relayer.to_account_id().into(), value: 10 * PDEX },
+			)
+			.assert_success();
+			node.council(MigrationCall::set_relayer_status {
+				relayer: relayer.to_account_id(),
+				status: true,
+			});
+		}
+
+		let eth_tx = Hash::repeat_byte(1);
+		for (approvals, relayer) in (1..).zip(RELAYERS) {
+			let included = node.submit(
+				relayer,
+				MigrationCall::mint { beneficiary: beneficiary.clone(), amount, eth_tx },
+			);
+			included.assert_success();
+			let event = if approvals < RELAYERS.len() {
+				MigrationEvent::TokenBurnDetected(eth_tx, relayer.to_account_id())
+			} else {
+				MigrationEvent::NativePDEXMintedAndLocked(
+					relayer.to_account_id(),
+					beneficiary.clone(),
+					amount,
+				)
+			};
+			assert!(included.contains(event), "events: {:?}", included.events);
+		}
+	}
+
+	fn has_lock(node: &TestNode, who: &AccountId, id: &[u8; 8], amount: Balance) -> bool {
+		node.locks(who).iter().any(|lock| lock.id == *id && lock.amount == amount)
+	}
+
+	#[test]
+	fn balance_transfer() {
+		let node = TestNode::new();
+		let alice = AccountKeyring::Alice.to_account_id();
+		let dave = AccountKeyring::Dave.to_account_id();
+		let alice_balance = node.free_balance(&alice);
+
+		let included = node.submit_and_finalize(
+			AccountKeyring::Alice,
+			BalancesCall::transfer { dest: dave.clone().into(), value: 10 * PDEX },
+		);
+		included.assert_success();
+		assert!(included.contains(pallet_balances::Event::<Runtime>::Transfer {
+			from: alice.clone(),
+			to: dave.clone(),
+			amount: 10 * PDEX,
+		}));
+		assert_eq!(node.client.info().finalized_hash, included.block);
+		assert_eq!(node.free_balance(&dave), 10 * PDEX);
+		assert!(node.free_balance(&alice) < alice_balance - 10 * PDEX, "fees are paid");
+	}
+
+	#[test]
+	fn pdex_migration_mint_is_locked() {
+		let node = TestNode::new();
+		let eve = AccountKeyring::Eve.to_account_id();
+
+		mint_through_relayers(&node, &eve, 1_000 * PDEX);
+		assert_eq!(node.free_balance(&eve), 1_000 * PDEX);
+		assert!(has_lock(&node, &eve, b"pdexlock", 1_000 * PDEX));
+
+		let included = node.submit(AccountKeyring::Eve, MigrationCall::unlock {});
+		assert_eq!(
+			included.dispatch_error(),
+			Some(pdex_migration::pallet::Error::<Runtime>::LiquidityRestrictions.into())
+		);
+		assert!(has_lock(&node, &eve, b"pdexlock", 1_000 * PDEX));
+	}
+
+	#[test]
+	// Seals the blocks of the lock period, shortened from 201_600 to 5 by the `fast-runtime`
+	// feature, run it with `--features fast-runtime`.
+	#[cfg_attr(not(feature = "fast-runtime"), ignore)]
+	fn pdex_migration_mint_then_unlock() {
+		let node = TestNode::new();
+		let eve = AccountKeyring::Eve.to_account_id();
+
+		mint_through_relayers(&node, &eve, 1_000 * PDEX);
+		node.create_blocks(<Runtime as pdex_migration::pallet::Config>::LockPeriod::get());
+		node.submit(AccountKeyring::Eve, MigrationCall::unlock {}).assert_success();
+		assert!(node.locks(&eve).is_empty());
+
+		let ferdie = AccountKeyring::Ferdie.to_account_id();
+		node.submit(
+			AccountKeyring::Eve,
+			BalancesCall::transfer { dest: ferdie.clone().into(), value: 900 * PDEX },
+		)
+		.assert_success();
+		assert_eq!(node.free_balance(&ferdie), 900 * PDEX);
+	}
+
+	#[test]
+	fn manual_seal_node_seals_a_block() {
+		let node = TestNode::new();
+		assert_eq!(node.client.info().best_number, 0);
+		node.create_blocks(1);
+		let info = node.client.info();
+		assert_eq!(info.best_number, 1);
+		assert_eq!(info.finalized_hash, info.best_hash);
+	}
+
+	#[test]
+	fn mmr_proofs_over_rpc() {
+		let node = TestNode::new();
+		node.create_blocks(7);
+		let best = node.client.info().best_hash;
+
+		for leaf_index in [0u64, 3, 6] {
+			let proof: LeafProof<Hash> =
+				node.rpc("mmr_generateProof", serde_json::json!([leaf_index, best]));
+			assert_eq!(proof.block_hash, best);
+			let leaf = EncodableOpaqueLeaf::decode(&mut &proof.leaf[..]).unwrap();
+			let proof = Proof::<Hash>::decode(&mut &proof.proof[..]).unwrap();
+			assert_eq!((proof.leaf_index, proof.leaf_count), (leaf_index, 7));
+			let verified = node
+				.client
+				.runtime_api()
+				.verify_proof(&BlockId::Hash(best), leaf, proof)
+				.expect("Calls the runtime");
+			assert_eq!(verified, Ok(()));
+		}
+	}
+
+	/// Code and metadata of the ink! contract of `contracts/balance-bot`, built by its `build.sh`.
+	fn balance_bot() -> (Vec<u8>, serde_json::Value) {
+		let fixture = |file: &str| {
+			let path =
+				format!("{}/tests/fixtures/balance-bot/{}", env!("CARGO_MANIFEST_DIR"), file);
+			std::fs::read(&path).unwrap_or_else(|e| panic!("Reads {}: {}", path, e))
+		};
+		let metadata = serde_json::from_slice(&fixture("metadata.json")).expect("Metadata is JSON");
+		(fixture("balance_bot.wasm"), metadata)
+	}
+
+	/// Selector of the constructor or message `label` in the metadata of a contract.
+	fn selector(metadata: &serde_json::Value, kind: &str, label: &str) -> Vec<u8> {
+		let selector = metadata["V3"]["spec"][kind]
+			.as_array()
+			.and_then(|items| items.iter().find(|item| item["label"] == label))
+			.and_then(|item| item["selector"].as_str())
+			.unwrap_or_else(|| panic!("Metadata has no {} {}", kind, label));
+		sp_core::bytes::from_hex(selector).expect("Selectors are hex")
+	}
+
+	#[test]
+	fn ink_contract_reads_balances_over_rpc() {
+		const GAS_LIMIT: u64 = 100_000_000_000;
+		let node = TestNode::new();
+		let alice = AccountKeyring::Alice.to_account_id();
+		let stash = get_account_id_from_seed::<sr25519::Public>("Alice//stash");
+		let (code, metadata) = balance_bot();
+
+		let included = node.submit(
+			AccountKeyring::Alice,
+			pallet_contracts::Call::<Runtime>::instantiate_with_code {
+				value: 0,
+				gas_limit: GAS_LIMIT,
+				storage_deposit_limit: None,
+				code,
+				data: selector(&metadata, "constructors", "new"),
+				salt: vec![],
+			},
+		);
+		included.assert_success();
+		let contract = included
+			.events
+			.iter()
+			.find_map(|event| match event {
+				Event::Contracts(pallet_contracts::Event::Instantiated { contract, .. }) =>
+					Some(contract.clone()),
+				_ => None,
+			})
+			.expect("Contract is instantiated");
+
+		let mut input = selector(&metadata, "messages", "balance");
+		input.extend((None::<u128>, stash.clone()).encode());
+		let result: serde_json::Value = node.rpc(
+			"contracts_call",
+			serde_json::json!([{
+				"origin": alice,
+				"dest": contract,
+				"value": 0,
+				"gasLimit": GAS_LIMIT,
+				"inputData": Bytes(input),
+			}]),
+		);
+		let output = &result["result"]["Ok"];
+		assert_eq!(output["flags"]["bits"], 0, "Contract reverted: {}", result);
+		let data = output["data"].as_str().and_then(|data| sp_core::bytes::from_hex(data).ok());
+		assert_eq!(
+			data.and_then(|data| Balance::decode(&mut &data[..]).ok()),
+			Some(node.free_balance(&stash))
+		);
+	}
+
+	#[test]
+	fn vested_transfer_from_treasury() {
+		let node = TestNode::new();
+		let treasury: AccountId = TreasuryPalletId::get().into_account();
+		let ferdie = AccountKeyring::Ferdie.to_account_id();
+		let schedule = orml_vesting::VestingSchedule {
+			start: 100,
+			period: 10,
+			period_count: 10,
+			per_period: 10 * PDEX,
+		};
+
+		let included = node.council(orml_vesting::Call::<Runtime>::vested_transfer {
+			dest: ferdie.clone().into(),
+			schedule: schedule.clone(),
+		});
+		assert!(included.contains(orml_vesting::Event::<Runtime>::VestingScheduleAdded {
+			from: treasury.clone(),
+			to: ferdie.clone(),
+			vesting_schedule: schedule,
+		}));
+		assert!(included.contains(pallet_balances::Event::<Runtime>::Transfer {
+			from: treasury,
+			to: ferdie.clone(),
+			amount: 100 * PDEX,
+		}));
+		assert_eq!(node.free_balance(&ferdie), 100 * PDEX);
+		assert!(has_lock(&node, &ferdie, b"ormlvest", 100 * PDEX));
+	}
+}
@@ -0,0 +1,316 @@
+//! The `testnet-gen` subcommand, which generates everything needed to run a testnet of new
+//! validators.
+//!
+//! For every validator it generates a secret phrase, from which its stash, controller and session
+//! keys are derived like for `authority_keys_from_suri`, a keystore with the session keys and a
+//! network key. The raw chain spec of the testnet is the local testnet genesis of these validators,
+//! and the set can be started with docker compose or with a launch script.
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use libp2p::identity::{ed25519, PublicKey};
+use sc_keystore::LocalKeystore;
+use sc_service::ChainType;
+use sp_core::{
+	crypto::{key_types, KeyTypeId},
+	hexdisplay::HexDisplay,
+	sr25519, Pair,
+};
+use sp_keystore::SyncCryptoStore;
+
+use crate::chain_spec::{
+	authority_keys_from_suri, local_testnet_genesis, max_local_testnet_validators, ChainSpec,
+};
+
+/// Name of the chain spec of generated testnets.
+const CHAIN_NAME: &str = "Generated Testnet";
+/// Id of the chain spec of generated testnets, which names their directory in the base path.
+const CHAIN_ID: &str = "generated_testnet";
+/// Subnet of the docker compose network, validators are `172.28.1.<n>`.
+const DOCKER_SUBNET: &str = "172.28.0.0/16";
+
+/// The `testnet-gen` command.
+#[derive(Debug, clap::Parser)]
+pub struct TestnetGenCmd {
+	/// Number of validators.
+	#[clap(long, default_value = "2")]
+	pub validators: u8,
+
+	/// Directory to write the testnet to, which must not exist yet.
+	#[clap(long, parse(from_os_str))]
+	pub output: PathBuf,
+
+	/// Docker image of the node, used by the docker compose file.
+	#[clap(long, default_value = "polkadex-node")]
+	pub image: String,
+}
+
+/// Secrets of a generated validator.
+struct Validator {
+	/// Directory of the validator, `validator-<n>`, which is its base path.
+	name: String,
+	/// Secret phrase of the controller and of the session keys.
+	phrase: String,
+	/// Secret of the network key, from which the peer id is derived.
+	node_key: ed25519::Keypair,
+}
+
+impl Validator {
+	fn generate(index: usize) -> Self {
+		let (_, phrase, _) = sr25519::Pair::generate_with_phrase(None);
+		Self {
+			name: format!("validator-{}", index + 1),
+			phrase,
+			node_key: ed25519::Keypair::generate(),
+		}
+	}
+
+	fn peer_id(&self) -> String {
+		PublicKey::Ed25519(self.node_key.public()).to_peer_id().to_string()
+	}
+}
+
+/// Address of the validator `index` in the docker compose network.
+fn docker_ip(index: usize) -> String {
+	format!("172.28.1.{}", index + 1)
+}
+
+impl TestnetGenCmd {
+	/// Writes the chain spec, the keystores, the node keys and the launch files of the testnet.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		if self.validators == 0 {
+			return Err("A testnet needs at least one validator".into())
+		}
+		let max = max_local_testnet_validators();
+		if usize::from(self.validators) > max {
+			return Err(format!("A testnet has at most {} validators", max).into())
+		}
+		if self.output.exists() {
+			return Err(format!("{} already exists", self.output.display()).into())
+		}
+		let validators = (0..self.validators.into()).map(Validator::generate).collect::<Vec<_>>();
+
+		fs::create_dir_all(&self.output)?;
+		fs::write(self.output.join("chain-spec.json"), chain_spec(&validators)?)?;
+		for validator in &validators {
+			let base_path = self.output.join(&validator.name);
+			insert_session_keys(&base_path, &validator.phrase)?;
+			fs::write(
+				base_path.join("node-key"),
+				HexDisplay::from(&validator.node_key.secret().as_ref()).to_string(),
+			)?;
+			fs::write(base_path.join("secret-phrase"), &validator.phrase)?;
+		}
+		fs::write(
+			self.output.join("docker-compose.yml"),
+			docker_compose(&validators, &self.image),
+		)?;
+		let launch_script = self.output.join("launch.sh");
+		fs::write(&launch_script, launch_script_contents(&validators))?;
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::PermissionsExt;
+			fs::set_permissions(&launch_script, fs::Permissions::from_mode(0o755))?;
+		}
+
+		println!(
+			"Generated a testnet of {} validators in {}. The secret phrases and node keys of the \
+			 validators are in their directories, keep them out of version control.",
+			validators.len(),
+			self.output.display()
+		);
+		Ok(())
+	}
+}
+
+/// Raw chain spec of the local testnet genesis of `validators`.
+fn chain_spec(validators: &[Validator]) -> sc_cli::Result<String> {
+	let initial_authorities = validators
+		.iter()
+		.map(|validator| authority_keys_from_suri(&validator.phrase))
+		.collect::<Vec<_>>();
+	let spec = ChainSpec::from_genesis(
+		CHAIN_NAME,
+		CHAIN_ID,
+		ChainType::Local,
+		move || local_testnet_genesis(initial_authorities.clone()),
+		vec![],
+		None,
+		None,
+		None,
+		None,
+		Default::default(),
+	);
+	Ok(spec.as_json(true)?)
+}
+
+/// Inserts the session keys derived from `phrase` into the keystore of the node at `base_path`.
+fn insert_session_keys(base_path: &Path, phrase: &str) -> sc_cli::Result<()> {
+	let keystore_path = base_path.join("chains").join(CHAIN_ID).join("keystore");
+	let keystore = LocalKeystore::open(keystore_path, None)
+		.map_err(|e| format!("Unable to open the keystore: {}", e))?;
+	let (_, _, grandpa, babe, im_online, authority_discovery) = authority_keys_from_suri(phrase);
+	let keys: [(KeyTypeId, &[u8]); 4] = [
+		(key_types::BABE, babe.as_ref()),
+		(key_types::GRANDPA, grandpa.as_ref()),
+		(key_types::IM_ONLINE, im_online.as_ref()),
+		(key_types::AUTHORITY_DISCOVERY, authority_discovery.as_ref()),
+	];
+	for (key_type, public) in keys {
+		SyncCryptoStore::insert_unknown(&keystore, key_type, phrase, public)
+			.map_err(|_| format!("Unable to insert the {:?} key into the keystore", key_type))?;
+	}
+	Ok(())
+}
+
+/// Docker compose file of the testnet, whose services mount the output directory on `/testnet`.
+fn docker_compose(validators: &[Validator], image: &str) -> String {
+	let bootnode = format!("/ip4/{}/tcp/30333/p2p/{}", docker_ip(0), validators[0].peer_id());
+	let mut compose =
+		String::from("# Generated by `polkadex-node testnet-gen`.\nversion: '3'\nservices:\n");
+	for (index, validator) in validators.iter().enumerate() {
+		let bootnodes =
+			if index == 0 { String::new() } else { format!(" --bootnodes {}", bootnode) };
+		compose.push_str(&format!(
+			"  {name}:
+    image: {image}
+    container_name: {name}
+    command: >
+      polkadex-node --base-path /testnet/{name} --chain /testnet/chain-spec.json
+      --node-key-file /testnet/{name}/node-key --validator --port 30333 --ws-port 9944
+      --rpc-port 9933 --unsafe-rpc-external --unsafe-ws-external --rpc-cors all{bootnodes}
+    ports:
+      - \"{p2p}:30333\"
+      - \"{rpc}:9933\"
+      - \"{ws}:9944\"
+    volumes:
+      - \".:/testnet\"
+    networks:
+      testnet:
+        ipv4_address: {ip}
+",
+			name = validator.name,
+			image = image,
+			bootnodes = bootnodes,
+			p2p = 30333 + index,
+			rpc = 9933 + index,
+			ws = 9944 + index,
+			ip = docker_ip(index),
+		));
+	}
+	compose.push_str(&format!(
+		"
+networks:
+  testnet:
+    ipam:
+      driver: default
+      config:
+        - subnet: {}
+",
+		DOCKER_SUBNET
+	));
+	compose
+}
+
+/// Script starting the validators of the testnet on the local machine, each with its own ports.
+fn launch_script_contents(validators: &[Validator]) -> String {
+	let bootnode = format!("/ip4/127.0.0.1/tcp/30333/p2p/{}", validators[0].peer_id());
+	let mut script = String::from(
+		"#!/usr/bin/env bash
+# Generated by `polkadex-node testnet-gen`: starts the validators of the testnet on this machine,
+# with the node binary of $POLKADEX_NODE or `polkadex-node` from the PATH. Logs are written to the
+# directory of every validator.
+set -e
+
+cd \"$(dirname \"${BASH_SOURCE[0]}\")\"
+NODE=\"${POLKADEX_NODE:-polkadex-node}\"
+trap 'kill $(jobs -p) 2>/dev/null' EXIT
+
+",
+	);
+	for (index, validator) in validators.iter().enumerate() {
+		let bootnodes =
+			if index == 0 { String::new() } else { format!(" --bootnodes {}", bootnode) };
+		script.push_str(&format!(
+			"\"$NODE\" --base-path {name} --chain chain-spec.json --node-key-file {name}/node-key \
+			 --validator --port {p2p} --rpc-port {rpc} --ws-port {ws} --prometheus-port {prometheus}\
+			 {bootnodes} > {name}/node.log 2>&1 &\n",
+			name = validator.name,
+			p2p = 30333 + index,
+			rpc = 9933 + index,
+			ws = 9944 + index,
+			prometheus = 9615 + index,
+			bootnodes = bootnodes,
+		));
+	}
+	script.push_str(&format!(
+		"\necho \"{} validators started, RPC on ws://127.0.0.1:9944\"\nwait\n",
+		validators.len()
+	));
+	script
+}
+
+#[cfg(test)]
+mod tests {
+	use node_polkadex_runtime::constants::currency::PDEX;
+
+	use super::*;
+	use crate::genesis_check::Report;
+
+	#[test]
+	fn testnet_is_generated() {
+		let dir = tempfile::tempdir().unwrap();
+		let output = dir.path().join("testnet");
+		let cmd =
+			TestnetGenCmd { validators: 3, output: output.clone(), image: "polkadex-node".into() };
+		cmd.run().unwrap();
+
+		let spec = ChainSpec::from_json_file(output.join("chain-spec.json")).unwrap();
+		let storage = sp_runtime::BuildStorage::build_storage(&spec).unwrap();
+		let report = Report::new(storage, 20_000_000 * PDEX, 3_172_895 * PDEX);
+		assert_eq!(report.violations, Vec::<String>::new());
+		assert_eq!(report.validators.len(), 3);
+
+		for index in 1..=3 {
+			let base_path = output.join(format!("validator-{}", index));
+			let phrase = fs::read_to_string(base_path.join("secret-phrase")).unwrap();
+			let keys = authority_keys_from_suri(&phrase);
+			assert!(report.validators.iter().any(|validator| validator.stash == keys.0));
+
+			let keystore =
+				LocalKeystore::open(base_path.join("chains").join(CHAIN_ID).join("keystore"), None)
+					.unwrap();
+			assert!(SyncCryptoStore::has_keys(
+				&keystore,
+				&[
+					(keys.3.as_ref().to_vec(), key_types::BABE),
+					(keys.2.as_ref().to_vec(), key_types::GRANDPA),
+					(keys.4.as_ref().to_vec(), key_types::IM_ONLINE),
+					(keys.5.as_ref().to_vec(), key_types::AUTHORITY_DISCOVERY),
+				]
+			));
+			assert_eq!(fs::read_to_string(base_path.join("node-key")).unwrap().len(), 64);
+		}
+
+		let compose = fs::read_to_string(output.join("docker-compose.yml")).unwrap();
+		assert_eq!(compose.matches("--bootnodes").count(), 2);
+		let script = fs::read_to_string(output.join("launch.sh")).unwrap();
+		assert!(script.contains("--prometheus-port 9617"));
+
+		assert!(cmd.run().is_err(), "existing output directories are not overwritten");
+	}
+
+	#[test]
+	fn validators_are_bounded() {
+		let dir = tempfile::tempdir().unwrap();
+		for validators in [0, u8::MAX] {
+			let output = dir.path().join(format!("testnet-{}", validators));
+			let cmd = TestnetGenCmd { validators, output: output.clone(), image: "img".into() };
+			assert!(cmd.run().is_err());
+			assert!(!output.exists());
+		}
+	}
+}
@@ -0,0 +1,115 @@
+//! Transaction fees paid in assets go through the same feeless and discount handling as fees
+//! paid in PDEX.
+
+use asset_fees::FeeRate;
+use fee_discounts::DiscountTier;
+use frame_support::{assert_ok, weights::DispatchInfo};
+use node_polkadex_runtime::{
+	constants::currency::PDEX, AssetFees, Assets, Call, FeeDiscounts, Feeless, Origin, Runtime,
+	System,
+};
+use pallet_asset_tx_payment::ChargeAssetTxPayment;
+use polkadex_node::chain_spec::{
+	authority_keys_from_seed, get_account_id_from_seed, testnet_genesis,
+};
+use polkadex_primitives::{AccountId, Balance};
+use sp_core::sr25519;
+use sp_runtime::{traits::SignedExtension, BuildStorage, FixedU128, Percent};
+
+const ASSET: u128 = 1;
+const LEN: usize = 100;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	testnet_genesis(
+		vec![authority_keys_from_seed("Alice")],
+		vec![],
+		Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+	)
+	.build_storage()
+	.unwrap()
+	.into()
+}
+
+fn account(seed: &str) -> AccountId {
+	get_account_id_from_seed::<sr25519::Public>(seed)
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![0; 32] })
+}
+
+fn info() -> DispatchInfo {
+	DispatchInfo { weight: 1_000_000, ..Default::default() }
+}
+
+/// Creates the fee asset, charged at twice the PDEX fee, and gives `holders` some of it.
+fn setup_fee_asset(holders: &[AccountId]) {
+	System::set_block_number(1);
+	let owner = account("Alice");
+	assert_ok!(Assets::force_create(Origin::root(), ASSET, owner.clone().into(), true, 1));
+	for holder in holders {
+		assert_ok!(Assets::mint(
+			Origin::signed(owner.clone()),
+			ASSET,
+			holder.clone().into(),
+			1_000_000_000_000_000
+		));
+	}
+	assert_ok!(AssetFees::set_fee_asset(
+		Origin::root(),
+		ASSET,
+		Some(FeeRate::Fixed(FixedU128::from(2)))
+	));
+}
+
+/// Asset balance `who` spends on the fee of `call`.
+fn pay_asset_fee(who: &AccountId, call: &Call) -> Balance {
+	let before = Assets::balance(ASSET, who);
+	let pre = ChargeAssetTxPayment::<Runtime>::from(0, Some(ASSET))
+		.pre_dispatch(who, call, &info(), LEN)
+		.unwrap();
+	assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+		Some(pre),
+		&info(),
+		&Default::default(),
+		LEN,
+		&Ok(())
+	));
+	before - Assets::balance(ASSET, who)
+}
+
+#[test]
+fn feeless_calls_are_free_in_assets() {
+	let (alice, bob) = (account("Alice"), account("Bob"));
+	new_test_ext().execute_with(|| {
+		setup_fee_asset(&[alice.clone(), bob.clone()]);
+		assert_ok!(Feeless::set_call_status(
+			Origin::root(),
+			b"System".to_vec(),
+			b"remark".to_vec(),
+			true
+		));
+		assert_ok!(Feeless::set_registration(Origin::root(), alice.clone(), true));
+
+		assert_eq!(pay_asset_fee(&alice, &remark()), 0);
+		assert!(pay_asset_fee(&bob, &remark()) > 0);
+	});
+}
+
+#[test]
+fn asset_fees_are_discounted() {
+	// Alice's stash has 2 PDEX bonded by the genesis.
+	let (stash, bob) = (account("Alice//stash"), account("Bob"));
+	new_test_ext().execute_with(|| {
+		setup_fee_asset(&[stash.clone(), bob.clone()]);
+		assert_ok!(FeeDiscounts::set_tiers(
+			Origin::root(),
+			vec![DiscountTier { stake: PDEX, discount: Percent::from_percent(20) }],
+		));
+
+		// The asset is charged at twice the PDEX fee, which is discounted before the conversion.
+		let fee = pay_asset_fee(&bob, &remark()) / 2;
+		assert!(fee > 0);
+		assert_eq!(pay_asset_fee(&stash, &remark()), 2 * (fee - Percent::from_percent(20) * fee));
+	});
+}
@@ -0,0 +1,66 @@
+//! Deploys a trading bot contract, which reads balances through the Polkadex chain extension.
+
+use codec::{Decode, Encode};
+use node_polkadex_runtime::{Balances, Contracts, System};
+use pallet_contracts_primitives::Code;
+use polkadex_node::chain_spec::{
+	authority_keys_from_seed, get_account_id_from_seed, testnet_genesis,
+};
+use polkadex_primitives::Balance;
+use sp_core::{sr25519, Bytes};
+use sp_runtime::BuildStorage;
+
+const GAS_LIMIT: u64 = 100_000_000_000;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	testnet_genesis(
+		vec![authority_keys_from_seed("Alice")],
+		vec![],
+		Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+	)
+	.build_storage()
+	.unwrap()
+	.into()
+}
+
+#[test]
+fn contract_reads_balances_through_chain_extension() {
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let stash = get_account_id_from_seed::<sr25519::Public>("Alice//stash");
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let wasm =
+			wat::parse_file(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/balance.wat"))
+				.unwrap();
+		let contract = Contracts::bare_instantiate(
+			alice.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			Code::Upload(Bytes(wasm)),
+			vec![],
+			vec![],
+			false,
+		)
+		.result
+		.unwrap()
+		.account_id;
+
+		let result = Contracts::bare_call(
+			alice,
+			contract,
+			0,
+			GAS_LIMIT,
+			None,
+			(None::<u128>, stash.clone()).encode(),
+			false,
+		)
+		.result
+		.unwrap();
+		assert!(!result.did_revert());
+		assert_eq!(
+			Balance::decode(&mut &result.data.0[..]).unwrap(),
+			Balances::free_balance(&stash)
+		);
+	});
+}
@@ -0,0 +1,90 @@
+//! Fee queries report the fee discounted for the stake of the sender.
+
+use fee_discounts::DiscountTier;
+use frame_support::assert_ok;
+use node_polkadex_runtime::{
+	constants::currency::PDEX, Block, Call, FeeDiscounts, Origin, Runtime, SignedExtra, System,
+	TransactionPayment, UncheckedExtrinsic,
+};
+use pallet_transaction_payment_rpc_runtime_api::runtime_decl_for_TransactionPaymentApi::TransactionPaymentApi;
+use polkadex_node::chain_spec::{
+	authority_keys_from_seed, get_account_id_from_seed, testnet_genesis,
+};
+use polkadex_primitives::{AccountId, Balance};
+use sp_core::sr25519;
+use sp_runtime::{generic::Era, BuildStorage, Percent};
+
+const LEN: u32 = 100;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	testnet_genesis(
+		vec![authority_keys_from_seed("Alice")],
+		vec![],
+		Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+	)
+	.build_storage()
+	.unwrap()
+	.into()
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![0; 32] })
+}
+
+/// Remark from `sender`. Fee queries do not check the signature.
+fn signed_remark(sender: AccountId) -> UncheckedExtrinsic {
+	let extra: SignedExtra = (
+		frame_system::CheckSpecVersion::<Runtime>::new(),
+		frame_system::CheckTxVersion::<Runtime>::new(),
+		frame_system::CheckGenesis::<Runtime>::new(),
+		frame_system::CheckMortality::<Runtime>::from(Era::Immortal),
+		frame_system::CheckNonce::<Runtime>::from(0),
+		frame_system::CheckWeight::<Runtime>::new(),
+		feeless::CheckFeeless::<Runtime>::new(),
+		pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(0, None),
+	);
+	UncheckedExtrinsic::new_signed(
+		remark(),
+		sender.into(),
+		sr25519::Signature::from_raw([0; 64]).into(),
+		extra,
+	)
+}
+
+fn query_fee(uxt: UncheckedExtrinsic) -> Balance {
+	<Runtime as TransactionPaymentApi<Block, Balance>>::query_info(uxt, LEN).partial_fee
+}
+
+#[test]
+fn query_info_reports_discounted_fee() {
+	// Alice's stash has 2 PDEX bonded by the genesis.
+	let stash = get_account_id_from_seed::<sr25519::Public>("Alice//stash");
+	let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let fee = TransactionPayment::query_info(signed_remark(stash.clone()), LEN).partial_fee;
+		assert!(fee > 0);
+		assert_eq!(query_fee(signed_remark(stash.clone())), fee);
+
+		assert_ok!(FeeDiscounts::set_tiers(
+			Origin::root(),
+			vec![DiscountTier { stake: PDEX, discount: Percent::from_percent(20) }],
+		));
+		assert_eq!(query_fee(signed_remark(stash.clone())), fee - Percent::from_percent(20) * fee);
+		let discounted = |fee: Balance| fee - Percent::from_percent(20) * fee;
+		let details = TransactionPayment::query_fee_details(signed_remark(stash.clone()), LEN)
+			.inclusion_fee
+			.unwrap();
+		let queried = <Runtime as TransactionPaymentApi<Block, Balance>>::query_fee_details(
+			signed_remark(stash),
+			LEN,
+		)
+		.inclusion_fee
+		.unwrap();
+		assert_eq!(queried.base_fee, discounted(details.base_fee));
+		assert_eq!(queried.len_fee, discounted(details.len_fee));
+		assert_eq!(queried.adjusted_weight_fee, discounted(details.adjusted_weight_fee));
+		assert_eq!(query_fee(signed_remark(bob)), fee);
+		assert_eq!(query_fee(UncheckedExtrinsic::new_unsigned(remark())), 0);
+	});
+}
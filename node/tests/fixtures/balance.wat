@@ -0,0 +1,36 @@
+;; Trading bot fixture: passes its input to the balance function of the Polkadex chain extension
+;; and returns the balance.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) size of the input buffer
+	(data (i32.const 0) "\00\01")
+
+	;; [4, 8) size of the output buffer
+	(data (i32.const 4) "\00\01")
+
+	;; [8, 264) input buffer
+
+	;; [264, 520) output buffer
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 8) (i32.const 0))
+		(drop
+			(call $seal_call_chain_extension
+				(i32.const 1)			;; Balance function
+				(i32.const 8)			;; Pointer to the input
+				(i32.load (i32.const 0))	;; Length of the input
+				(i32.const 264)			;; Pointer to the output
+				(i32.const 4)			;; Pointer to the size of the output
+			)
+		)
+		(call $seal_return (i32.const 0) (i32.const 264) (i32.load (i32.const 4)))
+	)
+)
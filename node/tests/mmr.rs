@@ -0,0 +1,48 @@
+//! Generation and verification of MMR proofs on a node with offchain indexing enabled.
+
+use frame_support::traits::OnInitialize;
+use node_polkadex_runtime::{Mmr, System};
+use polkadex_node::chain_spec::{
+	authority_keys_from_seed, get_account_id_from_seed, testnet_genesis,
+};
+use sp_core::{
+	offchain::{testing::TestOffchainExt, OffchainDbExt, OffchainWorkerExt},
+	sr25519, H256,
+};
+use sp_runtime::BuildStorage;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	testnet_genesis(
+		vec![authority_keys_from_seed("Alice")],
+		vec![],
+		Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+	)
+	.build_storage()
+	.unwrap()
+	.into()
+}
+
+fn new_block() {
+	let number = System::block_number() + 1;
+	System::initialize(&number, &H256::repeat_byte(number as u8), &Default::default());
+	Mmr::on_initialize(number);
+}
+
+#[test]
+fn mmr_proofs_are_generated_and_verified() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| (0..7).for_each(|_| new_block()));
+	// Leaves are only stored in the offchain database, through offchain indexing.
+	ext.persist_offchain_overlay();
+	let (offchain, _state) = TestOffchainExt::with_offchain_db(ext.offchain_db());
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+
+	ext.execute_with(|| {
+		assert_eq!(Mmr::mmr_leaves(), 7);
+		for leaf_index in 0..Mmr::mmr_leaves() {
+			let (leaf, proof) = Mmr::generate_proof(leaf_index).unwrap();
+			assert_eq!(Mmr::verify_leaf(leaf, proof), Ok(()));
+		}
+	});
+}
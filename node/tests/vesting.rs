@@ -0,0 +1,53 @@
+//! Vesting runtime API against the schedules of the development genesis.
+
+use hex_literal::hex;
+use node_polkadex_runtime::{Block, Runtime, System};
+use polkadex_node::chain_spec::development_config;
+use polkadex_primitives::{AccountId, Balance, BlockNumber};
+use sp_runtime::BuildStorage;
+use vesting_runtime_api::{runtime_decl_for_VestingApi::VestingApi, VestingInfo};
+
+const PDEX: Balance = 1_000_000_000_000;
+
+fn vesting_info(who: &AccountId) -> VestingInfo<BlockNumber, Balance> {
+	<Runtime as VestingApi<Block, AccountId, BlockNumber, Balance>>::vesting_info(who.clone())
+}
+
+#[test]
+fn vesting_info_follows_genesis_schedules() {
+	// Vests 6_000 PDEX at block 144_000, then 6_000 PDEX every 648_000 blocks, twice.
+	let investor: AccountId =
+		hex!["e4cdc8abc0405db44c1a6886a2f2c59012fa3b98c07b61d63cc7f9e437ba243e"].into();
+	let storage = development_config().build_storage().unwrap();
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		let info = vesting_info(&investor);
+		assert_eq!(info.schedules.len(), 2);
+		assert_eq!(info.total_locked, 18_000 * PDEX);
+		assert_eq!(info.claimable, 0);
+		assert_eq!(info.next_unlock, Some(144_000));
+
+		System::set_block_number(144_000);
+		let info = vesting_info(&investor);
+		assert_eq!(info.total_locked, 12_000 * PDEX);
+		assert_eq!(info.claimable, 6_000 * PDEX);
+		assert_eq!(info.next_unlock, Some(144_000 + 648_000));
+
+		System::set_block_number(144_000 + 2 * 648_000);
+		let info = vesting_info(&investor);
+		assert_eq!(info.total_locked, 0);
+		assert_eq!(info.claimable, 18_000 * PDEX);
+		assert_eq!(info.next_unlock, None);
+	});
+}
+
+#[test]
+fn vesting_info_of_account_without_schedules_is_empty() {
+	let storage = development_config().build_storage().unwrap();
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		let info = vesting_info(&AccountId::new([0; 32]));
+		assert!(info.schedules.is_empty());
+		assert_eq!(info.total_locked, 0);
+		assert_eq!(info.claimable, 0);
+		assert_eq!(info.next_unlock, None);
+	});
+}
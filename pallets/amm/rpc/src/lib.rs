@@ -0,0 +1,105 @@
+//! RPC interface for the AMM pallet.
+
+use std::sync::Arc;
+
+use amm::SwapKind;
+pub use amm_runtime_api::AmmApi as AmmRuntimeApi;
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Price of a swap as returned over RPC.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSwapQuote {
+	/// Amount paid out of the pool.
+	pub amount_out: NumberOrHex,
+	/// Whole fee taken from the input.
+	pub fee: NumberOrHex,
+	/// Part of the fee sent to the treasury.
+	pub treasury_fee: NumberOrHex,
+}
+
+#[rpc]
+pub trait AmmApi<BlockHash, AssetId> {
+	/// Prices swapping `amount_in` through the pool of `asset` without submitting a transaction.
+	#[rpc(name = "amm_quoteSwap")]
+	fn quote_swap(
+		&self,
+		asset: AssetId,
+		kind: SwapKind,
+		amount_in: NumberOrHex,
+		at: Option<BlockHash>,
+	) -> Result<Option<RpcSwapQuote>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The amount does not fit the balance type.
+	InvalidAmount,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidAmount => 2,
+		}
+	}
+}
+
+/// A struct that implements the [`AmmApi`].
+pub struct Amm<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Amm<C, B> {
+	/// Create new `Amm` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AssetId, Balance> AmmApi<<Block as BlockT>::Hash, AssetId> for Amm<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AmmRuntimeApi<Block, AssetId, Balance>,
+	AssetId: Codec,
+	Balance: Codec + TryFrom<NumberOrHex> + Into<NumberOrHex>,
+{
+	fn quote_swap(
+		&self,
+		asset: AssetId,
+		kind: SwapKind,
+		amount_in: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<RpcSwapQuote>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let amount_in: Balance = amount_in.try_into().map_err(|_| RpcError {
+			code: ErrorCode::ServerError(Error::InvalidAmount.into()),
+			message: "Amount does not fit the balance type.".into(),
+			data: None,
+		})?;
+
+		let quote = api.quote_swap(&at, asset, kind, amount_in).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to quote swap.".into(),
+			data: Some(e.to_string().into()),
+		})?;
+		Ok(quote.map(|quote| RpcSwapQuote {
+			amount_out: quote.amount_out.into(),
+			fee: quote.fee.into(),
+			treasury_fee: quote.treasury_fee.into(),
+		}))
+	}
+}
@@ -0,0 +1,17 @@
+//! Runtime API definition for the AMM pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use amm::{SwapKind, SwapQuote};
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AmmApi<AssetId, Balance> where
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// Prices swapping `amount_in` through the pool of `asset`, or `None` if there is no such
+		/// pool or the swap can not be executed.
+		fn quote_swap(asset: AssetId, kind: SwapKind, amount_in: Balance) -> Option<SwapQuote<Balance>>;
+	}
+}
@@ -0,0 +1,103 @@
+//! Benchmarking setup for amm
+
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+	assert_ok,
+	traits::{fungibles, Currency, EnsureOrigin},
+};
+use frame_system::RawOrigin;
+use sp_runtime::{traits::Bounded, Permill, SaturatedConversion};
+
+use crate::pallet::{Pallet as Amm, *};
+
+use super::*;
+
+const UNIT: u128 = 1000_000_000_000;
+
+fn create_asset<T: Config>() -> T::AssetId
+where
+	T::Assets: fungibles::Create<T::AccountId> + fungibles::Mutate<T::AccountId>,
+	T::AssetId: From<u32>,
+{
+	let asset: T::AssetId = 100u32.into();
+	let admin: T::AccountId = account("admin", 0, 0);
+	assert_ok!(<T::Assets as fungibles::Create<_>>::create(
+		asset,
+		admin,
+		true,
+		1u128.saturated_into()
+	));
+	asset
+}
+
+fn fund<T: Config>(who: &T::AccountId, asset: T::AssetId)
+where
+	T::Assets: fungibles::Mutate<T::AccountId>,
+{
+	T::Currency::make_free_balance_be(who, T::Balance::max_value() / 4u32.into());
+	assert_ok!(<T::Assets as fungibles::Mutate<_>>::mint_into(
+		asset,
+		who,
+		T::Balance::max_value() / 4u32.into()
+	));
+}
+
+fn create_pool<T: Config>(who: &T::AccountId, asset: T::AssetId) {
+	assert_ok!(Amm::<T>::create_pool(
+		RawOrigin::Signed(who.clone()).into(),
+		asset,
+		(1_000 * UNIT).saturated_into(),
+		(2_000 * UNIT).saturated_into()
+	));
+}
+
+benchmarks! {
+	where_clause {
+		where
+			T::Assets: fungibles::Create<T::AccountId> + fungibles::Mutate<T::AccountId>,
+			T::AssetId: From<u32>,
+	}
+
+	create_pool {
+		let asset = create_asset::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller, asset);
+		let native_amount: T::Balance = (1_000 * UNIT).saturated_into();
+		let asset_amount: T::Balance = (2_000 * UNIT).saturated_into();
+	}: _(RawOrigin::Signed(caller), asset, native_amount, asset_amount)
+
+	add_liquidity {
+		let asset = create_asset::<T>();
+		let creator: T::AccountId = account("creator", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&creator, asset);
+		fund::<T>(&caller, asset);
+		create_pool::<T>(&creator, asset);
+		let native_amount: T::Balance = (100 * UNIT).saturated_into();
+		let max_asset_amount: T::Balance = (300 * UNIT).saturated_into();
+	}: _(RawOrigin::Signed(caller), asset, native_amount, max_asset_amount, 0u32.into(), T::BlockNumber::max_value())
+
+	remove_liquidity {
+		let asset = create_asset::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller, asset);
+		create_pool::<T>(&caller, asset);
+		let shares: T::Balance = (500 * UNIT).saturated_into();
+	}: _(RawOrigin::Signed(caller), asset, shares, 0u32.into(), 0u32.into(), T::BlockNumber::max_value())
+
+	swap_exact_in {
+		let asset = create_asset::<T>();
+		let creator: T::AccountId = account("creator", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&creator, asset);
+		fund::<T>(&caller, asset);
+		create_pool::<T>(&creator, asset);
+		let amount_in: T::Balance = (10 * UNIT).saturated_into();
+	}: _(RawOrigin::Signed(caller), asset, SwapKind::AssetToNative, amount_in, 0u32.into(), T::BlockNumber::max_value())
+
+	set_fee_config {
+		let origin = T::GovernanceOrigin::successful_origin();
+	}: _<T::Origin>(origin, Permill::from_parts(3_000), Permill::from_percent(20))
+}
+
+impl_benchmark_test_suite!(Amm, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,580 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+//! Constant product liquidity pools.
+//!
+//! Every pool pairs native PDEX with one asset of the assets pallet and prices swaps so that the
+//! product of its reserves never decreases. Liquidity providers receive pool shares, minted as an
+//! asset of the assets pallet per pool, and redeem them for their part of the reserves. A fee is
+//! taken from the input of every swap; part of it is sent to the treasury and the rest stays in the
+//! pool.
+
+use frame_support::weights::Weight;
+pub mod types;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::*;
+
+/// Weight functions needed for amm.
+pub trait WeightInfo {
+	fn create_pool() -> Weight;
+	fn add_liquidity() -> Weight;
+	fn remove_liquidity() -> Weight;
+	fn swap_exact_in() -> Weight;
+	fn set_fee_config() -> Weight;
+}
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{fungibles, Currency, ExistenceRequirement},
+		transactional, PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		helpers_128bit::multiply_by_rational,
+		traits::{
+			AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, Convert, One, Saturating, Zero,
+		},
+		Permill, SaturatedConversion,
+	};
+
+	use crate::{types::*, WeightInfo};
+
+	pub type PoolOf<T> = Pool<<T as Config>::Balance>;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Balance type shared by the native currency and the assets.
+		type Balance: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaxEncodedLen;
+		/// Native currency
+		type Currency: Currency<Self::AccountId, Balance = Self::Balance>;
+		/// Identifier of non-native assets.
+		type AssetId: Parameter + Member + Copy + MaxEncodedLen;
+		/// Non-native assets, also used to mint the liquidity shares of every pool.
+		type Assets: fungibles::Inspect<Self::AccountId, AssetId = Self::AssetId, Balance = Self::Balance>
+			+ fungibles::Transfer<Self::AccountId>
+			+ fungibles::Create<Self::AccountId>
+			+ fungibles::Mutate<Self::AccountId>;
+		/// Identifier of the asset minted as liquidity shares of the pool of an asset, which the
+		/// runtime must keep other assets from using.
+		type ShareAssetId: Convert<Self::AssetId, Self::AssetId>;
+		/// Account receiving the treasury part of swap fees.
+		type TreasuryAccount: Get<Self::AccountId>;
+		/// Origin allowed to change the swap fee.
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+		/// Pallet id used to derive pool accounts.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Shares of every pool that are never issued to anyone, so a pool can not be emptied.
+		#[pallet::constant]
+		type MinimumLiquidity: Get<Self::Balance>;
+		/// Swap fee applied until governance sets one.
+		#[pallet::constant]
+		type DefaultSwapFee: Get<Permill>;
+		/// Treasury part of the swap fee applied until governance sets one.
+		#[pallet::constant]
+		type DefaultTreasuryShare: Get<Permill>;
+		/// Weight Info for the AMM
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::type_value]
+	pub fn DefaultFeeConfig<T: Config>() -> FeeConfig {
+		FeeConfig {
+			swap_fee: T::DefaultSwapFee::get(),
+			treasury_share: T::DefaultTreasuryShare::get(),
+		}
+	}
+
+	/// Pools by the asset they pair with PDEX
+	#[pallet::storage]
+	#[pallet::getter(fn pools)]
+	pub(super) type Pools<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, PoolOf<T>, OptionQuery>;
+
+	/// Fee charged on swaps
+	#[pallet::storage]
+	#[pallet::getter(fn fee_config)]
+	pub(super) type Fees<T: Config> = StorageValue<_, FeeConfig, ValueQuery, DefaultFeeConfig<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		PoolCreated {
+			asset: T::AssetId,
+			creator: T::AccountId,
+			native_amount: T::Balance,
+			asset_amount: T::Balance,
+			shares: T::Balance,
+		},
+		LiquidityAdded {
+			asset: T::AssetId,
+			who: T::AccountId,
+			native_amount: T::Balance,
+			asset_amount: T::Balance,
+			shares: T::Balance,
+		},
+		LiquidityRemoved {
+			asset: T::AssetId,
+			who: T::AccountId,
+			native_amount: T::Balance,
+			asset_amount: T::Balance,
+			shares: T::Balance,
+		},
+		Swapped {
+			asset: T::AssetId,
+			who: T::AccountId,
+			kind: SwapKind,
+			amount_in: T::Balance,
+			amount_out: T::Balance,
+			fee: T::Balance,
+			treasury_fee: T::Balance,
+		},
+		FeeConfigUpdated {
+			swap_fee: Permill,
+			treasury_share: Permill,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A pool for the asset already exists
+		PoolAlreadyExists,
+		/// There is no pool for the asset
+		PoolNotFound,
+		/// Asset does not exist
+		UnknownAsset,
+		/// The deadline of the call has passed
+		DeadlinePassed,
+		/// Amount must be greater than zero
+		ZeroAmount,
+		/// Initial liquidity must exceed the minimum liquidity
+		InsufficientInitialLiquidity,
+		/// Account holds fewer shares than requested
+		InsufficientShares,
+		/// Output is lower than the accepted minimum
+		InsufficientOutputAmount,
+		/// Input is higher than the accepted maximum
+		ExcessiveInputAmount,
+		/// Pool reserves are too small for the requested amount
+		InsufficientLiquidity,
+		/// Arithmetic overflow
+		Overflow,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Creates the pool between PDEX and `asset` with its initial reserves. The creator
+		/// receives `native_amount` shares less the minimum liquidity.
+		#[pallet::weight(<T as Config>::WeightInfo::create_pool())]
+		#[transactional]
+		pub fn create_pool(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			native_amount: T::Balance,
+			asset_amount: T::Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Pools::<T>::contains_key(asset), Error::<T>::PoolAlreadyExists);
+			ensure!(
+				!<T::Assets as fungibles::Inspect<_>>::minimum_balance(asset).is_zero(),
+				Error::<T>::UnknownAsset
+			);
+			ensure!(!asset_amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(
+				native_amount > T::MinimumLiquidity::get(),
+				Error::<T>::InsufficientInitialLiquidity
+			);
+
+			Self::deposit_into_pool(&who, asset, native_amount, asset_amount)?;
+			// The minimum liquidity is counted in the pool but never minted.
+			let shares = native_amount.saturating_sub(T::MinimumLiquidity::get());
+			let share_asset = Self::share_asset(asset);
+			<T::Assets as fungibles::Create<_>>::create(
+				share_asset,
+				Self::pool_account(asset),
+				false,
+				One::one(),
+			)?;
+			<T::Assets as fungibles::Mutate<_>>::mint_into(share_asset, &who, shares)?;
+			Pools::<T>::insert(
+				asset,
+				Pool {
+					native_reserve: native_amount,
+					asset_reserve: asset_amount,
+					total_shares: native_amount,
+				},
+			);
+			Self::deposit_event(Event::PoolCreated {
+				asset,
+				creator: who,
+				native_amount,
+				asset_amount,
+				shares,
+			});
+			Ok(())
+		}
+
+		/// Deposits `native_amount` and the matching amount of the asset at the current pool
+		/// ratio, failing if that takes more than `max_asset_amount` or issues fewer than
+		/// `min_shares`.
+		#[pallet::weight(<T as Config>::WeightInfo::add_liquidity())]
+		#[transactional]
+		pub fn add_liquidity(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			native_amount: T::Balance,
+			max_asset_amount: T::Balance,
+			min_shares: T::Balance,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_deadline(deadline)?;
+			ensure!(!native_amount.is_zero(), Error::<T>::ZeroAmount);
+			let mut pool = Pools::<T>::get(asset).ok_or(Error::<T>::PoolNotFound)?;
+
+			// Rounded up in favour of the pool.
+			let asset_amount =
+				Self::mul_div(native_amount, pool.asset_reserve, pool.native_reserve)?
+					.saturating_add(One::one());
+			ensure!(asset_amount <= max_asset_amount, Error::<T>::ExcessiveInputAmount);
+			let shares = Self::mul_div(native_amount, pool.total_shares, pool.native_reserve)?;
+			ensure!(
+				!shares.is_zero() && shares >= min_shares,
+				Error::<T>::InsufficientOutputAmount
+			);
+
+			Self::deposit_into_pool(&who, asset, native_amount, asset_amount)?;
+			pool.native_reserve =
+				pool.native_reserve.checked_add(&native_amount).ok_or(Error::<T>::Overflow)?;
+			pool.asset_reserve =
+				pool.asset_reserve.checked_add(&asset_amount).ok_or(Error::<T>::Overflow)?;
+			pool.total_shares =
+				pool.total_shares.checked_add(&shares).ok_or(Error::<T>::Overflow)?;
+			<T::Assets as fungibles::Mutate<_>>::mint_into(Self::share_asset(asset), &who, shares)?;
+			Pools::<T>::insert(asset, pool);
+			Self::deposit_event(Event::LiquidityAdded {
+				asset,
+				who,
+				native_amount,
+				asset_amount,
+				shares,
+			});
+			Ok(())
+		}
+
+		/// Redeems `shares` for their part of both reserves, failing if either payout is below
+		/// the given minimum.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity())]
+		#[transactional]
+		pub fn remove_liquidity(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			shares: T::Balance,
+			min_native_amount: T::Balance,
+			min_asset_amount: T::Balance,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_deadline(deadline)?;
+			ensure!(!shares.is_zero(), Error::<T>::ZeroAmount);
+			let mut pool = Pools::<T>::get(asset).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(Self::liquidity_shares(asset, &who) >= shares, Error::<T>::InsufficientShares);
+			<T::Assets as fungibles::Mutate<_>>::burn_from(Self::share_asset(asset), &who, shares)?;
+
+			// The pool account keeps the existential deposit, taken from the last payouts.
+			let native_amount = Self::mul_div(shares, pool.native_reserve, pool.total_shares)?
+				.min(pool.native_reserve.saturating_sub(T::Currency::minimum_balance()));
+			let asset_amount = Self::mul_div(shares, pool.asset_reserve, pool.total_shares)?;
+			ensure!(
+				native_amount >= min_native_amount && asset_amount >= min_asset_amount,
+				Error::<T>::InsufficientOutputAmount
+			);
+
+			let pool_account = Self::pool_account(asset);
+			T::Currency::transfer(
+				&pool_account,
+				&who,
+				native_amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			<T::Assets as fungibles::Transfer<_>>::transfer(
+				asset,
+				&pool_account,
+				&who,
+				asset_amount,
+				true,
+			)?;
+			pool.native_reserve = pool.native_reserve.saturating_sub(native_amount);
+			pool.asset_reserve = pool.asset_reserve.saturating_sub(asset_amount);
+			pool.total_shares = pool.total_shares.saturating_sub(shares);
+			Pools::<T>::insert(asset, pool);
+			Self::deposit_event(Event::LiquidityRemoved {
+				asset,
+				who,
+				native_amount,
+				asset_amount,
+				shares,
+			});
+			Ok(())
+		}
+
+		/// Swaps exactly `amount_in` through the pool of `asset`, failing if less than
+		/// `min_amount_out` would be received.
+		#[pallet::weight(<T as Config>::WeightInfo::swap_exact_in())]
+		#[transactional]
+		pub fn swap_exact_in(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			kind: SwapKind,
+			amount_in: T::Balance,
+			min_amount_out: T::Balance,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_deadline(deadline)?;
+			ensure!(!amount_in.is_zero(), Error::<T>::ZeroAmount);
+			let mut pool = Pools::<T>::get(asset).ok_or(Error::<T>::PoolNotFound)?;
+			let quote = Self::price_swap(asset, &pool, kind, amount_in)?;
+			ensure!(quote.amount_out >= min_amount_out, Error::<T>::InsufficientOutputAmount);
+
+			let pool_account = Self::pool_account(asset);
+			let treasury = T::TreasuryAccount::get();
+			let to_pool = amount_in.saturating_sub(quote.treasury_fee);
+			match kind {
+				SwapKind::NativeToAsset => {
+					T::Currency::transfer(
+						&who,
+						&pool_account,
+						to_pool,
+						ExistenceRequirement::AllowDeath,
+					)?;
+					if !quote.treasury_fee.is_zero() {
+						T::Currency::transfer(
+							&who,
+							&treasury,
+							quote.treasury_fee,
+							ExistenceRequirement::AllowDeath,
+						)?;
+					}
+					<T::Assets as fungibles::Transfer<_>>::transfer(
+						asset,
+						&pool_account,
+						&who,
+						quote.amount_out,
+						true,
+					)?;
+					pool.native_reserve =
+						pool.native_reserve.checked_add(&to_pool).ok_or(Error::<T>::Overflow)?;
+					pool.asset_reserve = pool.asset_reserve.saturating_sub(quote.amount_out);
+				},
+				SwapKind::AssetToNative => {
+					<T::Assets as fungibles::Transfer<_>>::transfer(
+						asset,
+						&who,
+						&pool_account,
+						to_pool,
+						false,
+					)?;
+					if !quote.treasury_fee.is_zero() {
+						<T::Assets as fungibles::Transfer<_>>::transfer(
+							asset,
+							&who,
+							&treasury,
+							quote.treasury_fee,
+							false,
+						)?;
+					}
+					T::Currency::transfer(
+						&pool_account,
+						&who,
+						quote.amount_out,
+						ExistenceRequirement::KeepAlive,
+					)?;
+					pool.asset_reserve =
+						pool.asset_reserve.checked_add(&to_pool).ok_or(Error::<T>::Overflow)?;
+					pool.native_reserve = pool.native_reserve.saturating_sub(quote.amount_out);
+				},
+			}
+			Pools::<T>::insert(asset, pool);
+			Self::deposit_event(Event::Swapped {
+				asset,
+				who,
+				kind,
+				amount_in,
+				amount_out: quote.amount_out,
+				fee: quote.fee,
+				treasury_fee: quote.treasury_fee,
+			});
+			Ok(())
+		}
+
+		/// Sets the swap fee and the part of it sent to the treasury.
+		#[pallet::weight(<T as Config>::WeightInfo::set_fee_config())]
+		pub fn set_fee_config(
+			origin: OriginFor<T>,
+			swap_fee: Permill,
+			treasury_share: Permill,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			Fees::<T>::put(FeeConfig { swap_fee, treasury_share });
+			Self::deposit_event(Event::FeeConfigUpdated { swap_fee, treasury_share });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Account holding the reserves of the pool of `asset`.
+		pub fn pool_account(asset: T::AssetId) -> T::AccountId {
+			T::PalletId::get().into_sub_account(asset)
+		}
+
+		/// Asset minted as liquidity shares of the pool of `asset`.
+		pub fn share_asset(asset: T::AssetId) -> T::AssetId {
+			T::ShareAssetId::convert(asset)
+		}
+
+		/// Shares of the pool of `asset` held by `who`.
+		pub fn liquidity_shares(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+			<T::Assets as fungibles::Inspect<_>>::balance(Self::share_asset(asset), who)
+		}
+
+		/// Prices swapping `amount_in` through the pool of `asset` against its current reserves.
+		pub fn quote_swap(
+			asset: T::AssetId,
+			kind: SwapKind,
+			amount_in: T::Balance,
+		) -> Option<SwapQuote<T::Balance>> {
+			let pool = Pools::<T>::get(asset)?;
+			Self::price_swap(asset, &pool, kind, amount_in).ok()
+		}
+
+		/// Value of `native_amount` PDEX in `asset` at the spot price of its pool, without fees
+		/// or slippage.
+		pub fn spot_value(asset: T::AssetId, native_amount: T::Balance) -> Option<T::Balance> {
+			let pool = Pools::<T>::get(asset)?;
+			Self::mul_div(native_amount, pool.asset_reserve, pool.native_reserve).ok()
+		}
+
+		fn price_swap(
+			asset: T::AssetId,
+			pool: &PoolOf<T>,
+			kind: SwapKind,
+			amount_in: T::Balance,
+		) -> Result<SwapQuote<T::Balance>, DispatchError> {
+			let fees = Fees::<T>::get();
+			let fee = fees.swap_fee.mul_floor(amount_in);
+			let treasury_fee =
+				Self::payable_treasury_fee(asset, kind, fees.treasury_share.mul_floor(fee));
+			let (reserve_in, reserve_out) = match kind {
+				SwapKind::NativeToAsset => (pool.native_reserve, pool.asset_reserve),
+				SwapKind::AssetToNative => (pool.asset_reserve, pool.native_reserve),
+			};
+			let net_in = amount_in.saturating_sub(fee);
+			let amount_out = Self::mul_div(
+				net_in,
+				reserve_out,
+				reserve_in.checked_add(&net_in).ok_or(Error::<T>::Overflow)?,
+			)?;
+			ensure!(!amount_out.is_zero(), Error::<T>::InsufficientLiquidity);
+			// The pool account must keep the existential deposit.
+			if kind == SwapKind::AssetToNative {
+				ensure!(
+					reserve_out.saturating_sub(amount_out) >= T::Currency::minimum_balance(),
+					Error::<T>::InsufficientLiquidity
+				);
+			}
+			Ok(SwapQuote { amount_out, fee, treasury_fee })
+		}
+
+		/// Part of a swap fee that the treasury can receive. A fee below the minimum balance of a
+		/// currency the treasury does not hold yet stays in the pool instead.
+		fn payable_treasury_fee(
+			asset: T::AssetId,
+			kind: SwapKind,
+			treasury_fee: T::Balance,
+		) -> T::Balance {
+			let treasury = T::TreasuryAccount::get();
+			let payable = match kind {
+				SwapKind::NativeToAsset =>
+					treasury_fee >= T::Currency::minimum_balance() ||
+						!T::Currency::total_balance(&treasury).is_zero(),
+				SwapKind::AssetToNative =>
+					treasury_fee >= <T::Assets as fungibles::Inspect<_>>::minimum_balance(asset) ||
+						!<T::Assets as fungibles::Inspect<_>>::balance(asset, &treasury).is_zero(),
+			};
+			if payable {
+				treasury_fee
+			} else {
+				Zero::zero()
+			}
+		}
+
+		/// Moves the reserves of a deposit from `who` to the pool account.
+		fn deposit_into_pool(
+			who: &T::AccountId,
+			asset: T::AssetId,
+			native_amount: T::Balance,
+			asset_amount: T::Balance,
+		) -> DispatchResult {
+			let pool_account = Self::pool_account(asset);
+			// Native first, the pool account must exist before it can hold the asset.
+			T::Currency::transfer(
+				who,
+				&pool_account,
+				native_amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			<T::Assets as fungibles::Transfer<_>>::transfer(
+				asset,
+				who,
+				&pool_account,
+				asset_amount,
+				false,
+			)?;
+			Ok(())
+		}
+
+		fn ensure_deadline(deadline: T::BlockNumber) -> DispatchResult {
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= deadline,
+				Error::<T>::DeadlinePassed
+			);
+			Ok(())
+		}
+
+		/// `a * b / c` rounded down, without intermediate overflow.
+		fn mul_div(
+			a: T::Balance,
+			b: T::Balance,
+			c: T::Balance,
+		) -> Result<T::Balance, DispatchError> {
+			ensure!(!c.is_zero(), Error::<T>::InsufficientLiquidity);
+			let result = multiply_by_rational(
+				a.saturated_into::<u128>(),
+				b.saturated_into::<u128>(),
+				c.saturated_into::<u128>(),
+			)
+			.map_err(|_| Error::<T>::Overflow)?;
+			Ok(result.saturated_into())
+		}
+	}
+}
@@ -0,0 +1,157 @@
+use frame_support::{
+	parameter_types,
+	traits::{ConstU128, ConstU32},
+	PalletId,
+};
+use frame_system as system;
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Convert, IdentityLookup},
+	Permill,
+};
+
+use crate::pallet as amm;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+type Balance = u128;
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Amm: amm::{Pallet, Call, Storage, Event<T>},
+	}
+);
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+pub const PDEX: Balance = 1000_000_000_000;
+pub const USDT: u128 = 1;
+pub const DOT: u128 = 2;
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1 * PDEX;
+	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
+}
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Test>;
+	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u128;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+pub const TREASURY: u64 = 99;
+
+pub struct ShareAssetId;
+impl Convert<u128, u128> for ShareAssetId {
+	fn convert(asset: u128) -> u128 {
+		asset + 1_000
+	}
+}
+
+parameter_types! {
+	pub const AmmPalletId: PalletId = PalletId(*b"py/ammpl");
+	pub const TreasuryAccount: u64 = TREASURY;
+	pub const MinimumLiquidity: Balance = 1_000;
+	pub const DefaultSwapFee: Permill = Permill::from_parts(3_000);
+	pub const DefaultTreasuryShare: Permill = Permill::from_percent(20);
+}
+
+impl amm::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type Currency = Balances;
+	type AssetId = u128;
+	type Assets = Assets;
+	type ShareAssetId = ShareAssetId;
+	type TreasuryAccount = TreasuryAccount;
+	type GovernanceOrigin = EnsureRoot<u64>;
+	type PalletId = AmmPalletId;
+	type MinimumLiquidity = MinimumLiquidity;
+	type DefaultSwapFee = DefaultSwapFee;
+	type DefaultTreasuryShare = DefaultTreasuryShare;
+	type WeightInfo = crate::weights::WeightInfo<Test>;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 10_000 * PDEX), (2, 10_000 * PDEX), (TREASURY, 1 * PDEX)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_assets::GenesisConfig::<Test> {
+		assets: vec![(USDT, 1, true, 1), (DOT, 1, true, PDEX)],
+		metadata: vec![],
+		accounts: vec![
+			(USDT, 1, 10_000 * PDEX),
+			(USDT, 2, 10_000 * PDEX),
+			(DOT, 1, 10_000 * PDEX),
+			(DOT, 2, 10_000 * PDEX),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,245 @@
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{traits::BadOrigin, Permill};
+
+use crate::mock::{new_test_ext, Amm, Assets, Balances, Origin, Test, DOT, PDEX, TREASURY, USDT};
+
+use crate::pallet::*;
+use crate::types::*;
+
+fn create_pool() {
+	assert_ok!(Amm::create_pool(Origin::signed(1), USDT, 1_000 * PDEX, 2_000 * PDEX));
+}
+
+#[test]
+pub fn create_pool_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Amm::create_pool(Origin::signed(1), 42, 1_000 * PDEX, 2_000 * PDEX),
+			Error::<Test>::UnknownAsset
+		);
+		assert_noop!(
+			Amm::create_pool(Origin::signed(1), USDT, 1_000, 2_000 * PDEX),
+			Error::<Test>::InsufficientInitialLiquidity
+		);
+		create_pool();
+		assert_eq!(
+			Amm::pools(USDT),
+			Some(Pool {
+				native_reserve: 1_000 * PDEX,
+				asset_reserve: 2_000 * PDEX,
+				total_shares: 1_000 * PDEX
+			})
+		);
+		assert_eq!(Amm::liquidity_shares(USDT, &1), 1_000 * PDEX - 1_000);
+		assert_eq!(Assets::total_supply(Amm::share_asset(USDT)), 1_000 * PDEX - 1_000);
+		assert_eq!(Balances::free_balance(Amm::pool_account(USDT)), 1_000 * PDEX);
+		assert_eq!(Assets::balance(USDT, Amm::pool_account(USDT)), 2_000 * PDEX);
+		assert_eq!(Amm::spot_value(USDT, 5 * PDEX), Some(10 * PDEX));
+		assert_noop!(
+			Amm::create_pool(Origin::signed(2), USDT, 1_000 * PDEX, 2_000 * PDEX),
+			Error::<Test>::PoolAlreadyExists
+		);
+	});
+}
+
+#[test]
+pub fn add_liquidity_keeps_pool_ratio() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_noop!(
+			Amm::add_liquidity(Origin::signed(2), USDT, 100 * PDEX, 100 * PDEX, 0, 10),
+			Error::<Test>::ExcessiveInputAmount
+		);
+		assert_noop!(
+			Amm::add_liquidity(Origin::signed(2), USDT, 100 * PDEX, 300 * PDEX, 200 * PDEX, 10),
+			Error::<Test>::InsufficientOutputAmount
+		);
+		assert_ok!(Amm::add_liquidity(Origin::signed(2), USDT, 100 * PDEX, 300 * PDEX, 0, 10));
+		assert_eq!(Amm::liquidity_shares(USDT, &2), 100 * PDEX);
+		assert_eq!(Assets::balance(USDT, 2), 9_800 * PDEX - 1);
+		let pool = Amm::pools(USDT).unwrap();
+		assert_eq!(pool.native_reserve, 1_100 * PDEX);
+		assert_eq!(pool.asset_reserve, 2_200 * PDEX + 1);
+		assert_eq!(pool.total_shares, 1_100 * PDEX);
+	});
+}
+
+#[test]
+pub fn remove_liquidity_returns_share_of_reserves() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_noop!(
+			Amm::remove_liquidity(Origin::signed(2), USDT, PDEX, 0, 0, 10),
+			Error::<Test>::InsufficientShares
+		);
+		assert_noop!(
+			Amm::remove_liquidity(Origin::signed(1), USDT, 500 * PDEX, 600 * PDEX, 0, 10),
+			Error::<Test>::InsufficientOutputAmount
+		);
+		assert_ok!(Amm::remove_liquidity(Origin::signed(1), USDT, 500 * PDEX, 0, 0, 10));
+		assert_eq!(Amm::liquidity_shares(USDT, &1), 500 * PDEX - 1_000);
+		assert_eq!(Assets::total_supply(Amm::share_asset(USDT)), 500 * PDEX - 1_000);
+		assert_eq!(Balances::free_balance(1), 9_500 * PDEX);
+		assert_eq!(Assets::balance(USDT, 1), 9_000 * PDEX);
+		assert_eq!(
+			Amm::pools(USDT),
+			Some(Pool {
+				native_reserve: 500 * PDEX,
+				asset_reserve: 1_000 * PDEX,
+				total_shares: 500 * PDEX
+			})
+		);
+	});
+}
+
+#[test]
+pub fn swap_pays_treasury_part_of_fee() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		let quote = Amm::quote_swap(USDT, SwapKind::NativeToAsset, 10 * PDEX).unwrap();
+		assert_eq!(
+			quote,
+			SwapQuote {
+				amount_out: 19_743_160_687_941,
+				fee: 30_000_000_000,
+				treasury_fee: 6_000_000_000
+			}
+		);
+		assert_ok!(Amm::swap_exact_in(
+			Origin::signed(2),
+			USDT,
+			SwapKind::NativeToAsset,
+			10 * PDEX,
+			quote.amount_out,
+			10
+		));
+		assert_eq!(Balances::free_balance(2), 9_990 * PDEX);
+		assert_eq!(Assets::balance(USDT, 2), 10_000 * PDEX + 19_743_160_687_941);
+		assert_eq!(Balances::free_balance(TREASURY), PDEX + 6_000_000_000);
+		let pool = Amm::pools(USDT).unwrap();
+		assert_eq!(pool.native_reserve, 1_010 * PDEX - 6_000_000_000);
+		assert_eq!(pool.asset_reserve, 2_000 * PDEX - 19_743_160_687_941);
+
+		assert_ok!(Amm::swap_exact_in(
+			Origin::signed(2),
+			USDT,
+			SwapKind::AssetToNative,
+			20 * PDEX,
+			0,
+			10
+		));
+		assert_eq!(Assets::balance(USDT, TREASURY), 12_000_000_000);
+	});
+}
+
+#[test]
+pub fn liquidity_shares_are_transferable() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_ok!(Assets::transfer(Origin::signed(1), Amm::share_asset(USDT), 2, 100 * PDEX));
+		assert_eq!(Amm::liquidity_shares(USDT, &2), 100 * PDEX);
+
+		assert_ok!(Amm::remove_liquidity(Origin::signed(2), USDT, 100 * PDEX, 0, 0, 10));
+		assert_eq!(Amm::liquidity_shares(USDT, &2), 0);
+		assert_eq!(Balances::free_balance(2), 10_100 * PDEX);
+		assert_eq!(Assets::balance(USDT, 2), 10_200 * PDEX);
+		assert_noop!(
+			Amm::remove_liquidity(Origin::signed(2), USDT, 1, 0, 0, 10),
+			Error::<Test>::InsufficientShares
+		);
+	});
+}
+
+#[test]
+pub fn pool_keeps_the_existential_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Amm::create_pool(Origin::signed(1), USDT, 2 * PDEX, 2 * PDEX));
+		// Draining the PDEX reserve through a swap would reap the pool account.
+		assert_eq!(Amm::quote_swap(USDT, SwapKind::AssetToNative, 9_000 * PDEX), None);
+		assert_noop!(
+			Amm::swap_exact_in(
+				Origin::signed(2),
+				USDT,
+				SwapKind::AssetToNative,
+				9_000 * PDEX,
+				0,
+				10
+			),
+			Error::<Test>::InsufficientLiquidity
+		);
+		// The last provider withdraws everything but the existential deposit.
+		let shares = Amm::liquidity_shares(USDT, &1);
+		assert_ok!(Amm::remove_liquidity(Origin::signed(1), USDT, shares, 0, 0, 10));
+		assert_eq!(Amm::liquidity_shares(USDT, &1), 0);
+		assert_eq!(Balances::free_balance(1), 9_999 * PDEX);
+		assert_eq!(Balances::free_balance(Amm::pool_account(USDT)), PDEX);
+		assert_eq!(
+			Amm::pools(USDT),
+			Some(Pool { native_reserve: PDEX, asset_reserve: 1_000, total_shares: 1_000 })
+		);
+	});
+}
+
+#[test]
+pub fn treasury_fee_below_min_balance_stays_in_pool() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Amm::create_pool(Origin::signed(1), DOT, 1_000 * PDEX, 2_000 * PDEX));
+		// 20% of the 0.3% fee on 10 DOT is below the minimum balance of DOT.
+		let quote = Amm::quote_swap(DOT, SwapKind::AssetToNative, 10 * PDEX).unwrap();
+		assert_eq!(quote.fee, 30_000_000_000);
+		assert_eq!(quote.treasury_fee, 0);
+		assert_ok!(Amm::swap_exact_in(
+			Origin::signed(2),
+			DOT,
+			SwapKind::AssetToNative,
+			10 * PDEX,
+			quote.amount_out,
+			10
+		));
+		assert_eq!(Assets::balance(DOT, TREASURY), 0);
+		assert_eq!(Amm::pools(DOT).unwrap().asset_reserve, 2_010 * PDEX);
+		assert_eq!(Assets::balance(DOT, Amm::pool_account(DOT)), 2_010 * PDEX);
+	});
+}
+
+#[test]
+pub fn swap_respects_slippage_and_deadline() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_noop!(
+			Amm::swap_exact_in(
+				Origin::signed(2),
+				USDT,
+				SwapKind::NativeToAsset,
+				10 * PDEX,
+				20 * PDEX,
+				10
+			),
+			Error::<Test>::InsufficientOutputAmount
+		);
+		frame_system::Pallet::<Test>::set_block_number(11);
+		assert_noop!(
+			Amm::swap_exact_in(Origin::signed(2), USDT, SwapKind::NativeToAsset, 10 * PDEX, 0, 10),
+			Error::<Test>::DeadlinePassed
+		);
+		assert_noop!(
+			Amm::swap_exact_in(Origin::signed(2), 42, SwapKind::NativeToAsset, 10 * PDEX, 0, 20),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+pub fn set_fee_config_works() {
+	new_test_ext().execute_with(|| {
+		let fees =
+			FeeConfig { swap_fee: Permill::from_percent(1), treasury_share: Permill::zero() };
+		assert_eq!(Amm::fee_config().swap_fee, Permill::from_parts(3_000));
+		assert_noop!(
+			Amm::set_fee_config(Origin::signed(1), fees.swap_fee, fees.treasury_share),
+			BadOrigin
+		);
+		assert_ok!(Amm::set_fee_config(Origin::root(), fees.swap_fee, fees.treasury_share));
+		assert_eq!(Amm::fee_config(), fees);
+	});
+}
@@ -0,0 +1,48 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::Permill;
+
+/// Reserves of a pool between PDEX and a single asset.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Pool<Balance> {
+	pub native_reserve: Balance,
+	pub asset_reserve: Balance,
+	/// Liquidity shares issued, including the permanently locked minimum liquidity.
+	pub total_shares: Balance,
+}
+
+/// Direction of a swap through a pool.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum SwapKind {
+	/// Sell PDEX for the pool asset.
+	NativeToAsset,
+	/// Sell the pool asset for PDEX.
+	AssetToNative,
+}
+
+/// Fee charged on the input of every swap.
+#[derive(
+	Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct FeeConfig {
+	/// Part of the swap input kept as a fee.
+	pub swap_fee: Permill,
+	/// Part of the fee sent to the treasury, the rest stays in the pool for liquidity providers.
+	pub treasury_share: Permill,
+}
+
+/// Outcome of pricing a swap against the current reserves.
+#[derive(Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct SwapQuote<Balance> {
+	/// Amount paid out of the pool.
+	pub amount_out: Balance,
+	/// Whole fee taken from the input.
+	pub fee: Balance,
+	/// Part of the fee sent to the treasury.
+	pub treasury_fee: Balance,
+}
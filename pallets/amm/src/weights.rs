@@ -0,0 +1,77 @@
+//! Autogenerated weights for `amm`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-05-04, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/polkadex-node
+// benchmark
+// --chain
+// dev
+// --execution
+// wasm
+// --wasm-execution
+// compiled
+// --pallet=amm
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output=benchout/amm.rs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for amm.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
+	// Storage: Amm Pools (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Account (r:3 w:3)
+	fn create_pool() -> Weight {
+		(74_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: Amm Pools (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:3 w:3)
+	fn add_liquidity() -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: Amm Pools (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:3 w:3)
+	fn remove_liquidity() -> Weight {
+		(78_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: Amm Pools (r:1 w:1)
+	// Storage: Amm Fees (r:1 w:0)
+	// Storage: System Account (r:3 w:3)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	fn swap_exact_in() -> Weight {
+		(83_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Amm Fees (r:0 w:1)
+	fn set_fee_config() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
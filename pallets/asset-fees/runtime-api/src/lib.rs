@@ -0,0 +1,23 @@
+//! Runtime API definition for paying transaction fees in assets.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_transaction_payment::FeeDetails;
+use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+
+sp_api::decl_runtime_apis! {
+	pub trait AssetFeesApi<AssetId, Balance> where
+		AssetId: Codec,
+		Balance: Codec + MaybeDisplay,
+	{
+		/// Fee details of `uxt` when paid in `asset`, or in PDEX if `asset` is `None`.
+		///
+		/// Returns `None` if fees cannot be paid in `asset`.
+		fn query_fee_details(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			asset: Option<AssetId>,
+		) -> Option<FeeDetails<Balance>>;
+	}
+}
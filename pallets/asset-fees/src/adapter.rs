@@ -0,0 +1,84 @@
+use frame_support::traits::{
+	fungibles::{Balanced, CreditOf, Inspect},
+	tokens::WithdrawConsequence,
+	OnUnbalanced,
+};
+use pallet_asset_tx_payment::OnChargeAssetTransaction;
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+use sp_std::marker::PhantomData;
+
+use crate::pallet::{Config, Event, Pallet};
+
+/// Charges transaction fees in the assets accepted by governance.
+///
+/// Fee and tip are converted from PDEX at the rate of the asset, the fee is handed to `OF` and
+/// the tip to `OT`, which defaults to the fee handler.
+pub struct AssetFeeAdapter<T, OF, OT = OF>(PhantomData<(T, OF, OT)>);
+
+type AssetCreditOf<T> = CreditOf<<T as frame_system::Config>::AccountId, <T as Config>::Assets>;
+
+impl<T, OF, OT> OnChargeAssetTransaction<T> for AssetFeeAdapter<T, OF, OT>
+where
+	T: Config + pallet_asset_tx_payment::Config<Fungibles = <T as Config>::Assets>,
+	OF: OnUnbalanced<AssetCreditOf<T>>,
+	OT: OnUnbalanced<AssetCreditOf<T>>,
+{
+	type Balance = <T as Config>::Balance;
+	type AssetId = <T as Config>::AssetId;
+	type LiquidityInfo = AssetCreditOf<T>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		_call: &<T as frame_system::Config>::Call,
+		_dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		asset_id: Self::AssetId,
+		fee: Self::Balance,
+		_tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let converted_fee =
+			Pallet::<T>::native_to_asset(asset_id, fee).ok_or(InvalidTransaction::Payment)?;
+		// The fee must not reap the account, it still has to pay for its next transactions.
+		if !matches!(
+			<T as Config>::Assets::can_withdraw(asset_id, who, converted_fee),
+			WithdrawConsequence::Success
+		) {
+			return Err(InvalidTransaction::Payment.into())
+		}
+		<T as Config>::Assets::withdraw(asset_id, who, converted_fee)
+			.map_err(|_| TransactionValidityError::from(InvalidTransaction::Payment))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		_dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		_post_info: &PostDispatchInfoOf<<T as frame_system::Config>::Call>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		let asset_id = already_withdrawn.asset();
+		// Keep everything that was withdrawn if the asset stopped being accepted during dispatch.
+		let converted_fee = Pallet::<T>::native_to_asset(asset_id, corrected_fee)
+			.unwrap_or_else(|| already_withdrawn.peek());
+		let converted_tip = Pallet::<T>::native_to_asset(asset_id, tip).unwrap_or_else(Zero::zero);
+		let (paid, refund) = already_withdrawn.split(converted_fee);
+		// A refund below the minimum balance of the asset cannot be deposited and is kept as fee.
+		let paid = match <T as Config>::Assets::resolve(who, refund) {
+			Ok(()) => paid,
+			Err(refund) => paid.merge(refund).unwrap_or_else(|(paid, _)| paid),
+		};
+		let (tip, fee) = paid.split(converted_tip);
+		Pallet::<T>::deposit_event(Event::AssetFeePaid {
+			who: who.clone(),
+			asset: asset_id,
+			fee: fee.peek(),
+			tip: tip.peek(),
+		});
+		OF::on_unbalanced(fee);
+		OT::on_unbalanced(tip);
+		Ok(())
+	}
+}
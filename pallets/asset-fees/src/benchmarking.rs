@@ -0,0 +1,31 @@
+//! Benchmarking setup for asset-fees
+
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::{
+	assert_ok,
+	traits::{fungibles::Inspect, EnsureOrigin},
+};
+use sp_runtime::{traits::Zero, FixedU128};
+
+use crate::pallet::{Pallet as AssetFees, *};
+
+use super::*;
+
+benchmarks! {
+	where_clause { where T::Assets: frame_support::traits::fungibles::Create<T::AccountId> }
+
+	set_fee_asset {
+		let admin: T::AccountId = account("admin", 0, 0);
+		let asset = T::AssetId::default();
+		if T::Assets::minimum_balance(asset).is_zero() {
+			assert_ok!(T::Assets::create(asset, admin, true, 1u32.into()));
+		}
+		let origin = T::GovernanceOrigin::successful_origin();
+		let rate = Some(FeeRate::Fixed(FixedU128::from(2)));
+	}: _<T::Origin>(origin, asset, rate)
+	verify {
+		assert_eq!(AssetFees::<T>::fee_rate(asset), rate);
+	}
+}
+
+impl_benchmark_test_suite!(AssetFees, crate::mock::new_test_ext(), crate::mock::Test);
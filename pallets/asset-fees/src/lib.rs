@@ -0,0 +1,172 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+//! Transaction fees paid in assets.
+//!
+//! Governance decides which assets are accepted for fees and how a fee in PDEX is converted into
+//! each of them, either at a fixed rate or at the spot price of the asset's PDEX pool bounded
+//! around a reference rate.
+//! [`AssetFeeAdapter`] charges the converted fee for `pallet-asset-tx-payment` and announces it
+//! with an `AssetFeePaid` event.
+
+use frame_support::weights::Weight;
+pub mod adapter;
+pub mod types;
+pub mod weights;
+
+pub use adapter::AssetFeeAdapter;
+pub use pallet::*;
+pub use types::*;
+
+/// Weight functions needed for asset_fees.
+pub trait WeightInfo {
+	fn set_fee_asset() -> Weight;
+}
+
+/// Prices PDEX in assets using on-chain liquidity.
+pub trait PoolPrice<AssetId, Balance> {
+	/// Value of `native_amount` PDEX in `asset`, if there is a pool to price it.
+	fn native_to_asset(asset: AssetId, native_amount: Balance) -> Option<Balance>;
+}
+
+impl<AssetId, Balance> PoolPrice<AssetId, Balance> for () {
+	fn native_to_asset(_asset: AssetId, _native_amount: Balance) -> Option<Balance> {
+		None
+	}
+}
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, traits::fungibles};
+	use frame_system::pallet_prelude::*;
+	use pallet_transaction_payment::{FeeDetails, InclusionFee};
+	use sp_runtime::{
+		traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Saturating, Zero},
+		FixedPointNumber, FixedPointOperand,
+	};
+
+	use crate::{types::*, PoolPrice, WeightInfo};
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Balance type of PDEX and the assets.
+		type Balance: Parameter
+			+ Member
+			+ AtLeast32BitUnsigned
+			+ Default
+			+ Copy
+			+ MaxEncodedLen
+			+ MaybeSerializeDeserialize
+			+ FixedPointOperand;
+		/// Identifier of an asset.
+		type AssetId: Parameter
+			+ Member
+			+ Copy
+			+ Default
+			+ MaxEncodedLen
+			+ MaybeSerializeDeserialize;
+		/// The assets in which fees are paid.
+		type Assets: fungibles::Balanced<
+			Self::AccountId,
+			AssetId = Self::AssetId,
+			Balance = Self::Balance,
+		>;
+		/// Prices assets charged at [`FeeRate::Pool`].
+		type PoolPrice: PoolPrice<Self::AssetId, Self::Balance>;
+		/// Origin allowed to change the assets accepted for fees.
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+		/// Weight Info for asset fees
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Assets accepted for fees and how fees are converted into them
+	#[pallet::storage]
+	#[pallet::getter(fn fee_rate)]
+	pub(super) type FeeAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, FeeRate, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		FeeAssetUpdated { asset: T::AssetId, rate: Option<FeeRate> },
+		/// `who` paid a fee and a tip in `asset`, in units of the asset, handed to the fee handler.
+		AssetFeePaid { who: T::AccountId, asset: T::AssetId, fee: T::Balance, tip: T::Balance },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Asset does not exist
+		UnknownAsset,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Accepts fees in `asset` at `rate`, or stops accepting them if `rate` is `None`.
+		#[pallet::weight(<T as Config>::WeightInfo::set_fee_asset())]
+		pub fn set_fee_asset(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			rate: Option<FeeRate>,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(
+				!<T::Assets as fungibles::Inspect<_>>::minimum_balance(asset).is_zero(),
+				Error::<T>::UnknownAsset
+			);
+			FeeAssets::<T>::set(asset, rate);
+			Self::deposit_event(Event::FeeAssetUpdated { asset, rate });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Value of `native_amount` PDEX in `asset`, if fees can be paid in `asset`.
+		pub fn native_to_asset(asset: T::AssetId, native_amount: T::Balance) -> Option<T::Balance> {
+			if native_amount.is_zero() {
+				return FeeAssets::<T>::contains_key(asset).then(Zero::zero)
+			}
+			match FeeAssets::<T>::get(asset)? {
+				FeeRate::Fixed(rate) => Some(rate.saturating_mul_int(native_amount)),
+				FeeRate::Pool { reference, tolerance } => {
+					let spot = T::PoolPrice::native_to_asset(asset, native_amount)?;
+					let expected = reference.saturating_mul_int(native_amount);
+					let margin = tolerance.mul_floor(expected);
+					let (low, high) =
+						(expected.saturating_sub(margin), expected.saturating_add(margin));
+					Some(spot.clamp(low, high))
+				},
+			}
+		}
+
+		/// Converts fee details in PDEX into `asset`.
+		pub fn fee_details_in_asset(
+			asset: T::AssetId,
+			details: FeeDetails<T::Balance>,
+		) -> Option<FeeDetails<T::Balance>> {
+			let inclusion_fee = match details.inclusion_fee {
+				Some(fee) => Some(InclusionFee {
+					base_fee: Self::native_to_asset(asset, fee.base_fee)?,
+					len_fee: Self::native_to_asset(asset, fee.len_fee)?,
+					adjusted_weight_fee: Self::native_to_asset(asset, fee.adjusted_weight_fee)?,
+				}),
+				None => None,
+			};
+			Some(FeeDetails { inclusion_fee, tip: Self::native_to_asset(asset, details.tip)? })
+		}
+	}
+}
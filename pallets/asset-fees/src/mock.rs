@@ -0,0 +1,165 @@
+use frame_support::{
+	parameter_types,
+	traits::{
+		fungibles::{Balanced, CreditOf},
+		ConstU128, ConstU32, OnUnbalanced,
+	},
+	weights::IdentityFee,
+};
+use frame_system as system;
+use frame_system::EnsureRoot;
+use pallet_transaction_payment::CurrencyAdapter;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+use crate::{pallet as asset_fees, AssetFeeAdapter, PoolPrice};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+type Balance = u128;
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Config<T>, Storage, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
+		AssetTxPayment: pallet_asset_tx_payment::{Pallet},
+		AssetFees: asset_fees::{Pallet, Call, Storage, Event<T>},
+	}
+);
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+pub const PDEX: Balance = 1000_000_000_000;
+pub const USDT: u128 = 1;
+pub const BTC: u128 = 2;
+pub const TREASURY: u64 = 99;
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1 * PDEX;
+	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
+}
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Test>;
+	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u128;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const OperationalFeeMultiplier: u8 = 5;
+}
+
+impl pallet_transaction_payment::Config for Test {
+	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type OperationalFeeMultiplier = OperationalFeeMultiplier;
+	type WeightToFee = IdentityFee<Balance>;
+	type LengthToFee = IdentityFee<Balance>;
+	type FeeMultiplierUpdate = ();
+}
+
+pub struct CreditToTreasury;
+impl OnUnbalanced<CreditOf<u64, Assets>> for CreditToTreasury {
+	fn on_nonzero_unbalanced(amount: CreditOf<u64, Assets>) {
+		let _ = Assets::resolve(&TREASURY, amount);
+	}
+}
+
+impl pallet_asset_tx_payment::Config for Test {
+	type Fungibles = Assets;
+	type OnChargeAssetTransaction = AssetFeeAdapter<Test, CreditToTreasury>;
+}
+
+/// Prices PDEX at ten units of PDEX per BTC.
+pub struct MockPool;
+impl PoolPrice<u128, Balance> for MockPool {
+	fn native_to_asset(asset: u128, native_amount: Balance) -> Option<Balance> {
+		(asset == BTC).then(|| native_amount / 10)
+	}
+}
+
+impl asset_fees::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u128;
+	type Assets = Assets;
+	type PoolPrice = MockPool;
+	type GovernanceOrigin = EnsureRoot<u64>;
+	type WeightInfo = crate::weights::WeightInfo<Test>;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 10_000 * PDEX)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_assets::GenesisConfig::<Test> {
+		assets: vec![(USDT, 1, true, 1), (BTC, 1, true, 1)],
+		metadata: vec![],
+		accounts: vec![(USDT, 2, 10_000 * PDEX), (BTC, 2, 10_000 * PDEX)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,150 @@
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::fungibles::Inspect,
+	weights::{DispatchInfo, PostDispatchInfo},
+};
+use pallet_asset_tx_payment::ChargeAssetTxPayment;
+use pallet_transaction_payment::{FeeDetails, InclusionFee};
+use sp_runtime::{
+	traits::{BadOrigin, SignedExtension},
+	transaction_validity::InvalidTransaction,
+	FixedPointNumber, FixedU128, Permill,
+};
+
+use crate::mock::{
+	new_test_ext, AssetFees, Assets, Balances, Call, Event, Origin, System, Test,
+	TransactionPayment, BTC, PDEX, TREASURY, USDT,
+};
+
+use crate::pallet::*;
+use crate::types::*;
+
+fn accept_usdt() {
+	assert_ok!(AssetFees::set_fee_asset(
+		Origin::root(),
+		USDT,
+		Some(FeeRate::Fixed(FixedU128::from(2)))
+	));
+}
+
+fn pool_rate(reference: u128, tolerance: u32) -> FeeRate {
+	FeeRate::Pool {
+		reference: FixedU128::saturating_from_rational(reference, 100),
+		tolerance: Permill::from_percent(tolerance),
+	}
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+#[test]
+pub fn set_fee_asset_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetFees::set_fee_asset(Origin::signed(1), USDT, Some(pool_rate(10, 10))),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetFees::set_fee_asset(Origin::root(), 42, Some(pool_rate(10, 10))),
+			Error::<Test>::UnknownAsset
+		);
+		accept_usdt();
+		assert_eq!(AssetFees::fee_rate(USDT), Some(FeeRate::Fixed(FixedU128::from(2))));
+		assert_ok!(AssetFees::set_fee_asset(Origin::root(), USDT, None));
+		assert_eq!(AssetFees::fee_rate(USDT), None);
+	});
+}
+
+#[test]
+pub fn native_to_asset_uses_rate_of_asset() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(AssetFees::native_to_asset(USDT, 100), None);
+		accept_usdt();
+		assert_ok!(AssetFees::set_fee_asset(Origin::root(), BTC, Some(pool_rate(10, 10))));
+		assert_eq!(AssetFees::native_to_asset(USDT, 100), Some(200));
+		assert_eq!(AssetFees::native_to_asset(BTC, 100), Some(10));
+		assert_eq!(AssetFees::native_to_asset(BTC, 0), Some(0));
+		assert_eq!(
+			AssetFees::fee_details_in_asset(
+				USDT,
+				FeeDetails {
+					inclusion_fee: Some(InclusionFee {
+						base_fee: 1,
+						len_fee: 2,
+						adjusted_weight_fee: 3
+					}),
+					tip: 4
+				}
+			),
+			Some(FeeDetails {
+				inclusion_fee: Some(InclusionFee {
+					base_fee: 2,
+					len_fee: 4,
+					adjusted_weight_fee: 6
+				}),
+				tip: 8
+			})
+		);
+	});
+}
+
+#[test]
+pub fn pool_rate_is_bounded_by_reference() {
+	new_test_ext().execute_with(|| {
+		// The pool quotes 10 BTC for 100 PDEX, 20 is expected and at most 10% is tolerated.
+		assert_ok!(AssetFees::set_fee_asset(Origin::root(), BTC, Some(pool_rate(20, 10))));
+		assert_eq!(AssetFees::native_to_asset(BTC, 100), Some(18));
+		assert_ok!(AssetFees::set_fee_asset(Origin::root(), BTC, Some(pool_rate(5, 20))));
+		assert_eq!(AssetFees::native_to_asset(BTC, 100), Some(6));
+		assert_ok!(AssetFees::set_fee_asset(Origin::root(), BTC, Some(pool_rate(9, 20))));
+		assert_eq!(AssetFees::native_to_asset(BTC, 100), Some(10));
+	});
+}
+
+#[test]
+pub fn fees_are_charged_in_asset() {
+	new_test_ext().execute_with(|| {
+		accept_usdt();
+		let info = DispatchInfo { weight: 1_000, ..Default::default() };
+		let post_info = PostDispatchInfo { actual_weight: Some(400), pays_fee: Default::default() };
+		let tip = 1_000;
+		let pre = ChargeAssetTxPayment::<Test>::from(tip, Some(USDT))
+			.pre_dispatch(&2, &remark(), &info, 10)
+			.unwrap();
+		let withdrawn = 2 * TransactionPayment::compute_fee(10, &info, tip);
+		assert_eq!(Assets::balance(USDT, 2), 10_000 * PDEX - withdrawn);
+
+		assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			10,
+			&Ok(())
+		));
+		let paid = 2 * TransactionPayment::compute_actual_fee(10, &info, &post_info, tip);
+		assert!(paid < withdrawn);
+		assert_eq!(Assets::balance(USDT, 2), 10_000 * PDEX - paid);
+		assert_eq!(Assets::balance(USDT, TREASURY), paid);
+		assert_eq!(Balances::free_balance(2), 0);
+		System::assert_has_event(Event::AssetFees(crate::Event::AssetFeePaid {
+			who: 2,
+			asset: USDT,
+			fee: paid - 2 * tip,
+			tip: 2 * tip,
+		}));
+	});
+}
+
+#[test]
+pub fn fees_are_rejected_in_unaccepted_asset() {
+	new_test_ext().execute_with(|| {
+		let info = DispatchInfo { weight: 1_000, ..Default::default() };
+		assert_eq!(
+			ChargeAssetTxPayment::<Test>::from(0, Some(BTC))
+				.pre_dispatch(&2, &remark(), &info, 10)
+				.map(|_| ()),
+			Err(InvalidTransaction::Payment.into())
+		);
+	});
+}
@@ -0,0 +1,14 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_runtime::{FixedU128, Permill};
+
+/// How fees paid in an asset are converted from PDEX.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum FeeRate {
+	/// Units of the asset charged per unit of PDEX, set by governance.
+	Fixed(FixedU128),
+	/// Spot price of the PDEX pool of the asset, kept within `tolerance` of the `reference` rate
+	/// set by governance. A pool moved within a block can only shift fees inside that band.
+	Pool { reference: FixedU128, tolerance: Permill },
+}
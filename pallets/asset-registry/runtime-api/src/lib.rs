@@ -0,0 +1,16 @@
+//! Runtime API definition for the asset registry pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use asset_registry::AssetMetadata;
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AssetRegistryApi<AssetId> where
+		AssetId: Codec,
+	{
+		/// All registered assets with their metadata.
+		fn registered_assets() -> Vec<(AssetId, AssetMetadata<Vec<u8>>)>;
+	}
+}
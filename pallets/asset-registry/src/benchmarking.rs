@@ -0,0 +1,54 @@
+//! Benchmarking setup for asset-registry
+
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::{
+	assert_ok,
+	traits::{EnsureOrigin, Get},
+};
+use sp_core::H160;
+use sp_runtime::SaturatedConversion;
+use sp_std::vec;
+
+use crate::pallet::{Pallet as AssetRegistry, *};
+
+use super::*;
+
+benchmarks! {
+	where_clause { where T::AssetId: From<u32> }
+
+	register_asset {
+		let admin: T::AccountId = account("admin", 0, 0);
+		let origin = T::RegistryOrigin::successful_origin();
+		let limit = T::StringLimit::get() as usize;
+		let asset_origin = AssetOrigin::Ethereum { contract: H160::repeat_byte(0x22) };
+	}: _<T::Origin>(
+		origin,
+		100u32.into(),
+		admin,
+		true,
+		1u128.saturated_into(),
+		vec![b'n'; limit],
+		vec![b's'; limit],
+		18,
+		asset_origin
+	)
+
+	update_metadata {
+		let admin: T::AccountId = account("admin", 0, 0);
+		let limit = T::StringLimit::get() as usize;
+		assert_ok!(AssetRegistry::<T>::register_asset(
+			T::RegistryOrigin::successful_origin(),
+			100u32.into(),
+			admin,
+			true,
+			1u128.saturated_into(),
+			vec![b'n'; limit],
+			vec![b's'; limit],
+			18,
+			AssetOrigin::Polkadex
+		));
+		let origin = T::RegistryOrigin::successful_origin();
+	}: _<T::Origin>(origin, 100u32.into(), vec![b'N'; limit], vec![b'S'; limit], 8)
+}
+
+impl_benchmark_test_suite!(AssetRegistry, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,230 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+//! Governed registry of the assets known to Polkadex.
+//!
+//! Assets are created in the assets pallet exclusively through this pallet, which records their
+//! metadata and, for bridged tokens, the contract they originate from. Registration is decided by
+//! governance and, unlike creation in the assets pallet, reserves no deposit. Identifiers reserved
+//! for assets that other pallets create, like the liquidity shares of AMM pools, can not be
+//! registered.
+
+use frame_support::weights::Weight;
+pub mod types;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::*;
+
+/// Weight functions needed for asset_registry.
+pub trait WeightInfo {
+	fn register_asset() -> Weight;
+	fn update_metadata() -> Weight;
+}
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{fungibles, Contains},
+		transactional,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_core::H160;
+	use sp_runtime::traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Zero};
+	use sp_std::vec::Vec;
+
+	use crate::{types::*, WeightInfo};
+
+	pub type AssetMetadataOf<T> = AssetMetadata<BoundedVec<u8, <T as Config>::StringLimit>>;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Balance type of the assets.
+		type Balance: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaxEncodedLen;
+		/// Identifier of an asset.
+		type AssetId: Parameter + Member + Copy + MaxEncodedLen + MaybeSerializeDeserialize;
+		/// The assets pallet, in which registered assets are created.
+		type Assets: fungibles::Inspect<Self::AccountId, AssetId = Self::AssetId, Balance = Self::Balance>
+			+ fungibles::Create<Self::AccountId>;
+		/// Origin allowed to register assets and change their metadata.
+		type RegistryOrigin: EnsureOrigin<Self::Origin>;
+		/// Identifiers of assets created by other pallets, which can not be registered.
+		type ReservedAssetIds: Contains<Self::AssetId>;
+		/// Maximum length of an asset name or symbol.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+		/// Weight Info for the asset registry
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Metadata of registered assets
+	#[pallet::storage]
+	#[pallet::getter(fn asset_metadata)]
+	pub(super) type RegisteredAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, AssetMetadataOf<T>, OptionQuery>;
+
+	/// Assets representing ERC20 tokens, by contract address
+	#[pallet::storage]
+	#[pallet::getter(fn erc20_asset)]
+	pub(super) type Erc20Assets<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, T::AssetId, OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Asset id, name, symbol, decimals and origin of assets created in the assets pallet
+		/// genesis.
+		pub assets: Vec<(T::AssetId, Vec<u8>, Vec<u8>, u8, AssetOrigin)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { assets: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (asset, name, symbol, decimals, origin) in &self.assets {
+				assert!(
+					!<T::Assets as fungibles::Inspect<_>>::minimum_balance(*asset).is_zero(),
+					"Registered asset must exist in the assets pallet"
+				);
+				let metadata =
+					Pallet::<T>::bounded_metadata(name.clone(), symbol.clone(), *decimals, *origin)
+						.expect("Asset name and symbol must fit the string limit");
+				Pallet::<T>::insert_asset(*asset, metadata)
+					.expect("Registered assets must be unique and not reserved");
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		AssetRegistered { asset: T::AssetId, origin: AssetOrigin },
+		MetadataUpdated { asset: T::AssetId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Asset is already registered
+		AssetAlreadyRegistered,
+		/// Asset identifier is reserved for assets created by other pallets
+		ReservedAssetId,
+		/// Asset is not registered
+		AssetNotRegistered,
+		/// Another asset already represents the ERC20 contract
+		Erc20AlreadyRegistered,
+		/// Name or symbol is longer than the string limit
+		BadMetadata,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Creates `asset` in the assets pallet, administered by `admin`, and records its
+		/// metadata.
+		///
+		/// Unlike creating an asset in the assets pallet, registering one reserves no asset
+		/// deposit: governance decides which assets take up storage, and its origin has no account
+		/// to reserve a deposit from. Nor is the deposit taken from `admin`, who has not consented
+		/// to it.
+		#[pallet::weight(<T as Config>::WeightInfo::register_asset())]
+		#[transactional]
+		#[allow(clippy::too_many_arguments)]
+		pub fn register_asset(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			admin: T::AccountId,
+			is_sufficient: bool,
+			min_balance: T::Balance,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			asset_origin: AssetOrigin,
+		) -> DispatchResult {
+			T::RegistryOrigin::ensure_origin(origin)?;
+			let metadata = Self::bounded_metadata(name, symbol, decimals, asset_origin)?;
+			Self::insert_asset(asset, metadata)?;
+			<T::Assets as fungibles::Create<_>>::create(asset, admin, is_sufficient, min_balance)?;
+			Self::deposit_event(Event::AssetRegistered { asset, origin: asset_origin });
+			Ok(())
+		}
+
+		/// Replaces the name, symbol and decimals of a registered asset.
+		#[pallet::weight(<T as Config>::WeightInfo::update_metadata())]
+		pub fn update_metadata(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+		) -> DispatchResult {
+			T::RegistryOrigin::ensure_origin(origin)?;
+			RegisteredAssets::<T>::try_mutate(asset, |metadata| -> DispatchResult {
+				let metadata = metadata.as_mut().ok_or(Error::<T>::AssetNotRegistered)?;
+				*metadata = Self::bounded_metadata(name, symbol, decimals, metadata.origin)?;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::MetadataUpdated { asset });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// All registered assets with their metadata.
+		pub fn registered_assets() -> Vec<(T::AssetId, AssetMetadata<Vec<u8>>)> {
+			RegisteredAssets::<T>::iter()
+				.map(|(asset, metadata)| (asset, metadata.into_unbounded()))
+				.collect()
+		}
+
+		fn insert_asset(asset: T::AssetId, metadata: AssetMetadataOf<T>) -> DispatchResult {
+			ensure!(!T::ReservedAssetIds::contains(&asset), Error::<T>::ReservedAssetId);
+			ensure!(
+				!RegisteredAssets::<T>::contains_key(asset),
+				Error::<T>::AssetAlreadyRegistered
+			);
+			if let AssetOrigin::Ethereum { contract } = metadata.origin {
+				ensure!(
+					!Erc20Assets::<T>::contains_key(contract),
+					Error::<T>::Erc20AlreadyRegistered
+				);
+				Erc20Assets::<T>::insert(contract, asset);
+			}
+			RegisteredAssets::<T>::insert(asset, metadata);
+			Ok(())
+		}
+
+		fn bounded_metadata(
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			origin: AssetOrigin,
+		) -> Result<AssetMetadataOf<T>, DispatchError> {
+			Ok(AssetMetadata {
+				name: name.try_into().map_err(|_| Error::<T>::BadMetadata)?,
+				symbol: symbol.try_into().map_err(|_| Error::<T>::BadMetadata)?,
+				decimals,
+				origin,
+			})
+		}
+	}
+}
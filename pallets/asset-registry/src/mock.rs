@@ -0,0 +1,154 @@
+use frame_support::{
+	parameter_types,
+	traits::{ConstU128, ConstU32, Contains},
+};
+use frame_system as system;
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+use crate::types::AssetOrigin;
+
+use crate::pallet as asset_registry;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+type Balance = u128;
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Config<T>, Storage, Event<T>},
+		AssetRegistry: asset_registry::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+pub const PDEX: Balance = 1000_000_000_000;
+pub const USDT: u128 = 1;
+pub const USDT_CONTRACT: [u8; 20] = [0xda; 20];
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1 * PDEX;
+	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
+}
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Test>;
+	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u128;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const StringLimit: u32 = 8;
+}
+
+pub const RESERVED_ASSET: u128 = 1 << 127;
+
+pub struct ReservedAssetIds;
+impl Contains<u128> for ReservedAssetIds {
+	fn contains(asset: &u128) -> bool {
+		asset & RESERVED_ASSET != 0
+	}
+}
+
+impl asset_registry::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u128;
+	type Assets = Assets;
+	type RegistryOrigin = EnsureRoot<u64>;
+	type ReservedAssetIds = ReservedAssetIds;
+	type StringLimit = StringLimit;
+	type WeightInfo = crate::weights::WeightInfo<Test>;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 10_000 * PDEX), (2, 10_000 * PDEX)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_assets::GenesisConfig::<Test> {
+		assets: vec![(USDT, 1, true, 1)],
+		metadata: vec![],
+		accounts: vec![(USDT, 1, 10_000 * PDEX), (USDT, 2, 10_000 * PDEX)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	asset_registry::GenesisConfig::<Test> {
+		assets: vec![(
+			USDT,
+			b"Tether".to_vec(),
+			b"USDT".to_vec(),
+			6,
+			AssetOrigin::Ethereum { contract: USDT_CONTRACT.into() },
+		)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
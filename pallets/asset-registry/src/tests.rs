@@ -0,0 +1,123 @@
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+use sp_core::H160;
+use sp_runtime::traits::BadOrigin;
+
+use crate::mock::{
+	new_test_ext, AssetRegistry, Assets, Origin, Test, RESERVED_ASSET, USDT, USDT_CONTRACT,
+};
+
+use crate::pallet::*;
+use crate::types::*;
+
+const BTC: u128 = 2;
+
+fn register_btc(contract: H160) -> frame_support::dispatch::DispatchResult {
+	AssetRegistry::register_asset(
+		Origin::root(),
+		BTC,
+		1,
+		true,
+		1,
+		b"Bitcoin".to_vec(),
+		b"WBTC".to_vec(),
+		8,
+		AssetOrigin::Ethereum { contract },
+	)
+}
+
+#[test]
+pub fn check_genesis_config() {
+	new_test_ext().execute_with(|| {
+		let contract: H160 = USDT_CONTRACT.into();
+		assert_eq!(AssetRegistry::erc20_asset(contract), Some(USDT));
+		assert_eq!(
+			AssetRegistry::registered_assets(),
+			vec![(
+				USDT,
+				AssetMetadata {
+					name: b"Tether".to_vec(),
+					symbol: b"USDT".to_vec(),
+					decimals: 6,
+					origin: AssetOrigin::Ethereum { contract }
+				}
+			)]
+		);
+	});
+}
+
+#[test]
+pub fn register_asset_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetRegistry::register_asset(
+				Origin::signed(1),
+				BTC,
+				1,
+				true,
+				1,
+				b"Bitcoin".to_vec(),
+				b"WBTC".to_vec(),
+				8,
+				AssetOrigin::Polkadex,
+			),
+			BadOrigin
+		);
+		assert_noop!(register_btc(USDT_CONTRACT.into()), Error::<Test>::Erc20AlreadyRegistered);
+		assert_ok!(register_btc(H160::repeat_byte(0x22)));
+		assert_eq!(Assets::minimum_balance(BTC), 1);
+		assert_eq!(AssetRegistry::erc20_asset(H160::repeat_byte(0x22)), Some(BTC));
+		assert_eq!(AssetRegistry::asset_metadata(BTC).unwrap().decimals, 8);
+		assert_noop!(register_btc(H160::repeat_byte(0x33)), Error::<Test>::AssetAlreadyRegistered);
+	});
+}
+
+#[test]
+pub fn reserved_asset_ids_can_not_be_registered() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetRegistry::register_asset(
+				Origin::root(),
+				BTC | RESERVED_ASSET,
+				1,
+				true,
+				1,
+				b"Bitcoin".to_vec(),
+				b"WBTC".to_vec(),
+				8,
+				AssetOrigin::Polkadex,
+			),
+			Error::<Test>::ReservedAssetId
+		);
+	});
+}
+
+#[test]
+pub fn update_metadata_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetRegistry::update_metadata(Origin::root(), BTC, b"B".to_vec(), b"B".to_vec(), 8),
+			Error::<Test>::AssetNotRegistered
+		);
+		assert_noop!(
+			AssetRegistry::update_metadata(
+				Origin::root(),
+				USDT,
+				b"Tether USD".to_vec(),
+				b"USDT".to_vec(),
+				6
+			),
+			Error::<Test>::BadMetadata
+		);
+		assert_ok!(AssetRegistry::update_metadata(
+			Origin::root(),
+			USDT,
+			b"USD".to_vec(),
+			b"USDT".to_vec(),
+			18
+		));
+		let metadata = AssetRegistry::asset_metadata(USDT).unwrap();
+		assert_eq!(metadata.name.into_inner(), b"USD".to_vec());
+		assert_eq!(metadata.decimals, 18);
+		assert_eq!(metadata.origin, AssetOrigin::Ethereum { contract: USDT_CONTRACT.into() });
+	});
+}
@@ -0,0 +1,38 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::H160;
+use sp_std::vec::Vec;
+
+/// Where the tokens represented by an asset originally live.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum AssetOrigin {
+	/// Issued on Polkadex.
+	Polkadex,
+	/// Bridged from an ERC20 contract on Ethereum.
+	Ethereum { contract: H160 },
+}
+
+/// Registry entry of an asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AssetMetadata<BoundedString> {
+	pub name: BoundedString,
+	pub symbol: BoundedString,
+	pub decimals: u8,
+	pub origin: AssetOrigin,
+}
+
+impl<BoundedString: Into<Vec<u8>>> AssetMetadata<BoundedString> {
+	/// Metadata with plain byte strings, as returned by the runtime API.
+	pub fn into_unbounded(self) -> AssetMetadata<Vec<u8>> {
+		AssetMetadata {
+			name: self.name.into(),
+			symbol: self.symbol.into(),
+			decimals: self.decimals,
+			origin: self.origin,
+		}
+	}
+}
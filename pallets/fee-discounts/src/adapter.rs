@@ -0,0 +1,116 @@
+use frame_support::traits::fungibles::{CreditOf, Inspect};
+use pallet_asset_tx_payment::OnChargeAssetTransaction;
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, DispatchInfoOf, PostDispatchInfoOf, Saturating},
+	transaction_validity::TransactionValidityError,
+	Percent,
+};
+use sp_std::marker::PhantomData;
+
+use crate::pallet::{Config, Pallet};
+
+/// Charges transactions through `OCT` with the fee, but not the tip, reduced by the discount of
+/// the sender. Wraps both the native `OnChargeTransaction` and the `OnChargeAssetTransaction` of
+/// fees paid in assets.
+pub struct DiscountAdapter<T, OCT>(PhantomData<(T, OCT)>);
+
+impl<T, OCT> OnChargeTransaction<T> for DiscountAdapter<T, OCT>
+where
+	T: Config + pallet_transaction_payment::Config,
+	OCT: OnChargeTransaction<T>,
+{
+	type Balance = OCT::Balance;
+	/// Discount applied when the fee was withdrawn, so that the fee is corrected with the same
+	/// discount even if the stake of the sender changed in between.
+	type LiquidityInfo = (Percent, OCT::LiquidityInfo);
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+		dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let discount = Pallet::<T>::discount(who);
+		let fee = discounted::<T, _>(discount, fee, tip);
+		OCT::withdraw_fee(who, call, dispatch_info, fee, tip).map(|info| (discount, info))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		post_info: &PostDispatchInfoOf<<T as frame_system::Config>::Call>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		(discount, already_withdrawn): Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		OCT::correct_and_deposit_fee(
+			who,
+			dispatch_info,
+			post_info,
+			discounted::<T, _>(discount, corrected_fee, tip),
+			tip,
+			already_withdrawn,
+		)
+	}
+}
+
+type AssetCreditOf<T> = CreditOf<
+	<T as frame_system::Config>::AccountId,
+	<T as pallet_asset_tx_payment::Config>::Fungibles,
+>;
+type AssetIdOf<T> = <<T as pallet_asset_tx_payment::Config>::Fungibles as Inspect<
+	<T as frame_system::Config>::AccountId,
+>>::AssetId;
+
+impl<T, OCT> OnChargeAssetTransaction<T> for DiscountAdapter<T, OCT>
+where
+	T: Config + pallet_asset_tx_payment::Config,
+	OCT: OnChargeAssetTransaction<T, AssetId = AssetIdOf<T>, LiquidityInfo = AssetCreditOf<T>>,
+{
+	type Balance = OCT::Balance;
+	type AssetId = OCT::AssetId;
+	/// The credit withdrawn in the asset, which can not carry the discount it was withdrawn with:
+	/// the fee is corrected with the discount of the sender after the dispatch.
+	type LiquidityInfo = OCT::LiquidityInfo;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+		dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		asset_id: Self::AssetId,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let fee = discounted::<T, _>(Pallet::<T>::discount(who), fee, tip);
+		OCT::withdraw_fee(who, call, dispatch_info, asset_id, fee, tip)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		post_info: &PostDispatchInfoOf<<T as frame_system::Config>::Call>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		OCT::correct_and_deposit_fee(
+			who,
+			dispatch_info,
+			post_info,
+			discounted::<T, _>(Pallet::<T>::discount(who), corrected_fee, tip),
+			tip,
+			already_withdrawn,
+		)
+	}
+}
+
+/// `fee_with_tip` with `discount` applied to the fee only.
+fn discounted<T: Config, Balance: AtLeast32BitUnsigned + Copy>(
+	discount: Percent,
+	fee_with_tip: Balance,
+	tip: Balance,
+) -> Balance {
+	Pallet::<T>::apply_discount(discount, fee_with_tip.saturating_sub(tip)).saturating_add(tip)
+}
@@ -0,0 +1,25 @@
+//! Benchmarking setup for fee-discounts
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::{EnsureOrigin, Get};
+use sp_runtime::Percent;
+use sp_std::vec::Vec;
+
+use crate::pallet::{Pallet as FeeDiscounts, *};
+
+use super::*;
+
+benchmarks! {
+	set_tiers {
+		let t in 0 .. T::MaxTiers::get();
+		let tiers: Vec<_> = (0..t)
+			.map(|i| DiscountTier { stake: (i + 1).into(), discount: Percent::from_percent(i as u8) })
+			.collect();
+		let origin = T::GovernanceOrigin::successful_origin();
+	}: _<T::Origin>(origin, tiers.clone())
+	verify {
+		assert_eq!(FeeDiscounts::<T>::tiers().into_inner(), tiers);
+	}
+}
+
+impl_benchmark_test_suite!(FeeDiscounts, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,193 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+//! Stake based discounts on transaction fees.
+//!
+//! Governance sets tiers of active stake, each giving a discount on transaction fees. An account
+//! gets the discount of the highest tier its stake reaches. Tips are never discounted.
+//! [`DiscountAdapter`] wraps the `OnChargeTransaction` and the `OnChargeAssetTransaction` of the
+//! runtime to charge the discounted fee, in PDEX or in assets, and the runtime applies
+//! [`Pallet::discounted_fee`] and [`Pallet::discounted_fee_details`] to fee queries so that they
+//! report the fee actually charged.
+
+use frame_support::weights::Weight;
+pub mod adapter;
+pub mod types;
+pub mod weights;
+
+pub use adapter::DiscountAdapter;
+pub use pallet::*;
+pub use types::*;
+
+/// Weight functions needed for fee_discounts.
+pub trait WeightInfo {
+	fn set_tiers(t: u32) -> Weight;
+}
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use feeless::StakeInfo;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_transaction_payment::{FeeDetails, InclusionFee};
+	use sp_runtime::{
+		traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Saturating},
+		Percent,
+	};
+	use sp_std::vec::Vec;
+
+	use crate::{types::*, WeightInfo};
+
+	pub type TiersOf<T> = BoundedVec<DiscountTier<<T as Config>::Balance>, <T as Config>::MaxTiers>;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Balance type of the stake.
+		type Balance: Parameter
+			+ Member
+			+ AtLeast32BitUnsigned
+			+ Default
+			+ Copy
+			+ MaxEncodedLen
+			+ MaybeSerializeDeserialize;
+		/// Active stake of accounts, checked against the tiers.
+		type StakeInfo: StakeInfo<Self::AccountId, Self::Balance>;
+		/// Origin allowed to set the tiers.
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+		/// Maximum number of tiers.
+		#[pallet::constant]
+		type MaxTiers: Get<u32>;
+		/// Weight Info for fee discounts
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Discount tiers, by strictly increasing stake
+	#[pallet::storage]
+	#[pallet::getter(fn tiers)]
+	pub(super) type Tiers<T: Config> = StorageValue<_, TiersOf<T>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Stake and discount of the tiers.
+		pub tiers: Vec<(T::Balance, Percent)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { tiers: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let tiers = self
+				.tiers
+				.iter()
+				.map(|&(stake, discount)| DiscountTier { stake, discount })
+				.collect();
+			Tiers::<T>::put(Pallet::<T>::checked_tiers(tiers).expect("Genesis tiers must be valid"));
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		TiersSet { tiers: Vec<DiscountTier<T::Balance>> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// More tiers than the tier limit
+		TooManyTiers,
+		/// Stakes of the tiers are not strictly increasing
+		TiersNotSorted,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Replaces the discount tiers. Stakes of the tiers must be strictly increasing.
+		#[pallet::weight(<T as Config>::WeightInfo::set_tiers(tiers.len() as u32))]
+		pub fn set_tiers(
+			origin: OriginFor<T>,
+			tiers: Vec<DiscountTier<T::Balance>>,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			Tiers::<T>::put(Self::checked_tiers(tiers.clone())?);
+			Self::deposit_event(Event::TiersSet { tiers });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Discount of the highest tier reached by the active stake of `who`.
+		pub fn discount(who: &T::AccountId) -> Percent {
+			let tiers = Self::tiers();
+			if tiers.is_empty() {
+				return Percent::zero()
+			}
+			let stake = T::StakeInfo::active_stake(who);
+			tiers
+				.iter()
+				.rev()
+				.find(|tier| stake >= tier.stake)
+				.map_or_else(Percent::zero, |tier| tier.discount)
+		}
+
+		/// `fee`, which must not include a tip, with the discount of `who` applied.
+		pub fn discounted_fee<Balance: AtLeast32BitUnsigned + Copy>(
+			who: &T::AccountId,
+			fee: Balance,
+		) -> Balance {
+			Self::apply_discount(Self::discount(who), fee)
+		}
+
+		/// `details` of the fee of a transaction of `who`, with the discount of `who` applied to
+		/// each part of the inclusion fee.
+		pub fn discounted_fee_details<Balance: AtLeast32BitUnsigned + Copy>(
+			who: &T::AccountId,
+			details: FeeDetails<Balance>,
+		) -> FeeDetails<Balance> {
+			let discount = Self::discount(who);
+			FeeDetails {
+				inclusion_fee: details.inclusion_fee.map(|fee| InclusionFee {
+					base_fee: Self::apply_discount(discount, fee.base_fee),
+					len_fee: Self::apply_discount(discount, fee.len_fee),
+					adjusted_weight_fee: Self::apply_discount(discount, fee.adjusted_weight_fee),
+				}),
+				tip: details.tip,
+			}
+		}
+
+		pub(crate) fn apply_discount<Balance: AtLeast32BitUnsigned + Copy>(
+			discount: Percent,
+			fee: Balance,
+		) -> Balance {
+			fee.saturating_sub(discount * fee)
+		}
+
+		fn checked_tiers(tiers: Vec<DiscountTier<T::Balance>>) -> Result<TiersOf<T>, DispatchError> {
+			ensure!(
+				tiers.windows(2).all(|pair| pair[0].stake < pair[1].stake),
+				Error::<T>::TiersNotSorted
+			);
+			tiers.try_into().map_err(|_| Error::<T>::TooManyTiers.into())
+		}
+	}
+}
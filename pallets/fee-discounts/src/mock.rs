@@ -0,0 +1,146 @@
+use feeless::StakeInfo;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, GenesisBuild},
+	weights::IdentityFee,
+};
+use frame_system as system;
+use frame_system::EnsureRoot;
+use pallet_transaction_payment::CurrencyAdapter;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Percent,
+};
+
+use crate::{pallet as fee_discounts, DiscountAdapter};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+type Balance = u128;
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
+		FeeDiscounts: fee_discounts::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+pub const PDEX: Balance = 1000_000_000_000;
+/// Has no stake.
+pub const NO_STAKE: u64 = 0;
+/// Has 1_000 PDEX staked, reaching the first tier.
+pub const SMALL_STAKER: u64 = 1;
+/// Has 20_000 PDEX staked, reaching the second tier.
+pub const BIG_STAKER: u64 = 20;
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1 * PDEX;
+	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
+}
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Test>;
+	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const OperationalFeeMultiplier: u8 = 5;
+}
+
+impl pallet_transaction_payment::Config for Test {
+	type OnChargeTransaction = DiscountAdapter<Test, CurrencyAdapter<Balances, ()>>;
+	type OperationalFeeMultiplier = OperationalFeeMultiplier;
+	type WeightToFee = IdentityFee<Balance>;
+	type LengthToFee = IdentityFee<Balance>;
+	type FeeMultiplierUpdate = ();
+}
+
+/// Every account has a thousand PDEX staked per unit of its id.
+pub struct MockStake;
+impl StakeInfo<u64, Balance> for MockStake {
+	fn active_stake(who: &u64) -> Balance {
+		*who as Balance * 1_000 * PDEX
+	}
+}
+
+parameter_types! {
+	pub const MaxTiers: u32 = 3;
+}
+
+impl fee_discounts::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type StakeInfo = MockStake;
+	type GovernanceOrigin = EnsureRoot<u64>;
+	type MaxTiers = MaxTiers;
+	type WeightInfo = crate::weights::WeightInfo<Test>;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(NO_STAKE, 100 * PDEX),
+			(SMALL_STAKER, 100 * PDEX),
+			(BIG_STAKER, 100 * PDEX),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	fee_discounts::GenesisConfig::<Test> {
+		tiers: vec![
+			(1_000 * PDEX, Percent::from_percent(10)),
+			(10_000 * PDEX, Percent::from_percent(20)),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
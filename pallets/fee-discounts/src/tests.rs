@@ -0,0 +1,146 @@
+use frame_support::{
+	assert_noop, assert_ok,
+	weights::{DispatchInfo, PostDispatchInfo},
+};
+use pallet_transaction_payment::{ChargeTransactionPayment, FeeDetails, InclusionFee};
+use sp_runtime::{
+	traits::{BadOrigin, SignedExtension},
+	Percent,
+};
+
+use crate::mock::{
+	new_test_ext, Balances, Call, Event, FeeDiscounts, Origin, System, Test, TransactionPayment,
+	BIG_STAKER, NO_STAKE, PDEX, SMALL_STAKER,
+};
+
+use crate::pallet::*;
+use crate::types::*;
+
+fn tier(stake: u128, discount: u8) -> DiscountTier<u128> {
+	DiscountTier { stake: stake * PDEX, discount: Percent::from_percent(discount) }
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn info() -> DispatchInfo {
+	DispatchInfo { weight: 1_000_000, ..Default::default() }
+}
+
+fn post_info() -> PostDispatchInfo {
+	PostDispatchInfo { actual_weight: Some(400_000), pays_fee: Default::default() }
+}
+
+#[test]
+pub fn set_tiers_works() {
+	new_test_ext().execute_with(|| {
+		let tiers = vec![tier(100, 5), tier(1_000, 10), tier(10_000, 20)];
+		assert_noop!(FeeDiscounts::set_tiers(Origin::signed(1), tiers.clone()), BadOrigin);
+		assert_noop!(
+			FeeDiscounts::set_tiers(Origin::root(), vec![tier(1_000, 10), tier(1_000, 20)]),
+			Error::<Test>::TiersNotSorted
+		);
+		assert_noop!(
+			FeeDiscounts::set_tiers(Origin::root(), vec![tier(1_000, 10), tier(100, 20)]),
+			Error::<Test>::TiersNotSorted
+		);
+		assert_noop!(
+			FeeDiscounts::set_tiers(
+				Origin::root(),
+				vec![tier(1, 1), tier(10, 2), tier(100, 3), tier(1_000, 4)]
+			),
+			Error::<Test>::TooManyTiers
+		);
+		assert_ok!(FeeDiscounts::set_tiers(Origin::root(), tiers.clone()));
+		System::assert_last_event(Event::FeeDiscounts(crate::Event::TiersSet {
+			tiers: tiers.clone(),
+		}));
+		assert_eq!(FeeDiscounts::tiers().into_inner(), tiers);
+		assert_ok!(FeeDiscounts::set_tiers(Origin::root(), vec![]));
+		assert!(FeeDiscounts::tiers().is_empty());
+	});
+}
+
+#[test]
+pub fn discount_is_the_highest_tier_reached() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(FeeDiscounts::discount(&NO_STAKE), Percent::zero());
+		assert_eq!(FeeDiscounts::discount(&SMALL_STAKER), Percent::from_percent(10));
+		assert_eq!(FeeDiscounts::discount(&9), Percent::from_percent(10));
+		assert_eq!(FeeDiscounts::discount(&10), Percent::from_percent(20));
+		assert_eq!(FeeDiscounts::discount(&BIG_STAKER), Percent::from_percent(20));
+		assert_eq!(FeeDiscounts::discounted_fee(&BIG_STAKER, 1_000u128), 800);
+		assert_ok!(FeeDiscounts::set_tiers(Origin::root(), vec![]));
+		assert_eq!(FeeDiscounts::discount(&BIG_STAKER), Percent::zero());
+	});
+}
+
+#[test]
+pub fn fee_details_are_discounted_but_not_tips() {
+	new_test_ext().execute_with(|| {
+		let details = |base_fee, len_fee, adjusted_weight_fee| FeeDetails {
+			inclusion_fee: Some(InclusionFee { base_fee, len_fee, adjusted_weight_fee }),
+			tip: 50u128,
+		};
+		assert_eq!(
+			FeeDiscounts::discounted_fee_details(&BIG_STAKER, details(100, 200, 1_000)),
+			details(80, 160, 800)
+		);
+		assert_eq!(
+			FeeDiscounts::discounted_fee_details(&NO_STAKE, details(100, 200, 1_000)),
+			details(100, 200, 1_000)
+		);
+		let unsigned = FeeDetails { inclusion_fee: None, tip: 0u128 };
+		assert_eq!(FeeDiscounts::discounted_fee_details(&BIG_STAKER, unsigned.clone()), unsigned);
+	});
+}
+
+#[test]
+pub fn fees_are_discounted_but_not_tips() {
+	new_test_ext().execute_with(|| {
+		let tip = 1_000;
+		for (who, discount) in [(NO_STAKE, 0), (SMALL_STAKER, 10), (BIG_STAKER, 20)] {
+			let pre = ChargeTransactionPayment::<Test>::from(tip)
+				.pre_dispatch(&who, &remark(), &info(), 10)
+				.unwrap();
+			let fee = TransactionPayment::compute_fee(10, &info(), 0);
+			let withdrawn = fee - Percent::from_percent(discount) * fee + tip;
+			assert_eq!(Balances::free_balance(who), 100 * PDEX - withdrawn);
+
+			assert_ok!(ChargeTransactionPayment::<Test>::post_dispatch(
+				Some(pre),
+				&info(),
+				&post_info(),
+				10,
+				&Ok(())
+			));
+			let fee = TransactionPayment::compute_actual_fee(10, &info(), &post_info(), 0);
+			let paid = fee - Percent::from_percent(discount) * fee + tip;
+			assert!(paid < withdrawn);
+			assert_eq!(Balances::free_balance(who), 100 * PDEX - paid);
+		}
+	});
+}
+
+#[test]
+pub fn fee_is_corrected_with_the_discount_it_was_withdrawn_with() {
+	new_test_ext().execute_with(|| {
+		let pre = ChargeTransactionPayment::<Test>::from(0)
+			.pre_dispatch(&BIG_STAKER, &remark(), &info(), 10)
+			.unwrap();
+		assert_ok!(FeeDiscounts::set_tiers(Origin::root(), vec![]));
+		assert_ok!(ChargeTransactionPayment::<Test>::post_dispatch(
+			Some(pre),
+			&info(),
+			&post_info(),
+			10,
+			&Ok(())
+		));
+		let fee = TransactionPayment::compute_actual_fee(10, &info(), &post_info(), 0);
+		assert_eq!(
+			Balances::free_balance(BIG_STAKER),
+			100 * PDEX - (fee - Percent::from_percent(20) * fee)
+		);
+	});
+}
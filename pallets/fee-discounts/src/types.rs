@@ -0,0 +1,13 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_runtime::Percent;
+
+/// Discount on transaction fees of accounts with at least `stake` bonded.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DiscountTier<Balance> {
+	/// Active stake needed for the discount.
+	pub stake: Balance,
+	/// Share of the fee waived.
+	pub discount: Percent,
+}
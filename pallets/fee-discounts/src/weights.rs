@@ -0,0 +1,42 @@
+//! Autogenerated weights for `fee_discounts`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-05-10, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/polkadex-node
+// benchmark
+// --chain
+// dev
+// --execution
+// wasm
+// --wasm-execution
+// compiled
+// --pallet=fee_discounts
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output=benchout/fee_discounts.rs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for fee_discounts.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
+	// Storage: FeeDiscounts Tiers (r:0 w:1)
+	fn set_tiers(t: u32, ) -> Weight {
+		(14_000_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((145_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,35 @@
+//! Benchmarking setup for fee-distribution
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::EnsureOrigin;
+use sp_runtime::Percent;
+
+use crate::pallet::{Pallet as FeeDistribution, *};
+
+use super::*;
+
+fn distribution() -> Distribution {
+	Distribution {
+		treasury: Percent::from_percent(50),
+		author: Percent::from_percent(30),
+		burn: Percent::from_percent(20),
+	}
+}
+
+benchmarks! {
+	set_fee_distribution {
+		let origin = T::UpdateOrigin::successful_origin();
+	}: _<T::Origin>(origin, distribution())
+	verify {
+		assert_eq!(FeeDistribution::<T>::fee_distribution(), distribution());
+	}
+
+	set_tip_distribution {
+		let origin = T::UpdateOrigin::successful_origin();
+	}: _<T::Origin>(origin, distribution())
+	verify {
+		assert_eq!(FeeDistribution::<T>::tip_distribution(), distribution());
+	}
+}
+
+impl_benchmark_test_suite!(FeeDistribution, crate::mock::new_test_ext(), crate::mock::Test);
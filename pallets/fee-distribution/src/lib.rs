@@ -0,0 +1,169 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+//! Distribution of transaction fees and tips.
+//!
+//! The pallet is the `OnUnbalanced` handler of transaction payment. It splits fees and tips
+//! between the treasury and the block author and burns the rest, following a [`Distribution`]
+//! stored on chain for each of them. Both distributions are set by governance and default to
+//! sending everything to the treasury. Every split is announced by a `Distributed` event. The
+//! runtime splits fees and tips paid in assets following the same distributions.
+
+use frame_support::weights::Weight;
+pub mod types;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::*;
+
+/// Weight functions needed for fee_distribution.
+pub trait WeightInfo {
+	fn set_fee_distribution() -> Weight;
+	fn set_tip_distribution() -> Weight;
+}
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, Imbalance, OnUnbalanced},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Zero;
+
+	use crate::{types::*, WeightInfo};
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Currency in which fees and tips are paid.
+		type Currency: Currency<Self::AccountId>;
+		/// Receives the treasury share.
+		type Treasury: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// Receives the block author share.
+		type Author: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// Origin allowed to change the distributions.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+		/// Weight Info for fee distribution
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Distribution of transaction fees
+	#[pallet::storage]
+	#[pallet::getter(fn fee_distribution)]
+	pub(super) type FeeDistribution<T: Config> = StorageValue<_, Distribution, ValueQuery>;
+
+	/// Distribution of transaction tips
+	#[pallet::storage]
+	#[pallet::getter(fn tip_distribution)]
+	pub(super) type TipDistribution<T: Config> = StorageValue<_, Distribution, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		FeeDistributionSet { distribution: Distribution },
+		TipDistributionSet { distribution: Distribution },
+		/// Fees or tips of a transaction were split between the treasury, the block author and
+		/// the burn.
+		Distributed {
+			total: BalanceOf<T>,
+			treasury: BalanceOf<T>,
+			author: BalanceOf<T>,
+			burnt: BalanceOf<T>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Shares of the distribution do not add up to 100%
+		InvalidDistribution,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Sets the distribution of transaction fees.
+		#[pallet::weight(<T as Config>::WeightInfo::set_fee_distribution())]
+		pub fn set_fee_distribution(
+			origin: OriginFor<T>,
+			distribution: Distribution,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(distribution.is_valid(), Error::<T>::InvalidDistribution);
+			FeeDistribution::<T>::put(distribution);
+			Self::deposit_event(Event::FeeDistributionSet { distribution });
+			Ok(())
+		}
+
+		/// Sets the distribution of transaction tips.
+		#[pallet::weight(<T as Config>::WeightInfo::set_tip_distribution())]
+		pub fn set_tip_distribution(
+			origin: OriginFor<T>,
+			distribution: Distribution,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(distribution.is_valid(), Error::<T>::InvalidDistribution);
+			TipDistribution::<T>::put(distribution);
+			Self::deposit_event(Event::TipDistributionSet { distribution });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Splits `amount` following `distribution`. Rounding leftovers go to the treasury.
+		pub fn distribute(amount: NegativeImbalanceOf<T>, distribution: Distribution) {
+			let total = amount.peek();
+			if total.is_zero() {
+				return
+			}
+			let (burnt, rest) = amount.split(distribution.burn * total);
+			let (author, treasury) = rest.split(distribution.author * total);
+			Self::deposit_event(Event::Distributed {
+				total,
+				treasury: treasury.peek(),
+				author: author.peek(),
+				burnt: burnt.peek(),
+			});
+			// Dropping the imbalance reduces the total issuance.
+			drop(burnt);
+			T::Author::on_unbalanced(author);
+			T::Treasury::on_unbalanced(treasury);
+		}
+	}
+
+	/// Distributes fees then tips, as handed over by transaction payment. A single imbalance is
+	/// distributed as fees.
+	impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Pallet<T> {
+		fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalanceOf<T>>) {
+			if let Some(fees) = fees_then_tips.next() {
+				Self::distribute(fees, Self::fee_distribution());
+				if let Some(tips) = fees_then_tips.next() {
+					Self::distribute(tips, Self::tip_distribution());
+				}
+			}
+		}
+
+		fn on_nonzero_unbalanced(fees: NegativeImbalanceOf<T>) {
+			Self::distribute(fees, Self::fee_distribution());
+		}
+	}
+}
@@ -0,0 +1,158 @@
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::Currency,
+	weights::{DispatchInfo, PostDispatchInfo},
+};
+use pallet_transaction_payment::ChargeTransactionPayment;
+use sp_runtime::{
+	traits::{BadOrigin, SignedExtension},
+	Percent,
+};
+
+use crate::mock::{
+	new_test_ext, Balances, Call, Event, FeeDistribution, Origin, System, Test, TransactionPayment,
+	AUTHOR, PDEX, TREASURY,
+};
+
+use crate::pallet::*;
+use crate::types::*;
+
+fn distribution(treasury: u8, author: u8, burn: u8) -> Distribution {
+	Distribution {
+		treasury: Percent::from_percent(treasury),
+		author: Percent::from_percent(author),
+		burn: Percent::from_percent(burn),
+	}
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+/// Charges the fees of a remark of account `1` with `tip`, and returns the fee paid without the
+/// tip.
+fn pay_fees(tip: u128) -> u128 {
+	let info = DispatchInfo { weight: 1_000_000, ..Default::default() };
+	let post_info = PostDispatchInfo { actual_weight: None, pays_fee: Default::default() };
+	let pre = ChargeTransactionPayment::<Test>::from(tip)
+		.pre_dispatch(&1, &remark(), &info, 10)
+		.unwrap();
+	assert_ok!(ChargeTransactionPayment::<Test>::post_dispatch(
+		Some(pre),
+		&info,
+		&post_info,
+		10,
+		&Ok(())
+	));
+	TransactionPayment::compute_actual_fee(10, &info, &post_info, tip) - tip
+}
+
+#[test]
+pub fn set_distributions_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeeDistribution::set_fee_distribution(Origin::signed(1), distribution(50, 30, 20)),
+			BadOrigin
+		);
+		assert_noop!(
+			FeeDistribution::set_fee_distribution(Origin::root(), distribution(50, 30, 30)),
+			Error::<Test>::InvalidDistribution
+		);
+		assert_noop!(
+			FeeDistribution::set_tip_distribution(Origin::root(), distribution(50, 30, 10)),
+			Error::<Test>::InvalidDistribution
+		);
+		assert_ok!(FeeDistribution::set_fee_distribution(Origin::root(), distribution(50, 30, 20)));
+		System::assert_last_event(Event::FeeDistribution(crate::Event::FeeDistributionSet {
+			distribution: distribution(50, 30, 20),
+		}));
+		assert_ok!(FeeDistribution::set_tip_distribution(Origin::root(), distribution(0, 100, 0)));
+		System::assert_last_event(Event::FeeDistribution(crate::Event::TipDistributionSet {
+			distribution: distribution(0, 100, 0),
+		}));
+		assert_eq!(FeeDistribution::fee_distribution(), distribution(50, 30, 20));
+		assert_eq!(FeeDistribution::tip_distribution(), distribution(0, 100, 0));
+	});
+}
+
+#[test]
+pub fn fees_and_tips_go_to_treasury_by_default() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(FeeDistribution::fee_distribution(), distribution(100, 0, 0));
+		assert_eq!(FeeDistribution::tip_distribution(), distribution(100, 0, 0));
+		let issuance = Balances::total_issuance();
+		let fee = pay_fees(1_000);
+		assert!(fee > 0);
+		assert_eq!(Balances::free_balance(1), 10_000 * PDEX - fee - 1_000);
+		assert_eq!(Balances::free_balance(TREASURY), 1 * PDEX + fee + 1_000);
+		assert_eq!(Balances::free_balance(AUTHOR), 1 * PDEX);
+		assert_eq!(Balances::total_issuance(), issuance);
+	});
+}
+
+#[test]
+pub fn fees_and_tips_are_distributed_separately() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FeeDistribution::set_fee_distribution(Origin::root(), distribution(50, 30, 20)));
+		assert_ok!(FeeDistribution::set_tip_distribution(Origin::root(), distribution(0, 100, 0)));
+		let issuance = Balances::total_issuance();
+		let fee = pay_fees(1_000);
+		let burnt = Percent::from_percent(20) * fee;
+		let to_author = Percent::from_percent(30) * fee;
+		assert!(burnt > 0 && to_author > 0);
+		assert_eq!(Balances::free_balance(1), 10_000 * PDEX - fee - 1_000);
+		assert_eq!(Balances::free_balance(TREASURY), 1 * PDEX + fee - burnt - to_author);
+		assert_eq!(Balances::free_balance(AUTHOR), 1 * PDEX + to_author + 1_000);
+		assert_eq!(Balances::total_issuance(), issuance - burnt);
+	});
+}
+
+#[test]
+pub fn everything_can_be_burnt() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FeeDistribution::set_fee_distribution(Origin::root(), distribution(0, 0, 100)));
+		assert_ok!(FeeDistribution::set_tip_distribution(Origin::root(), distribution(0, 0, 100)));
+		let issuance = Balances::total_issuance();
+		let fee = pay_fees(1_000);
+		assert_eq!(Balances::free_balance(TREASURY), 1 * PDEX);
+		assert_eq!(Balances::free_balance(AUTHOR), 1 * PDEX);
+		assert_eq!(Balances::total_issuance(), issuance - fee - 1_000);
+	});
+}
+
+#[test]
+pub fn distributions_are_announced() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FeeDistribution::set_fee_distribution(Origin::root(), distribution(50, 30, 20)));
+		assert_ok!(FeeDistribution::set_tip_distribution(Origin::root(), distribution(0, 100, 0)));
+		let distributed = || {
+			System::events()
+				.into_iter()
+				.filter_map(|record| match record.event {
+					Event::FeeDistribution(crate::Event::Distributed {
+						total,
+						treasury,
+						author,
+						burnt,
+					}) => Some((total, treasury, author, burnt)),
+					_ => None,
+				})
+				.collect::<Vec<_>>()
+		};
+
+		System::reset_events();
+		let fee = pay_fees(1_000);
+		let burnt = Percent::from_percent(20) * fee;
+		let to_author = Percent::from_percent(30) * fee;
+		assert_eq!(
+			distributed(),
+			vec![(fee, fee - burnt - to_author, to_author, burnt), (1_000, 0, 1_000, 0)]
+		);
+
+		// Nothing is announced for the absent tip.
+		System::reset_events();
+		let fee = pay_fees(0);
+		assert_eq!(distributed().len(), 1);
+		assert_eq!(distributed()[0].0, fee);
+	});
+}
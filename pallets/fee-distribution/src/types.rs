@@ -0,0 +1,36 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_runtime::Percent;
+
+/// Shares of an amount sent to the treasury and the block author, and burnt.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Distribution {
+	/// Share sent to the treasury.
+	pub treasury: Percent,
+	/// Share sent to the author of the block.
+	pub author: Percent,
+	/// Share removed from the total issuance.
+	pub burn: Percent,
+}
+
+impl Distribution {
+	/// Whether the shares add up to the whole amount.
+	pub fn is_valid(&self) -> bool {
+		u16::from(self.treasury.deconstruct()) +
+			u16::from(self.author.deconstruct()) +
+			u16::from(self.burn.deconstruct()) ==
+			100
+	}
+}
+
+/// Sends everything to the treasury.
+impl Default for Distribution {
+	fn default() -> Self {
+		Self {
+			treasury: Percent::from_percent(100),
+			author: Percent::from_percent(0),
+			burn: Percent::from_percent(0),
+		}
+	}
+}
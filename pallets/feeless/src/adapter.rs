@@ -0,0 +1,121 @@
+use frame_support::traits::{
+	fungibles::{CreditOf, Inspect},
+	GetCallMetadata,
+};
+use pallet_asset_tx_payment::OnChargeAssetTransaction;
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, Zero},
+	transaction_validity::TransactionValidityError,
+};
+use sp_std::marker::PhantomData;
+
+use crate::pallet::{Config, Pallet};
+
+/// Waives the fee of whitelisted calls from eligible accounts and charges every other
+/// transaction through `OCT`. Wraps both the native `OnChargeTransaction` and the
+/// `OnChargeAssetTransaction` of fees paid in assets.
+///
+/// The quota of feeless transactions is enforced by [`crate::CheckFeeless`], which must be part
+/// of the signed extensions of the runtime.
+pub struct FeelessAdapter<T, OCT>(PhantomData<(T, OCT)>);
+
+impl<T, OCT> OnChargeTransaction<T> for FeelessAdapter<T, OCT>
+where
+	T: Config + pallet_transaction_payment::Config,
+	<T as frame_system::Config>::Call: GetCallMetadata,
+	OCT: OnChargeTransaction<T>,
+{
+	type Balance = OCT::Balance;
+	/// `None` if the fee was waived.
+	type LiquidityInfo = Option<OCT::LiquidityInfo>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+		dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		if Pallet::<T>::is_feeless(who, call) {
+			return Ok(None)
+		}
+		OCT::withdraw_fee(who, call, dispatch_info, fee, tip).map(Some)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		post_info: &PostDispatchInfoOf<<T as frame_system::Config>::Call>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		match already_withdrawn {
+			Some(withdrawn) => OCT::correct_and_deposit_fee(
+				who,
+				dispatch_info,
+				post_info,
+				corrected_fee,
+				tip,
+				withdrawn,
+			),
+			None => Ok(()),
+		}
+	}
+}
+
+type AssetCreditOf<T> = CreditOf<
+	<T as frame_system::Config>::AccountId,
+	<T as pallet_asset_tx_payment::Config>::Fungibles,
+>;
+type AssetIdOf<T> = <<T as pallet_asset_tx_payment::Config>::Fungibles as Inspect<
+	<T as frame_system::Config>::AccountId,
+>>::AssetId;
+
+impl<T, OCT> OnChargeAssetTransaction<T> for FeelessAdapter<T, OCT>
+where
+	T: Config + pallet_asset_tx_payment::Config,
+	<T as frame_system::Config>::Call: GetCallMetadata,
+	OCT: OnChargeAssetTransaction<T, AssetId = AssetIdOf<T>, LiquidityInfo = AssetCreditOf<T>>,
+{
+	type Balance = OCT::Balance;
+	type AssetId = OCT::AssetId;
+	/// Empty if the fee was waived.
+	type LiquidityInfo = OCT::LiquidityInfo;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+		dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		asset_id: Self::AssetId,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		if Pallet::<T>::is_feeless(who, call) {
+			return Ok(AssetCreditOf::<T>::zero(asset_id))
+		}
+		OCT::withdraw_fee(who, call, dispatch_info, asset_id, fee, tip)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		post_info: &PostDispatchInfoOf<<T as frame_system::Config>::Call>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		if already_withdrawn.peek().is_zero() {
+			return Ok(())
+		}
+		OCT::correct_and_deposit_fee(
+			who,
+			dispatch_info,
+			post_info,
+			corrected_fee,
+			tip,
+			already_withdrawn,
+		)
+	}
+}
@@ -0,0 +1,31 @@
+//! Benchmarking setup for feeless
+
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::{EnsureOrigin, Get};
+use sp_runtime::SaturatedConversion;
+use sp_std::vec;
+
+use crate::pallet::{Pallet as Feeless, *};
+
+use super::*;
+
+benchmarks! {
+	set_call_status {
+		let origin = T::GovernanceOrigin::successful_origin();
+		let limit = T::MaxNameLength::get() as usize;
+	}: _<T::Origin>(origin, vec![b'p'; limit], vec![b'c'; limit], true)
+
+	set_registration {
+		let who: T::AccountId = account("who", 0, 0);
+		let origin = T::GovernanceOrigin::successful_origin();
+	}: _<T::Origin>(origin, who.clone(), true)
+	verify {
+		assert!(Feeless::<T>::is_registered(who));
+	}
+
+	set_minimum_stake {
+		let origin = T::GovernanceOrigin::successful_origin();
+	}: _<T::Origin>(origin, Some(1u128.saturated_into()))
+}
+
+impl_benchmark_test_suite!(Feeless, crate::mock::new_test_ext(), crate::mock::Test);
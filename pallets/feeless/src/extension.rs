@@ -0,0 +1,110 @@
+use codec::{Decode, Encode};
+use frame_support::traits::{Get, GetCallMetadata};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionLongevity, TransactionValidity, TransactionValidityError,
+		ValidTransaction,
+	},
+};
+
+use crate::pallet::{Config, Pallet};
+
+/// Custom error reported for feeless transactions beyond the per block quota of the sender.
+pub const FEELESS_QUOTA_EXCEEDED: u8 = 1;
+
+/// Number of blocks a feeless transaction stays valid in the pool.
+pub const FEELESS_LONGEVITY: TransactionLongevity = 4;
+
+/// Limits the number of feeless transactions an account can submit per block.
+///
+/// Fees of whitelisted calls from eligible accounts are waived by [`crate::FeelessAdapter`],
+/// this extension only enforces the quota. It must be placed before `ChargeTransactionPayment`.
+///
+/// The pool validates on the state of the best block, so an account that used up its quota in
+/// the best block has its feeless transactions rejected until a block leaves it some quota.
+/// Transactions validated on the same state all see the same count, so every feeless transaction
+/// of an account provides the same tag: the pool keeps one of them at a time, for at most
+/// [`FEELESS_LONGEVITY`] blocks, and rejects the others.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckFeeless<T: Config + Send + Sync>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckFeeless<T> {
+	pub fn new() -> Self {
+		Self(sp_std::marker::PhantomData)
+	}
+
+	fn check_quota(who: &T::AccountId) -> Result<(), TransactionValidityError> {
+		if Pallet::<T>::feeless_count(who) < T::MaxFeelessPerBlock::get() {
+			Ok(())
+		} else {
+			Err(InvalidTransaction::Custom(FEELESS_QUOTA_EXCEEDED).into())
+		}
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckFeeless<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckFeeless<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckFeeless")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckFeeless<T>
+where
+	<T as frame_system::Config>::Call: GetCallMetadata,
+{
+	const IDENTIFIER: &'static str = "CheckFeeless";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		if Pallet::<T>::is_feeless(who, call) {
+			Self::check_quota(who)?;
+			return ValidTransaction::with_tag_prefix("CheckFeeless")
+				.and_provides(who)
+				.longevity(FEELESS_LONGEVITY)
+				.build()
+		}
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		if Pallet::<T>::is_feeless(who, call) {
+			Self::check_quota(who)?;
+			Pallet::<T>::note_feeless(who);
+		}
+		Ok(())
+	}
+}
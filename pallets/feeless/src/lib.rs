@@ -0,0 +1,276 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+//! Feeless, rate limited transactions.
+//!
+//! Governance whitelists calls, such as trading or bridge relaying, that eligible accounts may
+//! submit without paying transaction fees. An account is eligible when it is registered by
+//! governance or has at least the minimum stake bonded. Feeless transactions are limited to a
+//! fixed number per account and block.
+//!
+//! The [`CheckFeeless`] signed extension enforces the quota and must be placed before
+//! `ChargeTransactionPayment`, whose `OnChargeTransaction` is wrapped in [`FeelessAdapter`].
+
+use frame_support::weights::Weight;
+pub mod adapter;
+pub mod extension;
+pub mod weights;
+
+pub use adapter::FeelessAdapter;
+pub use extension::CheckFeeless;
+pub use pallet::*;
+
+/// Weight functions needed for feeless.
+pub trait WeightInfo {
+	fn set_call_status() -> Weight;
+	fn set_registration() -> Weight;
+	fn set_minimum_stake() -> Weight;
+}
+
+/// Provides the amount an account has actively staked.
+pub trait StakeInfo<AccountId, Balance> {
+	fn active_stake(who: &AccountId) -> Balance;
+}
+
+impl<AccountId, Balance: Default> StakeInfo<AccountId, Balance> for () {
+	fn active_stake(_who: &AccountId) -> Balance {
+		Balance::default()
+	}
+}
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{pallet_prelude::*, traits::GetCallMetadata};
+	use sp_io::KillStorageResult;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize};
+	use sp_std::vec::Vec;
+
+	use crate::{StakeInfo, WeightInfo};
+
+	pub type NameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Balance type of the stake.
+		type Balance: Parameter
+			+ Member
+			+ AtLeast32BitUnsigned
+			+ Default
+			+ Copy
+			+ MaxEncodedLen
+			+ MaybeSerializeDeserialize;
+		/// Active stake of accounts, checked against the minimum stake.
+		type StakeInfo: StakeInfo<Self::AccountId, Self::Balance>;
+		/// Origin allowed to change the whitelisted calls and eligible accounts.
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+		/// Maximum number of feeless transactions per account and block.
+		#[pallet::constant]
+		type MaxFeelessPerBlock: Get<u32>;
+		/// Maximum length of a pallet or call name.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+		/// Weight Info for feeless
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Calls that can be submitted without fees, by pallet and call name
+	#[pallet::storage]
+	pub(super) type FeelessCalls<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		NameOf<T>,
+		Blake2_128Concat,
+		NameOf<T>,
+		(),
+		OptionQuery,
+	>;
+
+	/// Accounts registered for feeless transactions regardless of their stake
+	#[pallet::storage]
+	#[pallet::getter(fn is_registered)]
+	pub(super) type Registered<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	/// Stake that makes an account eligible for feeless transactions, if any
+	#[pallet::storage]
+	#[pallet::getter(fn minimum_stake)]
+	pub(super) type MinimumStake<T: Config> = StorageValue<_, T::Balance, OptionQuery>;
+
+	/// Number of feeless transactions each account submitted in the current block, cleared when
+	/// the next block is initialized
+	#[pallet::storage]
+	pub(super) type FeelessCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Pallet and call names of the whitelisted calls.
+		pub calls: Vec<(Vec<u8>, Vec<u8>)>,
+		pub registered: Vec<T::AccountId>,
+		pub minimum_stake: Option<T::Balance>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				calls: Default::default(),
+				registered: Default::default(),
+				minimum_stake: Default::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (pallet_name, call_name) in &self.calls {
+				let (pallet_name, call_name) =
+					Pallet::<T>::bounded_names(pallet_name.clone(), call_name.clone())
+						.expect("Pallet and call names must fit the name length limit");
+				FeelessCalls::<T>::insert(pallet_name, call_name, ());
+			}
+			for who in &self.registered {
+				Registered::<T>::insert(who, true);
+			}
+			MinimumStake::<T>::set(self.minimum_stake);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		CallStatusUpdated { pallet_name: Vec<u8>, call_name: Vec<u8>, feeless: bool },
+		RegistrationUpdated { who: T::AccountId, registered: bool },
+		MinimumStakeUpdated { minimum_stake: Option<T::Balance> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Pallet or call name is longer than the name length limit
+		NameTooLong,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			let removed = match FeelessCount::<T>::remove_all(None) {
+				KillStorageResult::AllRemoved(removed) |
+				KillStorageResult::SomeRemaining(removed) => removed,
+			};
+			T::DbWeight::get().writes(removed.into())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Adds a call to, or removes it from, the feeless whitelist.
+		#[pallet::weight(<T as Config>::WeightInfo::set_call_status())]
+		pub fn set_call_status(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			call_name: Vec<u8>,
+			feeless: bool,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let (bounded_pallet, bounded_call) =
+				Self::bounded_names(pallet_name.clone(), call_name.clone())?;
+			if feeless {
+				FeelessCalls::<T>::insert(bounded_pallet, bounded_call, ());
+			} else {
+				FeelessCalls::<T>::remove(bounded_pallet, bounded_call);
+			}
+			Self::deposit_event(Event::CallStatusUpdated { pallet_name, call_name, feeless });
+			Ok(())
+		}
+
+		/// Registers an account for feeless transactions, or removes its registration.
+		#[pallet::weight(<T as Config>::WeightInfo::set_registration())]
+		pub fn set_registration(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			registered: bool,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			if registered {
+				Registered::<T>::insert(&who, true);
+			} else {
+				Registered::<T>::remove(&who);
+			}
+			Self::deposit_event(Event::RegistrationUpdated { who, registered });
+			Ok(())
+		}
+
+		/// Sets the stake that makes an account eligible. `None` disables stake based
+		/// eligibility.
+		#[pallet::weight(<T as Config>::WeightInfo::set_minimum_stake())]
+		pub fn set_minimum_stake(
+			origin: OriginFor<T>,
+			minimum_stake: Option<T::Balance>,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			MinimumStake::<T>::set(minimum_stake);
+			Self::deposit_event(Event::MinimumStakeUpdated { minimum_stake });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `call` is whitelisted and `who` is eligible to submit it without fees.
+		pub fn is_feeless<Call: GetCallMetadata>(who: &T::AccountId, call: &Call) -> bool {
+			Self::is_feeless_call(call) && Self::is_eligible(who)
+		}
+
+		pub fn is_feeless_call<Call: GetCallMetadata>(call: &Call) -> bool {
+			let metadata = call.get_call_metadata();
+			Self::bounded_names(metadata.pallet_name.into(), metadata.function_name.into())
+				.map(|(pallet_name, call_name)| {
+					FeelessCalls::<T>::contains_key(pallet_name, call_name)
+				})
+				.unwrap_or(false)
+		}
+
+		pub fn is_eligible(who: &T::AccountId) -> bool {
+			Registered::<T>::get(who) ||
+				MinimumStake::<T>::get()
+					.map_or(false, |minimum| T::StakeInfo::active_stake(who) >= minimum)
+		}
+
+		/// Number of feeless transactions `who` submitted in the current block. The transaction
+		/// pool validates on the state of the best block, before the counts are cleared, and so
+		/// sees the transactions `who` submitted in the best block.
+		pub fn feeless_count(who: &T::AccountId) -> u32 {
+			FeelessCount::<T>::get(who)
+		}
+
+		pub(crate) fn note_feeless(who: &T::AccountId) {
+			FeelessCount::<T>::mutate(who, |count| *count = count.saturating_add(1));
+		}
+
+		fn bounded_names(
+			pallet_name: Vec<u8>,
+			call_name: Vec<u8>,
+		) -> Result<(NameOf<T>, NameOf<T>), DispatchError> {
+			Ok((
+				pallet_name.try_into().map_err(|_| Error::<T>::NameTooLong)?,
+				call_name.try_into().map_err(|_| Error::<T>::NameTooLong)?,
+			))
+		}
+	}
+}
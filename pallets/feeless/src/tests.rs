@@ -0,0 +1,171 @@
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Get, Hooks},
+	weights::DispatchInfo,
+};
+use pallet_transaction_payment::ChargeTransactionPayment;
+use sp_runtime::{
+	traits::{BadOrigin, SignedExtension},
+	transaction_validity::InvalidTransaction,
+};
+
+use crate::mock::{
+	new_test_ext, Balances, Call, Feeless, MaxFeelessPerBlock, Origin, System, Test, OTHER, PDEX,
+	REGISTERED, STAKER,
+};
+
+use crate::{
+	extension::{FEELESS_LONGEVITY, FEELESS_QUOTA_EXCEEDED},
+	pallet::*,
+	CheckFeeless,
+};
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn transfer() -> Call {
+	Call::Balances(pallet_balances::Call::transfer { dest: OTHER, value: PDEX })
+}
+
+fn info() -> DispatchInfo {
+	DispatchInfo { weight: 10, ..Default::default() }
+}
+
+fn pay_fee(who: u64, call: &Call) -> u128 {
+	let before = Balances::free_balance(who);
+	let pre = ChargeTransactionPayment::<Test>::from(0)
+		.pre_dispatch(&who, call, &info(), 10)
+		.unwrap();
+	assert_ok!(ChargeTransactionPayment::<Test>::post_dispatch(
+		Some(pre),
+		&info(),
+		&Default::default(),
+		10,
+		&Ok(())
+	));
+	before - Balances::free_balance(who)
+}
+
+#[test]
+pub fn set_call_status_updates_whitelist() {
+	new_test_ext().execute_with(|| {
+		assert!(!Feeless::is_feeless_call(&transfer()));
+		assert_ok!(Feeless::set_call_status(
+			Origin::root(),
+			b"Balances".to_vec(),
+			b"transfer".to_vec(),
+			true
+		));
+		assert!(Feeless::is_feeless_call(&transfer()));
+		assert_ok!(Feeless::set_call_status(
+			Origin::root(),
+			b"Balances".to_vec(),
+			b"transfer".to_vec(),
+			false
+		));
+		assert!(!Feeless::is_feeless_call(&transfer()));
+	});
+}
+
+#[test]
+pub fn set_call_status_rejects_bad_input() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Feeless::set_call_status(
+				Origin::signed(STAKER),
+				b"Balances".to_vec(),
+				b"transfer".to_vec(),
+				true
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			Feeless::set_call_status(Origin::root(), vec![b'a'; 33], b"transfer".to_vec(), true),
+			Error::<Test>::NameTooLong
+		);
+	});
+}
+
+#[test]
+pub fn eligibility_follows_registration_and_stake() {
+	new_test_ext().execute_with(|| {
+		assert!(Feeless::is_eligible(&STAKER));
+		assert!(Feeless::is_eligible(&REGISTERED));
+		assert!(!Feeless::is_eligible(&OTHER));
+
+		assert_ok!(Feeless::set_registration(Origin::root(), OTHER, true));
+		assert!(Feeless::is_eligible(&OTHER));
+		assert_ok!(Feeless::set_registration(Origin::root(), OTHER, false));
+		assert!(!Feeless::is_eligible(&OTHER));
+
+		assert_ok!(Feeless::set_minimum_stake(Origin::root(), Some(200 * PDEX)));
+		assert!(!Feeless::is_eligible(&STAKER));
+		assert_ok!(Feeless::set_minimum_stake(Origin::root(), None));
+		assert!(!Feeless::is_eligible(&STAKER));
+		assert!(Feeless::is_eligible(&REGISTERED));
+	});
+}
+
+#[test]
+pub fn fees_are_waived_for_feeless_calls() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(pay_fee(STAKER, &remark()), 0);
+		assert_eq!(pay_fee(REGISTERED, &remark()), 0);
+		assert!(pay_fee(OTHER, &remark()) > 0);
+		assert!(pay_fee(STAKER, &transfer()) > 0);
+	});
+}
+
+#[test]
+pub fn quota_limits_feeless_transactions_per_block() {
+	new_test_ext().execute_with(|| {
+		let check = || CheckFeeless::<Test>::new();
+		let quota_exceeded = InvalidTransaction::Custom(FEELESS_QUOTA_EXCEEDED);
+		for _ in 0..MaxFeelessPerBlock::get() {
+			assert_ok!(check().pre_dispatch(&STAKER, &remark(), &info(), 10));
+		}
+		assert_eq!(
+			check().pre_dispatch(&STAKER, &remark(), &info(), 10),
+			Err(quota_exceeded.into())
+		);
+		// Paying transactions and other accounts are not affected.
+		assert_ok!(check().pre_dispatch(&STAKER, &transfer(), &info(), 10));
+		assert_ok!(check().pre_dispatch(&REGISTERED, &remark(), &info(), 10));
+		assert_ok!(check().pre_dispatch(&OTHER, &remark(), &info(), 10));
+
+		// The pool validates for the next block on the state of this one.
+		System::set_block_number(2);
+		assert_eq!(check().validate(&STAKER, &remark(), &info(), 10), Err(quota_exceeded.into()));
+		assert_ok!(check().validate(&STAKER, &transfer(), &info(), 10));
+		assert_ok!(check().validate(&REGISTERED, &remark(), &info(), 10));
+
+		// Initializing the next block clears the counts.
+		Feeless::on_initialize(2);
+		assert_eq!(FeelessCount::<Test>::iter().count(), 0);
+		assert_ok!(check().validate(&STAKER, &remark(), &info(), 10));
+		assert_ok!(check().pre_dispatch(&STAKER, &remark(), &info(), 10));
+		assert_eq!(Feeless::feeless_count(&STAKER), 1);
+	});
+}
+
+#[test]
+pub fn pool_keeps_one_feeless_transaction_per_account() {
+	new_test_ext().execute_with(|| {
+		let check = || CheckFeeless::<Test>::new();
+		// Every transaction validated on the same state is within the quota, but they all provide
+		// the same tag, so the pool only keeps one of them.
+		let validity: Vec<_> = (0..=MaxFeelessPerBlock::get())
+			.map(|_| check().validate(&STAKER, &remark(), &info(), 10).unwrap())
+			.collect();
+		for valid in &validity {
+			assert_eq!(valid.provides, validity[0].provides);
+			assert_eq!(valid.provides.len(), 1);
+			assert_eq!(valid.longevity, FEELESS_LONGEVITY);
+		}
+		let other = check().validate(&REGISTERED, &remark(), &info(), 10).unwrap();
+		assert_ne!(other.provides, validity[0].provides);
+		// Paying transactions are not tagged.
+		assert!(check().validate(&STAKER, &transfer(), &info(), 10).unwrap().provides.is_empty());
+	});
+}
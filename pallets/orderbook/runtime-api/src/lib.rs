@@ -0,0 +1,17 @@
+//! Runtime API definition for the order book pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use orderbook::{OrderBookDepth, TradingPair};
+
+sp_api::decl_runtime_apis! {
+	pub trait OrderbookApi<AssetId, Balance> where
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// Aggregated open amounts of the best `levels` prices on each side of `pair`, or `None`
+		/// if the pair is not registered.
+		fn order_book_depth(pair: TradingPair<AssetId>, levels: u32) -> Option<OrderBookDepth<Balance>>;
+	}
+}
@@ -0,0 +1,141 @@
+//! Benchmarking setup for orderbook
+
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+	assert_ok,
+	traits::{fungibles, Currency, EnsureOrigin, Get},
+};
+use frame_system::RawOrigin;
+use sp_runtime::{traits::Bounded, FixedPointNumber, Permill, SaturatedConversion};
+
+use crate::pallet::{Pallet as Orderbook, *};
+
+use super::*;
+
+const UNIT: u128 = 1000_000_000_000;
+
+fn create_pair<T: Config>(asset: T::AssetId) -> TradingPairOf<T>
+where
+	T::Assets: fungibles::Create<T::AccountId> + fungibles::Mutate<T::AccountId>,
+{
+	let admin: T::AccountId = account("admin", 0, 0);
+	assert_ok!(<T::Assets as fungibles::Create<_>>::create(
+		asset,
+		admin,
+		true,
+		1u128.saturated_into()
+	));
+	let origin = T::GovernanceOrigin::successful_origin();
+	assert_ok!(Orderbook::<T>::register_trading_pair(
+		origin,
+		AssetId::Polkadex,
+		AssetId::Asset(asset),
+		Permill::from_parts(1_000),
+		Permill::from_parts(2_000),
+		1u128.saturated_into()
+	));
+	TradingPair { base: AssetId::Polkadex, quote: AssetId::Asset(asset) }
+}
+
+fn fund<T: Config>(who: &T::AccountId, asset: T::AssetId)
+where
+	T::Assets: fungibles::Mutate<T::AccountId>,
+{
+	T::Currency::make_free_balance_be(who, T::Balance::max_value() / 4u32.into());
+	assert_ok!(<T::Assets as fungibles::Mutate<_>>::mint_into(
+		asset,
+		who,
+		T::Balance::max_value() / 4u32.into()
+	));
+}
+
+fn place<T: Config>(
+	who: &T::AccountId,
+	pair: TradingPairOf<T>,
+	side: OrderSide,
+	price: u128,
+	amount: u128,
+) {
+	assert_ok!(Orderbook::<T>::place_limit_order(
+		RawOrigin::Signed(who.clone()).into(),
+		pair.base,
+		pair.quote,
+		side,
+		Price::saturating_from_integer(price),
+		amount.saturated_into()
+	));
+}
+
+benchmarks! {
+	where_clause {
+		where
+			T::Assets: fungibles::Create<T::AccountId> + fungibles::Mutate<T::AccountId>,
+			T::AssetId: From<u32>,
+	}
+
+	register_trading_pair {
+		let asset: T::AssetId = 100u32.into();
+		let admin: T::AccountId = account("admin", 0, 0);
+		assert_ok!(<T::Assets as fungibles::Create<_>>::create(
+			asset,
+			admin,
+			true,
+			1u128.saturated_into()
+		));
+		let origin = T::GovernanceOrigin::successful_origin();
+	}: _<T::Origin>(
+		origin,
+		AssetId::Polkadex,
+		AssetId::Asset(asset),
+		Permill::from_parts(1_000),
+		Permill::from_parts(2_000),
+		1u128.saturated_into()
+	)
+
+	set_trading_pair_status {
+		let pair = create_pair::<T>(100u32.into());
+		let origin = T::GovernanceOrigin::successful_origin();
+	}: _<T::Origin>(origin, pair.base, pair.quote, false)
+
+	place_limit_order {
+		let asset: T::AssetId = 100u32.into();
+		let pair = create_pair::<T>(asset);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller, asset);
+		let price = Price::saturating_from_integer(2u128);
+		let amount: T::Balance = (10 * UNIT).saturated_into();
+	}: _(RawOrigin::Signed(caller), pair.base, pair.quote, OrderSide::Bid, price, amount)
+
+	cancel_order {
+		let asset: T::AssetId = 100u32.into();
+		let pair = create_pair::<T>(asset);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller, asset);
+		place::<T>(&caller, pair, OrderSide::Bid, 2, 10 * UNIT);
+		Orderbook::<T>::match_pending_orders(1);
+	}: _(RawOrigin::Signed(caller), 0)
+
+	// A single taker order sweeping `f` resting orders.
+	match_orders {
+		let f in 1 .. T::MaxMatchesPerBlock::get();
+		let asset: T::AssetId = 100u32.into();
+		let pair = create_pair::<T>(asset);
+		let seller: T::AccountId = account("seller", 0, 0);
+		let buyer: T::AccountId = whitelisted_caller();
+		fund::<T>(&seller, asset);
+		fund::<T>(&buyer, asset);
+		let levels = T::MaxPriceLevels::get().min(f);
+		for i in 0 .. f {
+			place::<T>(&seller, pair, OrderSide::Ask, 2 + (i % levels) as u128, UNIT);
+			Orderbook::<T>::match_pending_orders(1);
+		}
+		place::<T>(&buyer, pair, OrderSide::Bid, 2 + levels as u128, f as u128 * UNIT);
+	}: {
+		Orderbook::<T>::match_pending_orders(f);
+	}
+	verify {
+		assert!(Orderbook::<T>::pending_orders().is_empty());
+	}
+}
+
+impl_benchmark_test_suite!(Orderbook, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,770 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+//! On-chain limit order book.
+//!
+//! Trading pairs are registered by governance between native PDEX and assets of the assets
+//! pallet. Placing an order reserves the funds it may spend and queues the order; queued orders
+//! are matched against the book with price-time priority in `on_initialize`, performing at most
+//! `MaxMatchesPerBlock` fills per block. Whatever is left of an order after matching rests in the
+//! book until it is filled or cancelled.
+
+use frame_support::weights::Weight;
+pub mod types;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::*;
+
+/// Weight functions needed for orderbook.
+pub trait WeightInfo {
+	fn register_trading_pair() -> Weight;
+	fn set_trading_pair_status() -> Weight;
+	fn place_limit_order() -> Weight;
+	fn cancel_order() -> Weight;
+	fn match_orders(f: u32) -> Weight;
+}
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		storage::with_transaction,
+		traits::{fungibles, BalanceStatus, Currency, NamedReservableCurrency, OnUnbalanced},
+		transactional, PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{AccountIdConversion, AtLeast32BitUnsigned, Saturating, Zero},
+		FixedPointNumber, FixedPointOperand, Permill, TransactionOutcome,
+	};
+
+	use crate::{types::*, WeightInfo};
+
+	/// Identifier of the native reserves held by the order book.
+	pub(crate) const RESERVE_ID: [u8; 8] = *b"orderbok";
+
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+	pub type AssetIdOf<T> = AssetId<<T as Config>::AssetId>;
+	pub type TradingPairOf<T> = TradingPair<<T as Config>::AssetId>;
+	pub type OrderOf<T> = Order<
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::AssetId,
+		<T as Config>::Balance,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub type PriceLevelOf<T> = PriceLevel<<T as Config>::Balance, <T as Config>::MaxOrdersPerLevel>;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Balance type shared by the native currency and the assets.
+		type Balance: Parameter
+			+ Member
+			+ AtLeast32BitUnsigned
+			+ FixedPointOperand
+			+ Default
+			+ Copy
+			+ MaxEncodedLen;
+		/// Native currency, reserved while it backs an open order.
+		type Currency: NamedReservableCurrency<
+			Self::AccountId,
+			Balance = Self::Balance,
+			ReserveIdentifier = [u8; 8],
+		>;
+		/// Identifier of non-native assets.
+		type AssetId: Parameter + Member + Copy + Ord + MaxEncodedLen;
+		/// Non-native assets, held by the pallet account while they back an open order.
+		type Assets: fungibles::Inspect<Self::AccountId, AssetId = Self::AssetId, Balance = Self::Balance>
+			+ fungibles::Transfer<Self::AccountId>;
+		/// Destination of trading fees paid in PDEX.
+		type NativeFeeHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// Account receiving trading fees paid in other assets, once they reach the asset's
+		/// minimum balance.
+		type AssetFeeCollector: Get<Self::AccountId>;
+		/// Origin allowed to register and configure trading pairs.
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+		/// Pallet id used to derive the account holding non-native order funds.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Maximum number of fills executed in a single block.
+		#[pallet::constant]
+		type MaxMatchesPerBlock: Get<u32>;
+		/// Maximum number of orders waiting to be matched.
+		#[pallet::constant]
+		type MaxPendingOrders: Get<u32>;
+		/// Maximum number of distinct prices per side of a trading pair.
+		#[pallet::constant]
+		type MaxPriceLevels: Get<u32>;
+		/// Maximum number of resting orders at a single price.
+		#[pallet::constant]
+		type MaxOrdersPerLevel: Get<u32>;
+		/// Weight Info for the order book
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Registered trading pairs
+	#[pallet::storage]
+	#[pallet::getter(fn trading_pairs)]
+	pub(super) type TradingPairs<T: Config> =
+		StorageMap<_, Blake2_128Concat, TradingPairOf<T>, PairConfig<T::Balance>, OptionQuery>;
+
+	/// Open orders, both queued and resting in the book
+	#[pallet::storage]
+	#[pallet::getter(fn orders)]
+	pub(super) type Orders<T: Config> =
+		StorageMap<_, Blake2_128Concat, OrderId, OrderOf<T>, OptionQuery>;
+
+	/// Identifier given to the next order
+	#[pallet::storage]
+	pub(super) type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// Prices with resting bids, highest first
+	#[pallet::storage]
+	pub(super) type BidPrices<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		TradingPairOf<T>,
+		BoundedVec<Price, T::MaxPriceLevels>,
+		ValueQuery,
+	>;
+
+	/// Prices with resting asks, lowest first
+	#[pallet::storage]
+	pub(super) type AskPrices<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		TradingPairOf<T>,
+		BoundedVec<Price, T::MaxPriceLevels>,
+		ValueQuery,
+	>;
+
+	/// Resting orders per side and price, in time priority
+	#[pallet::storage]
+	pub(super) type PriceLevels<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(TradingPairOf<T>, OrderSide),
+		Blake2_128Concat,
+		Price,
+		PriceLevelOf<T>,
+		ValueQuery,
+	>;
+
+	/// Orders waiting to be matched, oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn pending_orders)]
+	pub(super) type PendingOrders<T: Config> =
+		StorageValue<_, BoundedVec<OrderId, T::MaxPendingOrders>, ValueQuery>;
+
+	/// Trading fees held in the pallet account that were not yet handed to the fee collector
+	#[pallet::storage]
+	#[pallet::getter(fn accrued_fees)]
+	pub(super) type AccruedFees<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, T::Balance, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		TradingPairRegistered {
+			pair: TradingPairOf<T>,
+			maker_fee: Permill,
+			taker_fee: Permill,
+		},
+		TradingPairStatusUpdated {
+			pair: TradingPairOf<T>,
+			enabled: bool,
+		},
+		OrderPlaced {
+			order_id: OrderId,
+			owner: T::AccountId,
+			pair: TradingPairOf<T>,
+			side: OrderSide,
+			price: Price,
+			amount: T::Balance,
+		},
+		OrderCancelled {
+			order_id: OrderId,
+			owner: T::AccountId,
+		},
+		/// Two orders traded `amount` of the base asset at `price`.
+		Trade {
+			pair: TradingPairOf<T>,
+			price: Price,
+			amount: T::Balance,
+			maker_order: OrderId,
+			taker_order: OrderId,
+			maker: T::AccountId,
+			taker: T::AccountId,
+			maker_fee: T::Balance,
+			taker_fee: T::Balance,
+		},
+		OrderFilled {
+			order_id: OrderId,
+		},
+		/// Matching the order failed; it keeps its funds and can be cancelled by its owner.
+		MatchingFailed {
+			order_id: OrderId,
+			error: DispatchError,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Base and quote asset must differ
+		InvalidTradingPair,
+		/// Trading pair is already registered
+		TradingPairAlreadyRegistered,
+		/// Trading pair is not registered
+		UnknownTradingPair,
+		/// Trading pair does not accept new orders
+		TradingPairDisabled,
+		/// Asset does not exist
+		UnknownAsset,
+		/// Price must be greater than zero
+		InvalidPrice,
+		/// Order is smaller than the minimum of the trading pair
+		OrderAmountTooLow,
+		/// Arithmetic overflow
+		Overflow,
+		/// Too many orders are waiting to be matched
+		PendingQueueFull,
+		/// Side of the book has no room for another price
+		TooManyPriceLevels,
+		/// Price level has no room for another order
+		PriceLevelFull,
+		/// Order does not exist
+		UnknownOrder,
+		/// Order belongs to another account
+		NotOrderOwner,
+		/// Funds held for an order do not cover a settlement
+		InsufficientReserve,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			let fills = Self::match_pending_orders(T::MaxMatchesPerBlock::get());
+			T::WeightInfo::match_orders(fills)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Opens a market between `base` and `quote`.
+		#[pallet::weight(<T as Config>::WeightInfo::register_trading_pair())]
+		#[transactional]
+		pub fn register_trading_pair(
+			origin: OriginFor<T>,
+			base: AssetIdOf<T>,
+			quote: AssetIdOf<T>,
+			maker_fee: Permill,
+			taker_fee: Permill,
+			min_order_amount: T::Balance,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(base != quote, Error::<T>::InvalidTradingPair);
+			let pair = TradingPair { base, quote };
+			ensure!(
+				!TradingPairs::<T>::contains_key(&pair),
+				Error::<T>::TradingPairAlreadyRegistered
+			);
+			Self::prepare_escrow(base)?;
+			Self::prepare_escrow(quote)?;
+			TradingPairs::<T>::insert(
+				&pair,
+				PairConfig { maker_fee, taker_fee, min_order_amount, enabled: true },
+			);
+			Self::deposit_event(Event::TradingPairRegistered { pair, maker_fee, taker_fee });
+			Ok(())
+		}
+
+		/// Enables or disables new orders on a trading pair. Open orders are not affected.
+		#[pallet::weight(<T as Config>::WeightInfo::set_trading_pair_status())]
+		pub fn set_trading_pair_status(
+			origin: OriginFor<T>,
+			base: AssetIdOf<T>,
+			quote: AssetIdOf<T>,
+			enabled: bool,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let pair = TradingPair { base, quote };
+			TradingPairs::<T>::try_mutate(&pair, |config| -> DispatchResult {
+				let config = config.as_mut().ok_or(Error::<T>::UnknownTradingPair)?;
+				config.enabled = enabled;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::TradingPairStatusUpdated { pair, enabled });
+			Ok(())
+		}
+
+		/// Places a limit order to buy (`Bid`) or sell (`Ask`) `amount` of the base asset at
+		/// `price` or better. The order is matched at the start of the next block.
+		#[pallet::weight(<T as Config>::WeightInfo::place_limit_order())]
+		#[transactional]
+		pub fn place_limit_order(
+			origin: OriginFor<T>,
+			base: AssetIdOf<T>,
+			quote: AssetIdOf<T>,
+			side: OrderSide,
+			price: Price,
+			amount: T::Balance,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let pair = TradingPair { base, quote };
+			let config = TradingPairs::<T>::get(&pair).ok_or(Error::<T>::UnknownTradingPair)?;
+			ensure!(config.enabled, Error::<T>::TradingPairDisabled);
+			ensure!(!price.is_zero(), Error::<T>::InvalidPrice);
+			ensure!(
+				!amount.is_zero() && amount >= config.min_order_amount,
+				Error::<T>::OrderAmountTooLow
+			);
+
+			let order_id = NextOrderId::<T>::mutate(|next| {
+				let id = *next;
+				*next = next.saturating_add(1);
+				id
+			});
+			PendingOrders::<T>::try_append(order_id).map_err(|_| Error::<T>::PendingQueueFull)?;
+
+			let reserved = match side {
+				OrderSide::Bid => Self::reserve(quote, &owner, Self::quote_amount(price, amount)?)?,
+				OrderSide::Ask => Self::reserve(base, &owner, amount)?,
+			};
+			let order = Order {
+				owner: owner.clone(),
+				pair,
+				side,
+				price,
+				amount,
+				filled: Zero::zero(),
+				reserved,
+				placed_at: frame_system::Pallet::<T>::block_number(),
+			};
+			Orders::<T>::insert(order_id, order);
+			Self::deposit_event(Event::OrderPlaced { order_id, owner, pair, side, price, amount });
+			Ok(())
+		}
+
+		/// Cancels an open order and releases the funds still held for it.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
+		#[transactional]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::UnknownOrder)?;
+			ensure!(order.owner == who, Error::<T>::NotOrderOwner);
+
+			let queued = PendingOrders::<T>::mutate(|queue| {
+				let position = queue.iter().position(|id| *id == order_id);
+				position.map(|index| queue.remove(index)).is_some()
+			});
+			if !queued {
+				Self::remove_from_book(order_id, &order);
+			}
+			Self::release(&order.owner, Self::reserved_asset(&order), order.reserved)?;
+			Orders::<T>::remove(order_id);
+			Self::deposit_event(Event::OrderCancelled { order_id, owner: who });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Account holding non-native assets for open orders.
+		pub fn escrow_account() -> T::AccountId {
+			T::PalletId::get().into_account()
+		}
+
+		/// Aggregated open amounts of the best `levels` prices on each side of `pair`.
+		pub fn order_book_depth(
+			pair: TradingPairOf<T>,
+			levels: u32,
+		) -> Option<OrderBookDepth<T::Balance>> {
+			if !TradingPairs::<T>::contains_key(&pair) {
+				return None;
+			}
+			let depth = |side: OrderSide, prices: BoundedVec<Price, T::MaxPriceLevels>| {
+				prices
+					.into_iter()
+					.take(levels as usize)
+					.map(|price| (price, PriceLevels::<T>::get(&(pair, side), price).total))
+					.collect()
+			};
+			Some(OrderBookDepth {
+				bids: depth(OrderSide::Bid, BidPrices::<T>::get(&pair)),
+				asks: depth(OrderSide::Ask, AskPrices::<T>::get(&pair)),
+			})
+		}
+
+		/// Matches queued orders against the book until the queue is empty or `max_fills` fills
+		/// were executed. An order interrupted by the limit stays at the head of the queue.
+		pub(crate) fn match_pending_orders(max_fills: u32) -> u32 {
+			let mut queue = PendingOrders::<T>::get();
+			let mut fills: u32 = 0;
+			while let Some(order_id) = queue.first().copied() {
+				if fills >= max_fills {
+					break;
+				}
+				let budget = max_fills - fills;
+				let result = with_transaction(|| match Self::match_order(order_id, budget) {
+					Ok(outcome) => TransactionOutcome::Commit(Ok(outcome)),
+					Err(error) => TransactionOutcome::Rollback(Err(error)),
+				});
+				match result {
+					Ok((made, completed)) => {
+						// Orders that only rest in the book still cost a share of the budget.
+						fills = fills.saturating_add(made.max(1));
+						if !completed {
+							break;
+						}
+					},
+					Err(error) => {
+						fills = fills.saturating_add(1);
+						Self::deposit_event(Event::MatchingFailed { order_id, error });
+					},
+				}
+				queue.remove(0);
+			}
+			PendingOrders::<T>::put(queue);
+			fills
+		}
+
+		/// Matches a single queued order, executing at most `budget` fills. Returns the number of
+		/// fills and whether the order finished matching.
+		fn match_order(order_id: OrderId, budget: u32) -> Result<(u32, bool), DispatchError> {
+			let mut taker = Orders::<T>::get(order_id).ok_or(Error::<T>::UnknownOrder)?;
+			let config =
+				TradingPairs::<T>::get(&taker.pair).ok_or(Error::<T>::UnknownTradingPair)?;
+			let maker_side = taker.side.opposite();
+			let key = (taker.pair, maker_side);
+			let mut fills: u32 = 0;
+
+			while !taker.remaining().is_zero() {
+				let best = match Self::best_price(&taker.pair, maker_side) {
+					Some(price) => price,
+					None => break,
+				};
+				let crosses = match taker.side {
+					OrderSide::Bid => best <= taker.price,
+					OrderSide::Ask => best >= taker.price,
+				};
+				if !crosses {
+					break;
+				}
+				if fills >= budget {
+					Orders::<T>::insert(order_id, &taker);
+					return Ok((fills, false));
+				}
+
+				let mut level = PriceLevels::<T>::get(&key, best);
+				let maker_id = *level.orders.first().ok_or(Error::<T>::UnknownOrder)?;
+				let mut maker = Orders::<T>::get(maker_id).ok_or(Error::<T>::UnknownOrder)?;
+				let amount = taker.remaining().min(maker.remaining());
+				Self::settle(
+					&config,
+					(maker_id, &mut maker),
+					(order_id, &mut taker),
+					best,
+					amount,
+				)?;
+
+				level.total = level.total.saturating_sub(amount);
+				if maker.remaining().is_zero() {
+					level.orders.remove(0);
+					Self::close_order(maker_id, &maker)?;
+				} else {
+					Orders::<T>::insert(maker_id, &maker);
+				}
+				if level.orders.is_empty() {
+					PriceLevels::<T>::remove(&key, best);
+					Self::remove_price(&taker.pair, maker_side, best);
+				} else {
+					PriceLevels::<T>::insert(&key, best, level);
+				}
+				fills = fills.saturating_add(1);
+			}
+
+			if taker.remaining().is_zero() {
+				Self::close_order(order_id, &taker)?;
+			} else {
+				Self::insert_into_book(order_id, &taker)?;
+				Orders::<T>::insert(order_id, &taker);
+			}
+			Ok((fills, true))
+		}
+
+		/// Exchanges `amount` of the base asset at `price` between a resting and an incoming
+		/// order. Each side pays its fee out of the asset it receives.
+		fn settle(
+			config: &PairConfig<T::Balance>,
+			maker: (OrderId, &mut OrderOf<T>),
+			taker: (OrderId, &mut OrderOf<T>),
+			price: Price,
+			amount: T::Balance,
+		) -> DispatchResult {
+			let (maker_id, maker) = maker;
+			let (taker_id, taker) = taker;
+			let pair = taker.pair;
+			let quote_amount = Self::quote_amount(price, amount)?;
+
+			let (buyer, seller, buyer_fee_rate, seller_fee_rate) = match taker.side {
+				OrderSide::Bid => (&*taker, &*maker, config.taker_fee, config.maker_fee),
+				OrderSide::Ask => (&*maker, &*taker, config.maker_fee, config.taker_fee),
+			};
+			let base_fee = buyer_fee_rate.mul_floor(amount);
+			let quote_fee = seller_fee_rate.mul_floor(quote_amount);
+			Self::pay_out(pair.base, &seller.owner, &buyer.owner, amount, base_fee)?;
+			Self::pay_out(pair.quote, &buyer.owner, &seller.owner, quote_amount, quote_fee)?;
+
+			let (maker_fee, taker_fee) = match taker.side {
+				OrderSide::Bid => (quote_fee, base_fee),
+				OrderSide::Ask => (base_fee, quote_fee),
+			};
+			for order in [&mut *maker, &mut *taker] {
+				order.filled = order.filled.saturating_add(amount);
+				let spent = match order.side {
+					OrderSide::Bid => quote_amount,
+					OrderSide::Ask => amount,
+				};
+				order.reserved = order.reserved.saturating_sub(spent);
+			}
+
+			Self::deposit_event(Event::Trade {
+				pair,
+				price,
+				amount,
+				maker_order: maker_id,
+				taker_order: taker_id,
+				maker: maker.owner.clone(),
+				taker: taker.owner.clone(),
+				maker_fee,
+				taker_fee,
+			});
+			Ok(())
+		}
+
+		/// Moves `amount` held for `from` to `to`, routing `fee` of it to the fee handlers.
+		fn pay_out(
+			asset: AssetIdOf<T>,
+			from: &T::AccountId,
+			to: &T::AccountId,
+			amount: T::Balance,
+			fee: T::Balance,
+		) -> DispatchResult {
+			let net = amount.saturating_sub(fee);
+			match asset {
+				AssetId::Polkadex => {
+					if !net.is_zero() {
+						let missing = T::Currency::repatriate_reserved_named(
+							&RESERVE_ID,
+							from,
+							to,
+							net,
+							BalanceStatus::Free,
+						)?;
+						ensure!(missing.is_zero(), Error::<T>::InsufficientReserve);
+					}
+					if !fee.is_zero() {
+						let (imbalance, missing) =
+							T::Currency::slash_reserved_named(&RESERVE_ID, from, fee);
+						ensure!(missing.is_zero(), Error::<T>::InsufficientReserve);
+						T::NativeFeeHandler::on_unbalanced(imbalance);
+					}
+				},
+				AssetId::Asset(id) => {
+					AccruedFees::<T>::mutate(id, |accrued| *accrued = accrued.saturating_add(fee));
+					if !net.is_zero() {
+						Self::pay_from_escrow(id, to, net)?;
+					}
+					Self::sweep_fees(id);
+				},
+			}
+			Ok(())
+		}
+
+		/// Holds `amount` of `asset` for an order of `who`. Returns the amount actually held,
+		/// which includes any dust moved along when `who` empties an asset account.
+		fn reserve(
+			asset: AssetIdOf<T>,
+			who: &T::AccountId,
+			amount: T::Balance,
+		) -> Result<T::Balance, DispatchError> {
+			match asset {
+				AssetId::Polkadex => {
+					T::Currency::reserve_named(&RESERVE_ID, who, amount)?;
+					Ok(amount)
+				},
+				AssetId::Asset(id) => <T::Assets as fungibles::Transfer<_>>::transfer(
+					id,
+					who,
+					&Self::escrow_account(),
+					amount,
+					false,
+				),
+			}
+		}
+
+		/// Returns funds held for an order to its owner.
+		fn release(who: &T::AccountId, asset: AssetIdOf<T>, amount: T::Balance) -> DispatchResult {
+			if amount.is_zero() {
+				return Ok(());
+			}
+			match asset {
+				AssetId::Polkadex => {
+					T::Currency::unreserve_named(&RESERVE_ID, who, amount);
+				},
+				AssetId::Asset(id) => Self::pay_from_escrow(id, who, amount)?,
+			}
+			Ok(())
+		}
+
+		/// Pays `amount` of asset `id` out of the pallet account. The account is kept alive while
+		/// it holds funds of open orders; fee dust that would be left behind goes along instead.
+		fn pay_from_escrow(
+			id: T::AssetId,
+			to: &T::AccountId,
+			amount: T::Balance,
+		) -> DispatchResult {
+			let escrow = Self::escrow_account();
+			let accrued = AccruedFees::<T>::get(id);
+			let rest = <T::Assets as fungibles::Inspect<_>>::balance(id, &escrow)
+				.saturating_sub(amount);
+			let min_balance = <T::Assets as fungibles::Inspect<_>>::minimum_balance(id);
+			let keep_alive = rest >= min_balance || rest > accrued;
+			let paid = <T::Assets as fungibles::Transfer<_>>::transfer(
+				id, &escrow, to, amount, keep_alive,
+			)?;
+			let dust = paid.saturating_sub(amount);
+			AccruedFees::<T>::mutate(id, |accrued| *accrued = accrued.saturating_sub(dust));
+			Ok(())
+		}
+
+		/// Hands the fees accrued in asset `id` to the fee collector once they reach the asset's
+		/// minimum balance. Smaller amounts, or fees the collector cannot receive yet, wait for
+		/// later trades.
+		fn sweep_fees(id: T::AssetId) {
+			let accrued = AccruedFees::<T>::get(id);
+			if accrued.is_zero() ||
+				accrued < <T::Assets as fungibles::Inspect<_>>::minimum_balance(id)
+			{
+				return
+			}
+			with_transaction(|| {
+				AccruedFees::<T>::remove(id);
+				match Self::pay_from_escrow(id, &T::AssetFeeCollector::get(), accrued) {
+					Ok(()) => TransactionOutcome::Commit(()),
+					Err(_) => TransactionOutcome::Rollback(()),
+				}
+			});
+		}
+
+		/// Removes a fully traded order, returning any price improvement left in its reserve.
+		fn close_order(order_id: OrderId, order: &OrderOf<T>) -> DispatchResult {
+			Self::release(&order.owner, Self::reserved_asset(order), order.reserved)?;
+			Orders::<T>::remove(order_id);
+			Self::deposit_event(Event::OrderFilled { order_id });
+			Ok(())
+		}
+
+		/// Makes sure the pallet account can hold `asset`. The account is made a provider of
+		/// itself, so it can receive assets that are not sufficient without holding native funds.
+		fn prepare_escrow(asset: AssetIdOf<T>) -> DispatchResult {
+			let id = match asset {
+				AssetId::Polkadex => return Ok(()),
+				AssetId::Asset(id) => id,
+			};
+			let min_balance = <T::Assets as fungibles::Inspect<_>>::minimum_balance(id);
+			ensure!(!min_balance.is_zero(), Error::<T>::UnknownAsset);
+			let escrow = Self::escrow_account();
+			if frame_system::Pallet::<T>::providers(&escrow).is_zero() {
+				frame_system::Pallet::<T>::inc_providers(&escrow);
+			}
+			Ok(())
+		}
+
+		fn reserved_asset(order: &OrderOf<T>) -> AssetIdOf<T> {
+			match order.side {
+				OrderSide::Bid => order.pair.quote,
+				OrderSide::Ask => order.pair.base,
+			}
+		}
+
+		fn quote_amount(price: Price, amount: T::Balance) -> Result<T::Balance, DispatchError> {
+			price.checked_mul_int(amount).ok_or_else(|| Error::<T>::Overflow.into())
+		}
+
+		fn best_price(pair: &TradingPairOf<T>, side: OrderSide) -> Option<Price> {
+			match side {
+				OrderSide::Bid => BidPrices::<T>::get(pair).first().copied(),
+				OrderSide::Ask => AskPrices::<T>::get(pair).first().copied(),
+			}
+		}
+
+		fn insert_into_book(order_id: OrderId, order: &OrderOf<T>) -> DispatchResult {
+			let key = (order.pair, order.side);
+			let mut level = PriceLevels::<T>::get(&key, order.price);
+			if level.orders.is_empty() {
+				Self::insert_price(&order.pair, order.side, order.price)?;
+			}
+			level.orders.try_push(order_id).map_err(|_| Error::<T>::PriceLevelFull)?;
+			level.total = level.total.saturating_add(order.remaining());
+			PriceLevels::<T>::insert(&key, order.price, level);
+			Ok(())
+		}
+
+		fn remove_from_book(order_id: OrderId, order: &OrderOf<T>) {
+			let key = (order.pair, order.side);
+			let mut level = PriceLevels::<T>::get(&key, order.price);
+			level.orders.retain(|id| *id != order_id);
+			level.total = level.total.saturating_sub(order.remaining());
+			if level.orders.is_empty() {
+				PriceLevels::<T>::remove(&key, order.price);
+				Self::remove_price(&order.pair, order.side, order.price);
+			} else {
+				PriceLevels::<T>::insert(&key, order.price, level);
+			}
+		}
+
+		/// Adds a price to one side of the book, keeping the best price first.
+		fn insert_price(pair: &TradingPairOf<T>, side: OrderSide, price: Price) -> DispatchResult {
+			let insert = |prices: &mut BoundedVec<Price, T::MaxPriceLevels>| -> DispatchResult {
+				let position = match side {
+					OrderSide::Bid => prices.iter().position(|p| *p < price),
+					OrderSide::Ask => prices.iter().position(|p| *p > price),
+				}
+				.unwrap_or_else(|| prices.len());
+				prices
+					.try_insert(position, price)
+					.map_err(|_| Error::<T>::TooManyPriceLevels.into())
+			};
+			match side {
+				OrderSide::Bid => BidPrices::<T>::try_mutate(pair, insert),
+				OrderSide::Ask => AskPrices::<T>::try_mutate(pair, insert),
+			}
+		}
+
+		fn remove_price(pair: &TradingPairOf<T>, side: OrderSide, price: Price) {
+			let remove =
+				|prices: &mut BoundedVec<Price, T::MaxPriceLevels>| prices.retain(|p| *p != price);
+			match side {
+				OrderSide::Bid => BidPrices::<T>::mutate(pair, remove),
+				OrderSide::Ask => AskPrices::<T>::mutate(pair, remove),
+			}
+		}
+	}
+}
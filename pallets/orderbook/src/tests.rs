@@ -0,0 +1,306 @@
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_runtime::{
+	traits::{BadOrigin, Zero},
+	FixedPointNumber, Permill,
+};
+
+use crate::mock::{
+	new_test_ext, Assets, Balances, Orderbook, Origin, System, Test, BTC, DOT, FEE_COLLECTOR, PDEX,
+	USDT,
+};
+
+use crate::pallet::*;
+use crate::types::*;
+
+const PAIR: TradingPair<u128> =
+	TradingPair { base: AssetId::Polkadex, quote: AssetId::Asset(USDT) };
+
+fn price(value: u128) -> Price {
+	Price::saturating_from_integer(value)
+}
+
+fn register_pair() {
+	assert_ok!(Orderbook::register_trading_pair(
+		Origin::root(),
+		AssetId::Polkadex,
+		AssetId::Asset(USDT),
+		Permill::from_parts(1_000),
+		Permill::from_parts(2_000),
+		1
+	));
+}
+
+fn place(who: u64, side: OrderSide, price: Price, amount: u128) {
+	assert_ok!(Orderbook::place_limit_order(
+		Origin::signed(who),
+		AssetId::Polkadex,
+		AssetId::Asset(USDT),
+		side,
+		price,
+		amount
+	));
+}
+
+fn next_block() {
+	System::set_block_number(System::block_number() + 1);
+	Orderbook::on_initialize(System::block_number());
+}
+
+#[test]
+pub fn register_trading_pair_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Orderbook::register_trading_pair(
+				Origin::signed(1),
+				AssetId::Polkadex,
+				AssetId::Asset(USDT),
+				Permill::zero(),
+				Permill::zero(),
+				1
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			Orderbook::register_trading_pair(
+				Origin::root(),
+				AssetId::Asset(USDT),
+				AssetId::Asset(USDT),
+				Permill::zero(),
+				Permill::zero(),
+				1
+			),
+			Error::<Test>::InvalidTradingPair
+		);
+		assert_noop!(
+			Orderbook::register_trading_pair(
+				Origin::root(),
+				AssetId::Polkadex,
+				AssetId::Asset(42),
+				Permill::zero(),
+				Permill::zero(),
+				1
+			),
+			Error::<Test>::UnknownAsset
+		);
+		register_pair();
+		assert!(Orderbook::trading_pairs(PAIR).unwrap().enabled);
+		assert_eq!(Assets::balance(USDT, Orderbook::escrow_account()), 0);
+		assert_eq!(Assets::total_supply(USDT), 30_000 * PDEX);
+		assert_noop!(
+			Orderbook::register_trading_pair(
+				Origin::root(),
+				AssetId::Polkadex,
+				AssetId::Asset(USDT),
+				Permill::zero(),
+				Permill::zero(),
+				1
+			),
+			Error::<Test>::TradingPairAlreadyRegistered
+		);
+	});
+}
+
+#[test]
+pub fn place_limit_order_holds_funds() {
+	new_test_ext().execute_with(|| {
+		register_pair();
+		place(1, OrderSide::Ask, price(2), 10 * PDEX);
+		place(2, OrderSide::Bid, price(1), 10 * PDEX);
+		assert_eq!(Balances::reserved_balance(1), 10 * PDEX);
+		assert_eq!(Assets::balance(USDT, 2), 9_990 * PDEX);
+		assert_eq!(Assets::balance(USDT, Orderbook::escrow_account()), 10 * PDEX);
+		assert_eq!(Orderbook::pending_orders().into_inner(), vec![0, 1]);
+		assert_noop!(
+			Orderbook::place_limit_order(
+				Origin::signed(1),
+				AssetId::Polkadex,
+				AssetId::Asset(USDT),
+				OrderSide::Ask,
+				Price::zero(),
+				PDEX
+			),
+			Error::<Test>::InvalidPrice
+		);
+		assert_noop!(
+			Orderbook::place_limit_order(
+				Origin::signed(1),
+				AssetId::Polkadex,
+				AssetId::Asset(BTC),
+				OrderSide::Ask,
+				price(2),
+				PDEX
+			),
+			Error::<Test>::UnknownTradingPair
+		);
+	});
+}
+
+#[test]
+pub fn disabled_pair_rejects_orders() {
+	new_test_ext().execute_with(|| {
+		register_pair();
+		assert_ok!(Orderbook::set_trading_pair_status(
+			Origin::root(),
+			AssetId::Polkadex,
+			AssetId::Asset(USDT),
+			false
+		));
+		assert_noop!(
+			Orderbook::place_limit_order(
+				Origin::signed(1),
+				AssetId::Polkadex,
+				AssetId::Asset(USDT),
+				OrderSide::Ask,
+				price(2),
+				PDEX
+			),
+			Error::<Test>::TradingPairDisabled
+		);
+	});
+}
+
+#[test]
+pub fn crossing_orders_trade_at_maker_price() {
+	new_test_ext().execute_with(|| {
+		register_pair();
+		place(1, OrderSide::Ask, price(2), 10 * PDEX);
+		next_block();
+		place(2, OrderSide::Bid, price(3), 10 * PDEX);
+		next_block();
+
+		// Seller receives 20 USDT minus the 0.1% maker fee.
+		assert_eq!(Balances::free_balance(1), 990 * PDEX);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Assets::balance(USDT, 1), 10_019_980_000_000_000);
+		// Buyer receives 10 PDEX minus the 0.2% taker fee and gets the price improvement back.
+		assert_eq!(Balances::free_balance(2), 1_009_980_000_000_000);
+		assert_eq!(Assets::balance(USDT, 2), 9_980 * PDEX);
+		assert_eq!(Assets::balance(USDT, FEE_COLLECTOR), 20_000_000_000);
+		assert_eq!(Assets::balance(USDT, Orderbook::escrow_account()), 0);
+		assert_eq!(Orderbook::accrued_fees(USDT), 0);
+		assert_eq!(Orderbook::orders(0), None);
+		assert_eq!(Orderbook::orders(1), None);
+		assert!(Orderbook::pending_orders().is_empty());
+		System::assert_has_event(
+			Event::<Test>::Trade {
+				pair: PAIR,
+				price: price(2),
+				amount: 10 * PDEX,
+				maker_order: 0,
+				taker_order: 1,
+				maker: 1,
+				taker: 2,
+				maker_fee: 20_000_000_000,
+				taker_fee: 20_000_000_000,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+pub fn partially_filled_order_rests_in_book() {
+	new_test_ext().execute_with(|| {
+		register_pair();
+		place(1, OrderSide::Ask, price(2), 10 * PDEX);
+		place(2, OrderSide::Bid, price(2), 4 * PDEX);
+		place(3, OrderSide::Bid, price(1), 5 * PDEX);
+		next_block();
+
+		let ask = Orderbook::orders(0).unwrap();
+		assert_eq!(ask.remaining(), 6 * PDEX);
+		assert_eq!(Balances::reserved_balance(1), 6 * PDEX);
+		assert_eq!(Orderbook::orders(1), None);
+		assert_eq!(
+			Orderbook::order_book_depth(PAIR, 10),
+			Some(OrderBookDepth {
+				bids: vec![(price(1), 5 * PDEX)],
+				asks: vec![(price(2), 6 * PDEX)]
+			})
+		);
+		assert_eq!(Orderbook::order_book_depth(PAIR, 0), Some(OrderBookDepth::default()));
+	});
+}
+
+#[test]
+pub fn matching_is_limited_per_block() {
+	new_test_ext().execute_with(|| {
+		register_pair();
+		for ask_price in [2, 2, 2, 3, 3] {
+			place(1, OrderSide::Ask, price(ask_price), PDEX);
+		}
+		next_block();
+		next_block();
+		place(2, OrderSide::Bid, price(3), 5 * PDEX);
+		next_block();
+
+		// Four fills were executed, the bid waits at the head of the queue for the last one.
+		assert_eq!(Orderbook::pending_orders().into_inner(), vec![5]);
+		assert_eq!(Orderbook::orders(5).unwrap().filled, 4 * PDEX);
+		next_block();
+		assert!(Orderbook::pending_orders().is_empty());
+		assert_eq!(Orderbook::orders(5), None);
+		assert_eq!(Orderbook::order_book_depth(PAIR, 10), Some(OrderBookDepth::default()));
+	});
+}
+
+#[test]
+pub fn cancel_order_releases_funds() {
+	new_test_ext().execute_with(|| {
+		register_pair();
+		place(1, OrderSide::Ask, price(2), 10 * PDEX);
+		place(2, OrderSide::Bid, price(1), 10 * PDEX);
+		next_block();
+		assert_noop!(Orderbook::cancel_order(Origin::signed(1), 1), Error::<Test>::NotOrderOwner);
+		assert_noop!(Orderbook::cancel_order(Origin::signed(1), 7), Error::<Test>::UnknownOrder);
+
+		assert_ok!(Orderbook::cancel_order(Origin::signed(1), 0));
+		assert_ok!(Orderbook::cancel_order(Origin::signed(2), 1));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Assets::balance(USDT, 2), 10_000 * PDEX);
+		assert_eq!(Orderbook::order_book_depth(PAIR, 10), Some(OrderBookDepth::default()));
+	});
+}
+
+#[test]
+pub fn fees_below_min_balance_accrue_in_escrow() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Orderbook::register_trading_pair(
+			Origin::root(),
+			AssetId::Polkadex,
+			AssetId::Asset(DOT),
+			Permill::from_parts(1_000),
+			Permill::from_parts(2_000),
+			1
+		));
+		let place = |who: u64, side: OrderSide, price: Price, amount: u128| {
+			assert_ok!(Orderbook::place_limit_order(
+				Origin::signed(who),
+				AssetId::Polkadex,
+				AssetId::Asset(DOT),
+				side,
+				price,
+				amount
+			));
+		};
+		place(1, OrderSide::Ask, price(2), 10 * PDEX);
+		place(2, OrderSide::Bid, price(1), 10 * PDEX);
+		next_block();
+		place(2, OrderSide::Bid, price(2), 10 * PDEX);
+		next_block();
+
+		// The 0.02 DOT maker fee is below the minimum balance of DOT and stays in the escrow.
+		assert_eq!(Orderbook::orders(2), None);
+		assert_eq!(Assets::balance(DOT, 1), 19_980_000_000_000);
+		assert_eq!(Assets::balance(DOT, FEE_COLLECTOR), 0);
+		assert_eq!(Orderbook::accrued_fees(DOT), 20_000_000_000);
+		assert_eq!(Assets::balance(DOT, Orderbook::escrow_account()), 10_020_000_000_000);
+
+		// Releasing the last order funds hands the fee dust along instead of failing.
+		assert_ok!(Orderbook::cancel_order(Origin::signed(2), 1));
+		assert_eq!(Assets::balance(DOT, 2), 80_020_000_000_000);
+		assert_eq!(Orderbook::accrued_fees(DOT), 0);
+		assert_eq!(Assets::balance(DOT, Orderbook::escrow_account()), 0);
+		assert_eq!(Assets::total_supply(DOT), 100 * PDEX);
+	});
+}
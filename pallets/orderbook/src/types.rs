@@ -0,0 +1,128 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::Get, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Saturating, FixedU128, Permill};
+use sp_std::vec::Vec;
+
+/// Price of one unit of the base asset expressed in units of the quote asset.
+pub type Price = FixedU128;
+
+/// Identifier of an order, assigned sequentially.
+pub type OrderId = u64;
+
+/// An asset that can be traded on the order book.
+#[derive(
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum AssetId<Id> {
+	/// Native PDEX held in `Balances`.
+	Polkadex,
+	/// An asset registered with the assets pallet.
+	Asset(Id),
+}
+
+/// A market in which the base asset is bought and sold for the quote asset.
+#[derive(
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct TradingPair<Id> {
+	pub base: AssetId<Id>,
+	pub quote: AssetId<Id>,
+}
+
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum OrderSide {
+	/// Buy the base asset.
+	Bid,
+	/// Sell the base asset.
+	Ask,
+}
+
+impl OrderSide {
+	pub fn opposite(&self) -> Self {
+		match self {
+			OrderSide::Bid => OrderSide::Ask,
+			OrderSide::Ask => OrderSide::Bid,
+		}
+	}
+}
+
+/// Governance controlled parameters of a trading pair.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PairConfig<Balance> {
+	/// Fee paid by the resting order, taken from the asset it receives.
+	pub maker_fee: Permill,
+	/// Fee paid by the incoming order, taken from the asset it receives.
+	pub taker_fee: Permill,
+	/// Smallest order, in units of the base asset.
+	pub min_order_amount: Balance,
+	/// New orders are only accepted while the pair is enabled.
+	pub enabled: bool,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Order<AccountId, Id, Balance, BlockNumber> {
+	pub owner: AccountId,
+	pub pair: TradingPair<Id>,
+	pub side: OrderSide,
+	pub price: Price,
+	/// Amount of the base asset to buy or sell.
+	pub amount: Balance,
+	/// Amount of the base asset traded so far.
+	pub filled: Balance,
+	/// Funds still held for the order: quote asset for bids, base asset for asks.
+	pub reserved: Balance,
+	pub placed_at: BlockNumber,
+}
+
+impl<AccountId, Id, Balance: Saturating + Copy, BlockNumber>
+	Order<AccountId, Id, Balance, BlockNumber>
+{
+	/// Amount of the base asset that is still open.
+	pub fn remaining(&self) -> Balance {
+		self.amount.saturating_sub(self.filled)
+	}
+}
+
+/// Orders resting at a single price, oldest first.
+#[derive(Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxOrders))]
+#[codec(mel_bound(Balance: MaxEncodedLen))]
+pub struct PriceLevel<Balance, MaxOrders: Get<u32>> {
+	pub orders: BoundedVec<OrderId, MaxOrders>,
+	/// Sum of the open base amount of all orders at this level.
+	pub total: Balance,
+}
+
+impl<Balance: Default, MaxOrders: Get<u32>> Default for PriceLevel<Balance, MaxOrders> {
+	fn default() -> Self {
+		Self { orders: BoundedVec::default(), total: Balance::default() }
+	}
+}
+
+/// Aggregated open amount per price level, best price first.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct OrderBookDepth<Balance> {
+	pub bids: Vec<(Price, Balance)>,
+	pub asks: Vec<(Price, Balance)>,
+}
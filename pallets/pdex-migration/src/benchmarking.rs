@@ -21,11 +21,11 @@ pub const PDEX: u128 = 1000_000_000_000;
 benchmarks! {
 	set_migration_operational_status {
 
-	}: _(RawOrigin::Root, true)
+	}: _(T::AdminOrigin::successful_origin(), true)
 
 	set_relayer_status {
 		let relayer : T::AccountId = account("relayer",0,0);
-	}: _ (RawOrigin::Root, relayer, true)
+	}: _ (T::AdminOrigin::successful_origin(), relayer, true)
 
 	mint {
 		let relayer1: T::AccountId = account("relayer1",0,0);
@@ -40,11 +40,11 @@ benchmarks! {
 		eth_hash.as_mut().copy_from_slice(&random_slice);
 
 
-			assert_ok!(PDEXMigration::<T>::set_migration_operational_status(RawOrigin::Root.into(),true));
+			assert_ok!(PDEXMigration::<T>::set_migration_operational_status(T::AdminOrigin::successful_origin(),true));
 			// Register relayers
-			assert_ok!(PDEXMigration::<T>::set_relayer_status(RawOrigin::Root.into(),relayer1.clone(),true));
-			assert_ok!(PDEXMigration::<T>::set_relayer_status(RawOrigin::Root.into(),relayer2.clone(),true));
-			assert_ok!(PDEXMigration::<T>::set_relayer_status(RawOrigin::Root.into(),relayer3.clone(),true));
+			assert_ok!(PDEXMigration::<T>::set_relayer_status(T::AdminOrigin::successful_origin(),relayer1.clone(),true));
+			assert_ok!(PDEXMigration::<T>::set_relayer_status(T::AdminOrigin::successful_origin(),relayer2.clone(),true));
+			assert_ok!(PDEXMigration::<T>::set_relayer_status(T::AdminOrigin::successful_origin(),relayer3.clone(),true));
 
 			assert_ok!(PDEXMigration::<T>::mint(RawOrigin::Signed(relayer1).into(), beneficiary.clone(),amount,eth_hash));
 			assert_ok!(PDEXMigration::<T>::mint(RawOrigin::Signed(relayer2).into(), beneficiary.clone(),amount,eth_hash));
@@ -65,11 +65,11 @@ benchmarks! {
 		let mut eth_hash: T::Hash = T::Hash::default();
 		eth_hash.as_mut().copy_from_slice(&random_slice);
 
-		   assert_ok!(PDEXMigration::<T>::set_migration_operational_status(RawOrigin::Root.into(),true));
+		   assert_ok!(PDEXMigration::<T>::set_migration_operational_status(T::AdminOrigin::successful_origin(),true));
 			// Register relayers
-			assert_ok!(PDEXMigration::<T>::set_relayer_status(RawOrigin::Root.into(),relayer1.clone(),true));
-			assert_ok!(PDEXMigration::<T>::set_relayer_status(RawOrigin::Root.into(),relayer2.clone(),true));
-			assert_ok!(PDEXMigration::<T>::set_relayer_status(RawOrigin::Root.into(),relayer3.clone(),true));
+			assert_ok!(PDEXMigration::<T>::set_relayer_status(T::AdminOrigin::successful_origin(),relayer1.clone(),true));
+			assert_ok!(PDEXMigration::<T>::set_relayer_status(T::AdminOrigin::successful_origin(),relayer2.clone(),true));
+			assert_ok!(PDEXMigration::<T>::set_relayer_status(T::AdminOrigin::successful_origin(),relayer3.clone(),true));
 
 		  assert_ok!(PDEXMigration::<T>::mint(RawOrigin::Signed(relayer1).into(), beneficiary.clone(),amount,eth_hash));
 			assert_ok!(PDEXMigration::<T>::mint(RawOrigin::Signed(relayer2).into(), beneficiary.clone(),amount,eth_hash));
@@ -91,17 +91,17 @@ benchmarks! {
 		let mut eth_hash: T::Hash = T::Hash::default();
 		eth_hash.as_mut().copy_from_slice(&random_slice);
 
-		   assert_ok!(PDEXMigration::<T>::set_migration_operational_status(RawOrigin::Root.into(),true));
+		   assert_ok!(PDEXMigration::<T>::set_migration_operational_status(T::AdminOrigin::successful_origin(),true));
 			// Register relayers
-			assert_ok!(PDEXMigration::<T>::set_relayer_status(RawOrigin::Root.into(),relayer1.clone(),true));
-			assert_ok!(PDEXMigration::<T>::set_relayer_status(RawOrigin::Root.into(),relayer2.clone(),true));
-			assert_ok!(PDEXMigration::<T>::set_relayer_status(RawOrigin::Root.into(),relayer3.clone(),true));
+			assert_ok!(PDEXMigration::<T>::set_relayer_status(T::AdminOrigin::successful_origin(),relayer1.clone(),true));
+			assert_ok!(PDEXMigration::<T>::set_relayer_status(T::AdminOrigin::successful_origin(),relayer2.clone(),true));
+			assert_ok!(PDEXMigration::<T>::set_relayer_status(T::AdminOrigin::successful_origin(),relayer3.clone(),true));
 
 		  assert_ok!(PDEXMigration::<T>::mint(RawOrigin::Signed(relayer1).into(), beneficiary.clone(),amount,eth_hash));
 			assert_ok!(PDEXMigration::<T>::mint(RawOrigin::Signed(relayer2).into(), beneficiary.clone(),amount,eth_hash));
 			assert_ok!(PDEXMigration::<T>::mint(RawOrigin::Signed(relayer3).into(), beneficiary.clone(),amount,eth_hash));
 
-	}: _(RawOrigin::Root,beneficiary)
+	}: _(T::AdminOrigin::successful_origin(),beneficiary)
 }
 #[cfg(test)]
 mod tests {
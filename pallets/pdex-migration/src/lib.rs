@@ -57,7 +57,7 @@ pub mod pallet {
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	/// Configure the pallet by specifying the parameters and types on which it depends.
-	pub trait Config: frame_system::Config + pallet_balances::Config + pallet_sudo::Config {
+	pub trait Config: frame_system::Config + pallet_balances::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		/// Max Number of relayers
@@ -66,6 +66,8 @@ pub mod pallet {
 		/// Lock Period
 		#[pallet::constant]
 		type LockPeriod: Get<<Self as frame_system::Config>::BlockNumber>;
+		/// Origin allowed to manage relayers, the operational status and fraudulent mints
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
 		/// Weight Info for PDEX migration
 		type WeightInfo: WeightInfo;
 	}
@@ -177,7 +179,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			status: bool,
 		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
+			T::AdminOrigin::ensure_origin(origin)?;
 			Operational::<T>::put(status);
 			Ok(Pays::No.into())
 		}
@@ -188,7 +190,7 @@ pub mod pallet {
 			relayer: T::AccountId,
 			status: bool,
 		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
+			T::AdminOrigin::ensure_origin(origin)?;
 			Relayers::<T>::insert(&relayer, status);
 			Self::deposit_event(Event::RelayerStatusUpdated(relayer, status));
 			Ok(Pays::No.into())
@@ -231,7 +233,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			beneficiary: T::AccountId,
 		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
+			T::AdminOrigin::ensure_origin(origin)?;
 			Self::remove_fradulent_tokens(beneficiary)?;
 			Ok(Pays::No.into())
 		}
@@ -1,5 +1,6 @@
-use frame_support::parameter_types;
+use frame_support::{ord_parameter_types, parameter_types, traits::EnsureOneOf};
 use frame_system as system;
+use frame_system::{EnsureRoot, EnsureSignedBy};
 use sp_core::H256;
 // use sp_runtime::testing::H256;
 // use crate::benchmarking::H256;
@@ -23,7 +24,6 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
 		PDEXMigration: pdex_migration::{Pallet, Call, Config<T>, Storage, Event<T>},
 	}
@@ -80,28 +80,24 @@ parameter_types! {
 	pub const LockPeriod: u64 = 201600;
 	pub const MaxRelayers: u32 = 3;
 }
+ord_parameter_types! {
+	pub const Admin: u64 = 1;
+}
 
 impl pdex_migration::Config for Test {
 	type Event = Event;
 	type MaxRelayers = MaxRelayers;
 	type LockPeriod = LockPeriod;
+	type AdminOrigin = EnsureOneOf<EnsureRoot<u64>, EnsureSignedBy<Admin, u64>>;
 	type WeightInfo = crate::weights::WeightInfo<Test>;
 }
-impl pallet_sudo::Config for Test {
-	type Event = Event;
-	type Call = Call;
-}
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let alice = 1u64;
 	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
 	pallet_balances::GenesisConfig::<Test>::default()
 		.assimilate_storage(&mut t)
 		.unwrap();
-	pallet_sudo::GenesisConfig::<Test> { key: Some(alice) }
-		.assimilate_storage(&mut t)
-		.unwrap();
 	pdex_migration::GenesisConfig::<Test>::default()
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -29,6 +29,22 @@ pub fn set_migration_operational_status_works() {
 	});
 }
 
+#[test]
+pub fn admin_origin_manages_migration() {
+	new_test_ext().execute_with(|| {
+		let admin = 1u64;
+		let relayer = 2u64;
+		assert_ok!(PDEXMigration::set_migration_operational_status(Origin::signed(admin), true));
+		assert_eq!(PDEXMigration::operational(), true);
+		assert_ok!(PDEXMigration::set_relayer_status(Origin::signed(admin), relayer, true));
+		assert_eq!(Relayers::<Test>::get(&relayer), true);
+		assert_noop!(
+			PDEXMigration::set_relayer_status(Origin::signed(relayer), relayer, false),
+			BadOrigin,
+		);
+	});
+}
+
 #[test]
 pub fn set_relayer_status_works() {
 	new_test_ext().execute_with(|| {
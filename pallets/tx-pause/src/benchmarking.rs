@@ -0,0 +1,31 @@
+//! Benchmarking setup for tx-pause
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::{
+	assert_ok,
+	traits::{EnsureOrigin, Get},
+};
+use sp_std::vec;
+
+use crate::pallet::{Pallet as TxPause, *};
+
+use super::*;
+
+benchmarks! {
+	pause_call {
+		let origin = T::PauseOrigin::successful_origin();
+		let limit = T::MaxNameLength::get() as usize;
+	}: _<T::Origin>(origin, vec![b'p'; limit], vec![b'c'; limit])
+
+	unpause_call {
+		let limit = T::MaxNameLength::get() as usize;
+		assert_ok!(TxPause::<T>::pause_call(
+			T::PauseOrigin::successful_origin(),
+			vec![b'p'; limit],
+			vec![b'c'; limit]
+		));
+		let origin = T::UnpauseOrigin::successful_origin();
+	}: _<T::Origin>(origin, vec![b'p'; limit], vec![b'c'; limit])
+}
+
+impl_benchmark_test_suite!(TxPause, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,171 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+//! Emergency pause of calls.
+//!
+//! The technical committee can pause individual calls, by pallet and call name, to stop a
+//! misbehaving pallet without a runtime upgrade. Paused calls are rejected by the runtime's
+//! `BaseCallFilter`, which also applies to calls nested in batches and proxies, as well as to
+//! inherents and unsigned calls. Pallets needed to produce blocks or to govern the chain are
+//! configured as unpausable.
+
+use frame_support::weights::Weight;
+pub mod weights;
+
+pub use pallet::*;
+
+/// Weight functions needed for tx_pause.
+pub trait WeightInfo {
+	fn pause_call() -> Weight;
+	fn unpause_call() -> Weight;
+}
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Contains, GetCallMetadata},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_std::vec::Vec;
+
+	use crate::WeightInfo;
+
+	pub type NameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Origin allowed to pause calls.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+		/// Origin allowed to unpause calls.
+		type UnpauseOrigin: EnsureOrigin<Self::Origin>;
+		/// Pallets whose calls can not be paused.
+		type UnpausablePallets: Contains<NameOf<Self>>;
+		/// Maximum length of a pallet or call name.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+		/// Weight Info for tx pause
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Paused calls, by pallet and call name
+	#[pallet::storage]
+	pub(super) type PausedCalls<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		NameOf<T>,
+		Blake2_128Concat,
+		NameOf<T>,
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		CallPaused { pallet_name: Vec<u8>, call_name: Vec<u8> },
+		CallUnpaused { pallet_name: Vec<u8>, call_name: Vec<u8> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Pallet or call name is longer than the name length limit
+		NameTooLong,
+		/// Calls of the pallet can not be paused
+		Unpausable,
+		/// Call is already paused
+		AlreadyPaused,
+		/// Call is not paused
+		NotPaused,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pauses a call until it is unpaused.
+		#[pallet::weight(<T as Config>::WeightInfo::pause_call())]
+		pub fn pause_call(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			call_name: Vec<u8>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			let (bounded_pallet, bounded_call) =
+				Self::bounded_names(pallet_name.clone(), call_name.clone())?;
+			ensure!(!T::UnpausablePallets::contains(&bounded_pallet), Error::<T>::Unpausable);
+			ensure!(
+				!PausedCalls::<T>::contains_key(&bounded_pallet, &bounded_call),
+				Error::<T>::AlreadyPaused
+			);
+			PausedCalls::<T>::insert(bounded_pallet, bounded_call, ());
+			Self::deposit_event(Event::CallPaused { pallet_name, call_name });
+			Ok(())
+		}
+
+		/// Unpauses a paused call.
+		#[pallet::weight(<T as Config>::WeightInfo::unpause_call())]
+		pub fn unpause_call(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			call_name: Vec<u8>,
+		) -> DispatchResult {
+			T::UnpauseOrigin::ensure_origin(origin)?;
+			let (bounded_pallet, bounded_call) =
+				Self::bounded_names(pallet_name.clone(), call_name.clone())?;
+			ensure!(
+				PausedCalls::<T>::contains_key(&bounded_pallet, &bounded_call),
+				Error::<T>::NotPaused
+			);
+			PausedCalls::<T>::remove(bounded_pallet, bounded_call);
+			Self::deposit_event(Event::CallUnpaused { pallet_name, call_name });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether `call` is paused.
+		pub fn is_paused<Call: GetCallMetadata>(call: &Call) -> bool {
+			let metadata = call.get_call_metadata();
+			match Self::bounded_names(metadata.pallet_name.into(), metadata.function_name.into()) {
+				Ok((pallet_name, call_name)) =>
+					!T::UnpausablePallets::contains(&pallet_name) &&
+						PausedCalls::<T>::contains_key(pallet_name, call_name),
+				Err(_) => false,
+			}
+		}
+
+		fn bounded_names(
+			pallet_name: Vec<u8>,
+			call_name: Vec<u8>,
+		) -> Result<(NameOf<T>, NameOf<T>), DispatchError> {
+			Ok((
+				pallet_name.try_into().map_err(|_| Error::<T>::NameTooLong)?,
+				call_name.try_into().map_err(|_| Error::<T>::NameTooLong)?,
+			))
+		}
+	}
+
+	/// Contains the calls that are not paused, to be used as or in `BaseCallFilter`.
+	impl<T: Config> Contains<<T as frame_system::Config>::Call> for Pallet<T>
+	where
+		<T as frame_system::Config>::Call: GetCallMetadata,
+	{
+		fn contains(call: &<T as frame_system::Config>::Call) -> bool {
+			!Self::is_paused(call)
+		}
+	}
+}
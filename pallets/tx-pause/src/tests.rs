@@ -0,0 +1,129 @@
+use frame_support::{assert_noop, assert_ok, dispatch::Dispatchable};
+use sp_runtime::traits::BadOrigin;
+
+use crate::mock::{
+	new_test_ext, Balances, Call, Council, Event, Origin, System, TechnicalCommittee, Test,
+	TxPause, Utility, PDEX,
+};
+
+use crate::pallet::*;
+
+fn transfer() -> Call {
+	Call::Balances(pallet_balances::Call::transfer { dest: 2, value: 10 * PDEX })
+}
+
+fn pause_transfer() {
+	assert_ok!(TxPause::pause_call(
+		Origin::signed(TechnicalCommittee::get()),
+		b"Balances".to_vec(),
+		b"transfer".to_vec()
+	));
+}
+
+#[test]
+pub fn pause_call_requires_pause_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::pause_call(
+				Origin::signed(Council::get()),
+				b"Balances".to_vec(),
+				b"transfer".to_vec()
+			),
+			BadOrigin
+		);
+		pause_transfer();
+		System::assert_last_event(Event::TxPause(crate::Event::CallPaused {
+			pallet_name: b"Balances".to_vec(),
+			call_name: b"transfer".to_vec(),
+		}));
+		assert!(TxPause::is_paused(&transfer()));
+		assert_noop!(
+			TxPause::pause_call(
+				Origin::signed(TechnicalCommittee::get()),
+				b"Balances".to_vec(),
+				b"transfer".to_vec()
+			),
+			Error::<Test>::AlreadyPaused
+		);
+	});
+}
+
+#[test]
+pub fn unpausable_pallets_can_not_be_paused() {
+	new_test_ext().execute_with(|| {
+		for pallet_name in [&b"System"[..], b"TxPause"] {
+			assert_noop!(
+				TxPause::pause_call(
+					Origin::signed(TechnicalCommittee::get()),
+					pallet_name.to_vec(),
+					b"remark".to_vec()
+				),
+				Error::<Test>::Unpausable
+			);
+		}
+		assert_noop!(
+			TxPause::pause_call(
+				Origin::signed(TechnicalCommittee::get()),
+				vec![b'a'; 33],
+				b"transfer".to_vec()
+			),
+			Error::<Test>::NameTooLong
+		);
+	});
+}
+
+#[test]
+pub fn paused_calls_fail_dispatch() {
+	new_test_ext().execute_with(|| {
+		pause_transfer();
+		assert_noop!(
+			transfer().dispatch(Origin::signed(1)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+		// Other calls of the pallet are not affected.
+		assert_ok!(Call::Balances(pallet_balances::Call::transfer_keep_alive {
+			dest: 2,
+			value: 10 * PDEX
+		})
+		.dispatch(Origin::signed(1)));
+	});
+}
+
+#[test]
+pub fn paused_calls_are_filtered_in_batches() {
+	new_test_ext().execute_with(|| {
+		pause_transfer();
+		assert_ok!(Utility::batch(Origin::signed(1), vec![transfer()]));
+		System::assert_last_event(Event::Utility(pallet_utility::Event::BatchInterrupted {
+			index: 0,
+			error: frame_system::Error::<Test>::CallFiltered.into(),
+		}));
+		assert_eq!(Balances::free_balance(2), 0);
+	});
+}
+
+#[test]
+pub fn council_can_unpause_calls() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::unpause_call(
+				Origin::signed(Council::get()),
+				b"Balances".to_vec(),
+				b"transfer".to_vec()
+			),
+			Error::<Test>::NotPaused
+		);
+		pause_transfer();
+		assert_noop!(
+			TxPause::unpause_call(Origin::signed(1), b"Balances".to_vec(), b"transfer".to_vec()),
+			BadOrigin
+		);
+		assert_ok!(TxPause::unpause_call(
+			Origin::signed(Council::get()),
+			b"Balances".to_vec(),
+			b"transfer".to_vec()
+		));
+		assert!(!TxPause::is_paused(&transfer()));
+		assert_ok!(transfer().dispatch(Origin::signed(1)));
+	});
+}
@@ -17,9 +17,18 @@
 
 //! Some configurable implementations as associated type for the substrate runtime.
 
-use frame_support::traits::{Currency, OnUnbalanced};
-
-use crate::{Authorship, Balances, NegativeImbalance};
+use codec::Encode;
+use fee_distribution::Distribution;
+use frame_support::traits::{fungibles, Currency, Get, OnUnbalanced};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use polkadex_primitives::AccountId;
+use sp_runtime::DispatchError;
+
+use crate::{
+	AssetCredit, Assets, Authorship, Balances, NegativeImbalance, Runtime, TreasuryAccount,
+};
 
 pub struct Author;
 
@@ -31,6 +40,71 @@ impl OnUnbalanced<NegativeImbalance> for Author {
 	}
 }
 
+/// Splits fees or tips paid in an asset following `distribution`, the way fee-distribution splits
+/// them in PDEX. The author share goes to the treasury when the block has no author or the author
+/// can not hold the asset. The treasury always exists, kept alive by its genesis endowment, but an
+/// amount below the minimum balance of the asset can not open its account and is burnt.
+pub fn distribute_asset_credit(credit: AssetCredit, distribution: Distribution) {
+	let total = credit.peek();
+	let (burnt, rest) = credit.split(distribution.burn * total);
+	let (author, treasury) = rest.split(distribution.author * total);
+	// Dropping the credit reduces the total issuance of the asset.
+	drop(burnt);
+	let resolve = |who: &AccountId, credit| {
+		<Assets as fungibles::Balanced<AccountId>>::resolve(who, credit)
+	};
+	let unresolved = match Authorship::author() {
+		Some(author_id) => resolve(&author_id, author).err(),
+		None => Some(author),
+	};
+	let treasury = match unresolved {
+		Some(author) => treasury.merge(author).unwrap_or_else(|(treasury, _)| treasury),
+		None => treasury,
+	};
+	if let Err(credit) = resolve(&TreasuryAccount::get(), treasury) {
+		log::warn!(
+			target: "runtime::asset-fees",
+			"treasury could not take {:?} of asset {:?} paid as fees, burning it",
+			credit.peek(),
+			credit.asset(),
+		);
+	}
+}
+
+/// Chain extension giving contracts read access to the state of the chain.
+///
+/// Functions, by id:
+/// - `1`: free balance of an account. Takes `(Option<AssetId>, AccountId)`, where `None` is PDEX,
+///   and returns the `Balance`.
+pub struct PolkadexChainExtension;
+
+impl PolkadexChainExtension {
+	pub const BALANCE: u32 = 1;
+}
+
+impl ChainExtension<Runtime> for PolkadexChainExtension {
+	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		match func_id {
+			Self::BALANCE => {
+				let mut env = env.buf_in_buf_out();
+				let (asset, who): (Option<u128>, AccountId) = env.read_as()?;
+				env.charge_weight(<Runtime as frame_system::Config>::DbWeight::get().reads(1))?;
+				let balance = match asset {
+					Some(asset) => Assets::balance(asset, &who),
+					None => Balances::free_balance(&who),
+				};
+				env.write(&balance.encode(), false, None)?;
+				Ok(RetVal::Converging(0))
+			},
+			_ => Err(DispatchError::Other("Unknown chain extension function")),
+		}
+	}
+}
+
 #[cfg(test)]
 mod multiplier_tests {
 	use frame_support::weights::{DispatchClass, Weight, WeightToFeePolynomial};
@@ -26,7 +26,7 @@ use frame_election_provider_support::{onchain, SequentialPhragmen};
 use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		Currency, EnsureOrigin, Imbalance, KeyOwnerProofSystem, LockIdentifier, U128CurrencyToVote,
+		fungibles, Currency, EnsureOrigin, KeyOwnerProofSystem, LockIdentifier, U128CurrencyToVote,
 	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
@@ -38,7 +38,7 @@ use frame_support::{
 use frame_support::{
 	pallet_prelude::ConstU32,
 	traits::{
-		ConstU16, EnsureOneOf, EqualPrivilegeOnly, Everything, Get, InstanceFilter, OnUnbalanced,
+		ConstU16, Contains, EnsureOneOf, EqualPrivilegeOnly, Get, InstanceFilter, OnUnbalanced,
 	},
 	weights::{ConstantMultiplier, WeightToFeeCoefficient},
 	PalletId,
@@ -51,6 +51,7 @@ use frame_system::{
 };
 #[cfg(any(feature = "std", test))]
 pub use pallet_balances::Call as BalancesCall;
+use pallet_contracts::weights::WeightInfo;
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
@@ -75,8 +76,8 @@ use sp_runtime::{
 	curve::PiecewiseLinear,
 	generic, impl_opaque_keys,
 	traits::{
-		self, AccountIdConversion, BlakeTwo256, Block as BlockT, BlockNumberProvider, NumberFor,
-		OpaqueKeys, SaturatedConversion, StaticLookup,
+		self, AccountIdConversion, BlakeTwo256, Block as BlockT, BlockNumberProvider, Convert,
+		NumberFor, OpaqueKeys, SaturatedConversion, StaticLookup,
 	},
 	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, FixedPointNumber, Perbill, Percent, Permill, Perquintill,
@@ -95,6 +96,8 @@ pub mod impls;
 
 /// Constant values used within the runtime.
 pub mod constants;
+pub mod migrations;
+mod voter_bags;
 mod weights;
 
 // Make the WASM binary available.
@@ -120,10 +123,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 276,
+	spec_version: 290,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 5,
 	state_version: 0, // TODO: Check if this is correct?
 };
 
@@ -142,16 +145,21 @@ pub fn native_version() -> NativeVersion {
 
 type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
 
-pub struct DealWithFees;
-impl OnUnbalanced<NegativeImbalance> for DealWithFees {
-	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
-		if let Some(mut fees) = fees_then_tips.next() {
-			if let Some(tips) = fees_then_tips.next() {
-				tips.merge_into(&mut fees);
-			}
-			// Sent everything to treasury
-			Treasury::on_unbalanced(fees);
-		}
+type AssetCredit = fungibles::CreditOf<AccountId, Assets>;
+
+/// Splits fees paid in assets following the fee distribution, like fees paid in PDEX.
+pub struct DealWithAssetFees;
+impl OnUnbalanced<AssetCredit> for DealWithAssetFees {
+	fn on_nonzero_unbalanced(fee: AssetCredit) {
+		impls::distribute_asset_credit(fee, FeeDistribution::fee_distribution());
+	}
+}
+
+/// Splits tips paid in assets following the tip distribution, like tips paid in PDEX.
+pub struct DealWithAssetTips;
+impl OnUnbalanced<AssetCredit> for DealWithAssetTips {
+	fn on_nonzero_unbalanced(tip: AssetCredit) {
+		impls::distribute_asset_credit(tip, FeeDistribution::tip_distribution());
 	}
 }
 
@@ -192,8 +200,24 @@ parameter_types! {
 
 const_assert!(NORMAL_DISPATCH_RATIO.deconstruct() >= AVERAGE_ON_INITIALIZE_RATIO.deconstruct());
 
+/// Calls that can not be dispatched by anyone.
+pub struct BaseFilter;
+impl Contains<Call> for BaseFilter {
+	fn contains(call: &Call) -> bool {
+		match call {
+			// Assets are created through the governed asset registry only.
+			Call::Assets(pallet_assets::Call::create { .. }) => false,
+			// Ids of AMM liquidity shares are reserved for the AMM.
+			Call::Assets(pallet_assets::Call::force_create { id, .. })
+				if AmmShareAssetIds::contains(id) =>
+				false,
+			_ => TxPause::contains(call),
+		}
+	}
+}
+
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = Everything;
+	type BaseCallFilter = BaseFilter;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type DbWeight = RocksDbWeight;
@@ -272,10 +296,22 @@ use sp_npos_elections::{ExtendedBalance};
 	TypeInfo,
 )]
 pub enum ProxyType {
+	/// Any call.
 	Any = 0,
+	/// Any call that cannot move funds of the proxied account, directly or indirectly.
 	NonTransfer = 1,
+	/// Council, elections, democracy and treasury calls.
 	Governance = 2,
+	/// Staking and session key calls.
 	Staking = 3,
+	/// Judgements of a registrar.
+	IdentityJudgement = 4,
+	/// Rejecting announcements of other proxies.
+	CancelProxy = 5,
+	/// Relaying migrated PDEX as a relayer of pdex-migration.
+	Bridge = 6,
+	/// Treasury spends, bounties and child bounties.
+	Treasury = 7,
 }
 
 impl Default for ProxyType {
@@ -287,18 +323,94 @@ impl Default for ProxyType {
 impl InstanceFilter<Call> for ProxyType {
 	fn filter(&self, c: &Call) -> bool {
 		match self {
-			ProxyType::Any => false,
-			ProxyType::NonTransfer => !matches!(
-				c,
-				Call::Balances(..) | Call::Indices(pallet_indices::Call::transfer { .. })
-			),
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => match c {
+				Call::System(..) |
+				Call::Utility(..) |
+				Call::Babe(..) |
+				Call::Timestamp(..) |
+				Call::Authorship(..) |
+				Call::ElectionProviderMultiPhase(..) |
+				Call::Staking(..) |
+				Call::Session(..) |
+				Call::Council(..) |
+				Call::TechnicalCommittee(..) |
+				Call::Elections(..) |
+				Call::TechnicalMembership(..) |
+				Call::Grandpa(..) |
+				Call::Treasury(..) |
+				Call::ImOnline(..) |
+				Call::Identity(..) |
+				Call::Scheduler(..) |
+				Call::Bounties(..) |
+				Call::Democracy(..) |
+				Call::Preimage(..) |
+				Call::ChildBounties(..) |
+				Call::AssetRegistry(..) |
+				Call::Feeless(..) |
+				Call::AssetFees(..) |
+				Call::TxPause(..) |
+				Call::BagsList(..) |
+				Call::FeeDistribution(..) |
+				Call::FeeDiscounts(..) => true,
+				Call::Indices(call) => matches!(
+					call,
+					pallet_indices::Call::claim { .. } |
+						pallet_indices::Call::free { .. } |
+						pallet_indices::Call::freeze { .. }
+				),
+				Call::PDEXMigration(call) => matches!(
+					call,
+					pdex_migration::pallet::Call::mint { .. } |
+						pdex_migration::pallet::Call::unlock { .. }
+				),
+				// Transfers, or calls that can be used to transfer funds on behalf of the account.
+				Call::Balances(..) |
+				Call::Recovery(..) |
+				Call::Proxy(..) |
+				Call::Multisig(..) |
+				Call::OrmlVesting(..) |
+				Call::Assets(..) |
+				Call::Orderbook(..) |
+				Call::Amm(..) |
+				Call::Contracts(..) => false,
+			},
 			ProxyType::Governance => matches!(
 				c,
 				Call::Council(..) |
 					Call::TechnicalCommittee(..) |
-					Call::Elections(..) | Call::Treasury(..)
+					Call::Elections(..) |
+					Call::Treasury(..) |
+					Call::Democracy(..) |
+					Call::Bounties(..) |
+					Call::ChildBounties(..) |
+					Call::Utility(..)
+			),
+			ProxyType::Staking => {
+				matches!(
+					c,
+					Call::Staking(..) | Call::Session(..) | Call::BagsList(..) | Call::Utility(..)
+				)
+			},
+			ProxyType::IdentityJudgement => matches!(
+				c,
+				Call::Identity(pallet_identity::Call::provide_judgement { .. }) | Call::Utility(..)
+			),
+			ProxyType::CancelProxy => matches!(
+				c,
+				Call::Proxy(pallet_proxy::Call::reject_announcement { .. }) | Call::Utility(..)
+			),
+			ProxyType::Bridge => matches!(
+				c,
+				Call::PDEXMigration(pdex_migration::pallet::Call::mint { .. }) | Call::Utility(..)
+			),
+			ProxyType::Treasury => matches!(
+				c,
+				Call::Treasury(..) |
+					Call::Bounties(..) |
+					Call::ChildBounties(..) |
+					Call::Utility(..)
 			),
-			ProxyType::Staking => matches!(c, Call::Staking(..)),
 		}
 	}
 	fn is_superset(&self, o: &Self) -> bool {
@@ -306,7 +418,15 @@ impl InstanceFilter<Call> for ProxyType {
 			(x, y) if x == y => true,
 			(ProxyType::Any, _) => true,
 			(_, ProxyType::Any) => false,
-			(ProxyType::NonTransfer, _) => true,
+			(
+				ProxyType::NonTransfer,
+				ProxyType::Governance |
+				ProxyType::Staking |
+				ProxyType::IdentityJudgement |
+				ProxyType::Bridge |
+				ProxyType::Treasury,
+			) => true,
+			(ProxyType::Governance, ProxyType::Treasury) => true,
 			_ => false,
 		}
 	}
@@ -360,7 +480,7 @@ impl pallet_preimage::Config for Runtime {
 	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
 	type Event = Event;
 	type Currency = Balances;
-	type ManagerOrigin = EnsureRoot<AccountId>;
+	type ManagerOrigin = EnsureRootOrHalfCouncil;
 	type MaxSize = PreimageMaxSize;
 	type BaseDeposit = PreimageBaseDeposit;
 	type ByteDeposit = PreimageByteDeposit;
@@ -455,7 +575,10 @@ impl WeightToFeePolynomial for WeightToFee {
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
+	type OnChargeTransaction = feeless::FeelessAdapter<
+		Runtime,
+		fee_discounts::DiscountAdapter<Runtime, CurrencyAdapter<Balances, FeeDistribution>>,
+	>;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type WeightToFee = WeightToFee;
 	type FeeMultiplierUpdate =
@@ -467,6 +590,17 @@ parameter_types! {
 	pub const MinimumPeriod: Moment = SLOT_DURATION / 2;
 }
 
+impl pallet_asset_tx_payment::Config for Runtime {
+	type Fungibles = Assets;
+	type OnChargeAssetTransaction = feeless::FeelessAdapter<
+		Runtime,
+		fee_discounts::DiscountAdapter<
+			Runtime,
+			asset_fees::AssetFeeAdapter<Runtime, DealWithAssetFees, DealWithAssetTips>,
+		>,
+	>;
+}
+
 impl pallet_timestamp::Config for Runtime {
 	type Moment = Moment;
 	type OnTimestampSet = Babe;
@@ -581,10 +715,22 @@ impl pallet_staking::Config for Runtime {
 	type MaxNominations = MaxNominations;
 	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
 	type BenchmarkingConfig = StakingBenchmarkingConfig;
-	type VoterList = pallet_staking::UseNominatorsAndValidatorsMap<Runtime>;
+	type VoterList = BagsList;
 	type MaxUnlockingChunks = ConstU32<32>;
 }
 
+parameter_types! {
+	pub const BagThresholds: &'static [u64] = &voter_bags::THRESHOLDS;
+}
+
+impl pallet_bags_list::Config for Runtime {
+	type Event = Event;
+	type ScoreProvider = Staking;
+	type WeightInfo = pallet_bags_list::weights::SubstrateWeight<Runtime>;
+	type BagThresholds = BagThresholds;
+	type Score = sp_npos_elections::VoteWeight;
+}
+
 parameter_types! {
 	// phase durations. 1/4 of the last session for each.
 	pub const SignedPhase: u32 = EPOCH_DURATION_IN_SLOTS / 4;
@@ -785,6 +931,11 @@ type EnsureRootOrHalfCouncil = EnsureOneOf<
 	pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
 >;
 
+type EnsureRootOrHalfTechnicalCommittee = EnsureOneOf<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>,
+>;
+
 impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
 	type Event = Event;
 	type AddOrigin = EnsureRootOrHalfCouncil;
@@ -952,11 +1103,6 @@ impl pallet_democracy::Config for Runtime {
 	type MaxProposals = MaxProposals;
 }
 
-impl pallet_sudo::Config for Runtime {
-	type Event = Event;
-	type Call = Call;
-}
-
 parameter_types! {
 	pub const ImOnlineUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
 	/// We prioritize im-online heartbeats over election solution submission.
@@ -995,7 +1141,8 @@ where
 			)),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
 			frame_system::CheckWeight::<Runtime>::new(),
-			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			feeless::CheckFeeless::<Runtime>::new(),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None),
 		);
 		let raw_payload = SignedPayload::new(call, extra)
 			.map_err(|e| {
@@ -1124,11 +1271,23 @@ impl BlockNumberProvider for SusbtrateBlockNumberProvider {
 	}
 }
 
+/// Root, a council majority or the treasury account. Root and the council act on behalf of the
+/// treasury.
 pub struct EnsureRootOrTreasury;
 impl EnsureOrigin<Origin> for EnsureRootOrTreasury {
 	type Success = AccountId;
 
 	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		let o = match pallet_collective::EnsureProportionMoreThan::<
+			AccountId,
+			CouncilCollective,
+			1,
+			2,
+		>::try_origin(o)
+		{
+			Ok(_) => return Ok(TreasuryPalletId::get().into_account()),
+			Err(o) => o,
+		};
 		Into::<Result<RawOrigin<AccountId>, Origin>>::into(o).and_then(|o| match o {
 			RawOrigin::Root => Ok(TreasuryPalletId::get().into_account()),
 			RawOrigin::Signed(caller) =>
@@ -1157,17 +1316,296 @@ impl orml_vesting::Config for Runtime {
 	type BlockNumberProvider = SusbtrateBlockNumberProvider;
 }
 parameter_types! {
-	pub const LockPeriod: BlockNumber = 201600;
+	pub const LockPeriod: BlockNumber = LOCK_PERIOD;
 	pub const MaxRelayers: u32 = 3;
 }
 
+/// Blocks during which minted PDEX stay locked, shortened by the `fast-runtime` feature for tests.
+#[cfg(not(feature = "fast-runtime"))]
+const LOCK_PERIOD: BlockNumber = 201600;
+#[cfg(feature = "fast-runtime")]
+const LOCK_PERIOD: BlockNumber = 5;
+
 impl pdex_migration::pallet::Config for Runtime {
 	type Event = Event;
 	type MaxRelayers = MaxRelayers;
 	type LockPeriod = LockPeriod;
+	type AdminOrigin = EnsureOneOf<EnsureRootOrHalfCouncil, EnsureRootOrHalfTechnicalCommittee>;
 	type WeightInfo = weights::pdex_migration::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const AssetDeposit: Balance = deposit(1, 190);
+	pub const AssetAccountDeposit: Balance = deposit(1, 16);
+	pub const ApprovalDeposit: Balance = deposit(1, 32);
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = deposit(1, 68);
+	pub const MetadataDepositPerByte: Balance = deposit(0, 1);
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u128;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+impl asset_registry::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u128;
+	type Assets = Assets;
+	type RegistryOrigin = EnsureRootOrHalfCouncil;
+	type ReservedAssetIds = AmmShareAssetIds;
+	type StringLimit = StringLimit;
+	type WeightInfo = weights::asset_registry::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const OrderbookPalletId: PalletId = PalletId(*b"py/ordbk");
+	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account();
+	pub const MaxMatchesPerBlock: u32 = 100;
+	pub const MaxPendingOrders: u32 = 1_000;
+	pub const MaxPriceLevels: u32 = 200;
+	pub const MaxOrdersPerLevel: u32 = 100;
+}
+
+impl orderbook::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Currency = Balances;
+	type AssetId = u128;
+	type Assets = Assets;
+	type NativeFeeHandler = Treasury;
+	type AssetFeeCollector = TreasuryAccount;
+	type GovernanceOrigin = EnsureRootOrHalfCouncil;
+	type PalletId = OrderbookPalletId;
+	type MaxMatchesPerBlock = MaxMatchesPerBlock;
+	type MaxPendingOrders = MaxPendingOrders;
+	type MaxPriceLevels = MaxPriceLevels;
+	type MaxOrdersPerLevel = MaxOrdersPerLevel;
+	type WeightInfo = weights::orderbook::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const AmmPalletId: PalletId = PalletId(*b"py/ammpl");
+	// Keeps at least the existential deposit of PDEX locked in every pool.
+	pub const MinimumLiquidity: Balance = ExistentialDeposit::get();
+	pub const DefaultSwapFee: Permill = Permill::from_parts(3_000);
+	pub const DefaultTreasuryShare: Permill = Permill::from_percent(20);
+}
+
+/// Top bit of the asset ids of AMM liquidity shares.
+const AMM_SHARE_ASSET_BIT: u128 = 1 << 127;
+
+/// Liquidity shares of the AMM pool of an asset are minted as the asset id with the top bit set.
+pub struct AmmShareAssetId;
+impl Convert<u128, u128> for AmmShareAssetId {
+	fn convert(asset: u128) -> u128 {
+		asset | AMM_SHARE_ASSET_BIT
+	}
+}
+
+/// Asset ids with the top bit set, reserved for AMM liquidity shares.
+pub struct AmmShareAssetIds;
+impl Contains<u128> for AmmShareAssetIds {
+	fn contains(asset: &u128) -> bool {
+		asset & AMM_SHARE_ASSET_BIT != 0
+	}
+}
+
+impl amm::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Currency = Balances;
+	type AssetId = u128;
+	type Assets = Assets;
+	type ShareAssetId = AmmShareAssetId;
+	type TreasuryAccount = TreasuryAccount;
+	type GovernanceOrigin = EnsureRootOrHalfCouncil;
+	type PalletId = AmmPalletId;
+	type MinimumLiquidity = MinimumLiquidity;
+	type DefaultSwapFee = DefaultSwapFee;
+	type DefaultTreasuryShare = DefaultTreasuryShare;
+	type WeightInfo = weights::amm::WeightInfo<Runtime>;
+}
+
+/// Prices PDEX in assets at the spot price of their AMM pool.
+pub struct AmmSpotPrice;
+impl asset_fees::PoolPrice<u128, Balance> for AmmSpotPrice {
+	fn native_to_asset(asset: u128, native_amount: Balance) -> Option<Balance> {
+		Amm::spot_value(asset, native_amount)
+	}
+}
+
+impl asset_fees::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u128;
+	type Assets = Assets;
+	type PoolPrice = AmmSpotPrice;
+	type GovernanceOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = weights::asset_fees::WeightInfo<Runtime>;
+}
+
+pub struct ActiveStake;
+impl feeless::StakeInfo<AccountId, Balance> for ActiveStake {
+	fn active_stake(who: &AccountId) -> Balance {
+		Staking::bonded(who)
+			.and_then(Staking::ledger)
+			.map(|ledger| ledger.active)
+			.unwrap_or_default()
+	}
+}
+
+parameter_types! {
+	pub const MaxFeelessPerBlock: u32 = 10;
+	pub const MaxCallNameLength: u32 = 64;
+}
+
+impl feeless::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type StakeInfo = ActiveStake;
+	type GovernanceOrigin = EnsureRootOrHalfCouncil;
+	type MaxFeelessPerBlock = MaxFeelessPerBlock;
+	type MaxNameLength = MaxCallNameLength;
+	type WeightInfo = weights::feeless::WeightInfo<Runtime>;
+}
+
+/// Pallets needed to produce blocks, whose inherents and unsigned calls also pass the
+/// `BaseCallFilter`, and pallets needed to govern the chain, including to unpause calls.
+pub struct UnpausablePallets;
+impl Contains<tx_pause::NameOf<Runtime>> for UnpausablePallets {
+	fn contains(pallet_name: &tx_pause::NameOf<Runtime>) -> bool {
+		matches!(
+			pallet_name.as_slice(),
+			b"System" |
+				b"Timestamp" | b"Babe" |
+				b"Authorship" | b"Grandpa" |
+				b"ImOnline" | b"ElectionProviderMultiPhase" |
+				b"Council" | b"TechnicalCommittee" |
+				b"TechnicalMembership" |
+				b"Elections" |
+				b"Democracy" |
+				b"Scheduler" |
+				b"Preimage" |
+				b"TxPause"
+		)
+	}
+}
+
+impl tx_pause::Config for Runtime {
+	type Event = Event;
+	type PauseOrigin = EnsureRootOrHalfTechnicalCommittee;
+	type UnpauseOrigin = EnsureOneOf<EnsureRootOrHalfTechnicalCommittee, EnsureRootOrHalfCouncil>;
+	type UnpausablePallets = UnpausablePallets;
+	type MaxNameLength = MaxCallNameLength;
+	type WeightInfo = weights::tx_pause::WeightInfo<Runtime>;
+}
+
+impl fee_distribution::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Treasury = Treasury;
+	type Author = impls::Author;
+	type UpdateOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = weights::fee_distribution::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const MaxDiscountTiers: u32 = 10;
+}
+
+impl fee_discounts::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type StakeInfo = ActiveStake;
+	type GovernanceOrigin = EnsureRootOrHalfCouncil;
+	type MaxTiers = MaxDiscountTiers;
+	type WeightInfo = weights::fee_discounts::WeightInfo<Runtime>;
+}
+
+impl pallet_mmr::Config for Runtime {
+	const INDEXING_PREFIX: &'static [u8] = b"mmr";
+	type Hashing = <Runtime as frame_system::Config>::Hashing;
+	type Hash = <Runtime as frame_system::Config>::Hash;
+	// Leaves are the parent hashes of the blocks.
+	type LeafData = frame_system::Pallet<Self>;
+	type OnNewRoot = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const DepositPerItem: Balance = deposit(1, 0);
+	pub const DepositPerByte: Balance = deposit(0, 1);
+	// The lazy deletion runs inside on_initialize.
+	pub DeletionWeightLimit: Weight = AVERAGE_ON_INITIALIZE_RATIO *
+		RuntimeBlockWeights::get().max_block;
+	// The weight needed for decoding the queue should be less or equal than a fifth
+	// of the overall weight dedicated to the lazy deletion.
+	pub DeletionQueueDepth: u32 = ((DeletionWeightLimit::get() / (
+			<Runtime as pallet_contracts::Config>::WeightInfo::on_initialize_per_queue_item(1) -
+			<Runtime as pallet_contracts::Config>::WeightInfo::on_initialize_per_queue_item(0)
+		)) / 5) as u32;
+	pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
+}
+
+/// Calls contracts can dispatch. Contracts are meant for trading bots, so they can trade and
+/// transfer funds, but can not dispatch governance or any other privileged call.
+///
+/// Whitelisted calls must stay stable, as changing them breaks deployed contracts.
+pub struct ContractsCallFilter;
+impl Contains<Call> for ContractsCallFilter {
+	fn contains(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::Balances(
+				pallet_balances::Call::transfer { .. } |
+					pallet_balances::Call::transfer_keep_alive { .. }
+			) | Call::Assets(
+				pallet_assets::Call::transfer { .. } |
+					pallet_assets::Call::transfer_keep_alive { .. }
+			) | Call::Orderbook(
+				orderbook::Call::place_limit_order { .. } | orderbook::Call::cancel_order { .. }
+			) | Call::Amm(
+				amm::Call::add_liquidity { .. } |
+					amm::Call::remove_liquidity { .. } |
+					amm::Call::swap_exact_in { .. }
+			)
+		)
+	}
+}
+
+impl pallet_contracts::Config for Runtime {
+	type Time = Timestamp;
+	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
+	type Currency = Balances;
+	type Event = Event;
+	type Call = Call;
+	type CallFilter = ContractsCallFilter;
+	type DepositPerItem = DepositPerItem;
+	type DepositPerByte = DepositPerByte;
+	type CallStack = [pallet_contracts::Frame<Self>; 31];
+	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
+	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
+	type ChainExtension = impls::PolkadexChainExtension;
+	type DeletionQueueDepth = DeletionQueueDepth;
+	type DeletionWeightLimit = DeletionWeightLimit;
+	type Schedule = Schedule;
+	type AddressGenerator = pallet_contracts::DefaultAddressGenerator;
+}
+
 construct_runtime!(
 	pub enum Runtime where
 		Block = Block,
@@ -1191,7 +1629,6 @@ construct_runtime!(
 		TechnicalMembership: pallet_membership::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>} = 14,
 		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event, ValidateUnsigned} = 15,
 		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>} = 16,
-		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>} = 17,
 		ImOnline: pallet_im_online::{Pallet, Call, Storage, Event<T>, ValidateUnsigned, Config<T>} = 18,
 		AuthorityDiscovery: pallet_authority_discovery::{Pallet, Config} = 19,
 		Offences: pallet_offences::{Pallet, Storage, Event} = 20,
@@ -1208,6 +1645,19 @@ construct_runtime!(
 		// Pallets
 		OrmlVesting: orml_vesting::{Pallet, Storage, Call, Event<T>, Config<T>} = 28,
 		PDEXMigration: pdex_migration::pallet::{Pallet, Storage, Call, Event<T>, Config<T>} = 29,
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>, Config<T>} = 34,
+		Orderbook: orderbook::{Pallet, Call, Storage, Event<T>} = 35,
+		Amm: amm::{Pallet, Call, Storage, Event<T>} = 36,
+		AssetRegistry: asset_registry::{Pallet, Call, Storage, Config<T>, Event<T>} = 37,
+		Feeless: feeless::{Pallet, Call, Storage, Config<T>, Event<T>} = 38,
+		AssetTxPayment: pallet_asset_tx_payment::{Pallet} = 39,
+		AssetFees: asset_fees::{Pallet, Call, Storage, Event<T>} = 40,
+		TxPause: tx_pause::{Pallet, Call, Storage, Event<T>} = 41,
+		BagsList: pallet_bags_list::{Pallet, Call, Storage, Event<T>} = 42,
+		Mmr: pallet_mmr::{Pallet, Storage} = 43,
+		Contracts: pallet_contracts::{Pallet, Call, Storage, Event<T>} = 44,
+		FeeDistribution: fee_distribution::{Pallet, Call, Storage, Event<T>} = 45,
+		FeeDiscounts: fee_discounts::{Pallet, Call, Storage, Config<T>, Event<T>} = 46,
 	}
 );
 /// Digest item type.
@@ -1234,7 +1684,8 @@ pub type SignedExtra = (
 	frame_system::CheckMortality<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	feeless::CheckFeeless<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -1242,6 +1693,10 @@ pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signatu
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
+/// Storage migrations run on the next runtime upgrade, before those of the pallets.
+///
+/// See [`migrations`] for how to add and retire a migration.
+pub type Migrations = (migrations::RemoveSudo, migrations::PopulateVoterList);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -1249,8 +1704,35 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// Sender of a signed extrinsic, who pays its fee.
+fn fee_payer(uxt: &UncheckedExtrinsic) -> Option<AccountId> {
+	uxt.signature.as_ref().and_then(|(address, _, _)| Indices::lookup(address.clone()).ok())
+}
+
+/// Details of the fee of `uxt`, discounted for its sender as charged by
+/// `fee_discounts::DiscountAdapter`, in PDEX or in assets.
+fn discounted_fee_details(uxt: UncheckedExtrinsic, len: u32) -> FeeDetails<Balance> {
+	let sender = fee_payer(&uxt);
+	let details = TransactionPayment::query_fee_details(uxt, len);
+	match sender {
+		Some(sender) => FeeDiscounts::discounted_fee_details(&sender, details),
+		None => details,
+	}
+}
+
+/// MMR helper types.
+mod mmr {
+	use super::Runtime;
+	pub use pallet_mmr::primitives::*;
+
+	pub type Leaf = <<Runtime as pallet_mmr::Config>::LeafData as LeafDataProvider>::LeafData;
+	pub type Hash = <Runtime as pallet_mmr::Config>::Hash;
+	pub type Hashing = <Runtime as pallet_mmr::Config>::Hashing;
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -1413,10 +1895,142 @@ impl_runtime_apis! {
 		Balance,
 	> for Runtime {
 		fn query_info(uxt: <Block as BlockT>::Extrinsic, len: u32) -> RuntimeDispatchInfo<Balance> {
-			TransactionPayment::query_info(uxt, len)
+			// Reports the fee discounted for the sender, as charged by `fee_discounts::DiscountAdapter`.
+			let sender = fee_payer(&uxt);
+			let mut info = TransactionPayment::query_info(uxt, len);
+			if let Some(sender) = sender {
+				info.partial_fee = FeeDiscounts::discounted_fee(&sender, info.partial_fee);
+			}
+			info
 		}
 		fn query_fee_details(uxt: <Block as BlockT>::Extrinsic, len: u32) -> FeeDetails<Balance> {
-			TransactionPayment::query_fee_details(uxt, len)
+			discounted_fee_details(uxt, len)
+		}
+	}
+
+	impl asset_fees_runtime_api::AssetFeesApi<Block, u128, Balance> for Runtime {
+		fn query_fee_details(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			asset: Option<u128>,
+		) -> Option<FeeDetails<Balance>> {
+			let details = discounted_fee_details(uxt, len);
+			match asset {
+				Some(asset) => AssetFees::fee_details_in_asset(asset, details),
+				None => Some(details),
+			}
+		}
+	}
+
+	impl orderbook_runtime_api::OrderbookApi<Block, u128, Balance> for Runtime {
+		fn order_book_depth(
+			pair: orderbook::TradingPair<u128>,
+			levels: u32,
+		) -> Option<orderbook::OrderBookDepth<Balance>> {
+			Orderbook::order_book_depth(pair, levels)
+		}
+	}
+
+	impl amm_runtime_api::AmmApi<Block, u128, Balance> for Runtime {
+		fn quote_swap(
+			asset: u128,
+			kind: amm::SwapKind,
+			amount_in: Balance,
+		) -> Option<amm::SwapQuote<Balance>> {
+			Amm::quote_swap(asset, kind, amount_in)
+		}
+	}
+
+	impl asset_registry_runtime_api::AssetRegistryApi<Block, u128> for Runtime {
+		fn registered_assets() -> Vec<(u128, asset_registry::AssetMetadata<Vec<u8>>)> {
+			AssetRegistry::registered_assets()
+		}
+	}
+
+	impl vesting_runtime_api::VestingApi<Block, AccountId, BlockNumber, Balance> for Runtime {
+		fn vesting_info(who: AccountId) -> vesting_runtime_api::VestingInfo<BlockNumber, Balance> {
+			let vesting_lock = Balances::locks(&who)
+				.into_iter()
+				.find(|lock| lock.id == orml_vesting::VESTING_LOCK_ID)
+				.map_or(0, |lock| lock.amount);
+			vesting_runtime_api::VestingInfo::new(
+				OrmlVesting::vesting_schedules(&who).into_inner(),
+				SusbtrateBlockNumberProvider::current_block_number(),
+				vesting_lock,
+			)
+		}
+	}
+
+	impl pallet_mmr::primitives::MmrApi<Block, mmr::Hash> for Runtime {
+		fn generate_proof(
+			leaf_index: pallet_mmr::primitives::LeafIndex,
+		) -> Result<(mmr::EncodableOpaqueLeaf, mmr::Proof<mmr::Hash>), mmr::Error> {
+			Mmr::generate_proof(leaf_index)
+				.map(|(leaf, proof)| (mmr::EncodableOpaqueLeaf::from_leaf(&leaf), proof))
+		}
+
+		fn verify_proof(
+			leaf: mmr::EncodableOpaqueLeaf,
+			proof: mmr::Proof<mmr::Hash>,
+		) -> Result<(), mmr::Error> {
+			let leaf: mmr::Leaf = leaf
+				.into_opaque_leaf()
+				.try_decode()
+				.ok_or(mmr::Error::Verify)?;
+			Mmr::verify_leaf(leaf, proof)
+		}
+
+		fn verify_proof_stateless(
+			root: mmr::Hash,
+			leaf: mmr::EncodableOpaqueLeaf,
+			proof: mmr::Proof<mmr::Hash>,
+		) -> Result<(), mmr::Error> {
+			let node = mmr::DataOrHash::Data(leaf.into_opaque_leaf());
+			pallet_mmr::verify_leaf_proof::<mmr::Hashing, _>(root, node, proof)
+		}
+	}
+
+	impl pallet_contracts_rpc_runtime_api::ContractsApi<
+		Block, AccountId, Balance, BlockNumber, Hash,
+	>
+		for Runtime
+	{
+		fn call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: u64,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+		) -> pallet_contracts_primitives::ContractExecResult<Balance> {
+			Contracts::bare_call(origin, dest, value, gas_limit, storage_deposit_limit, input_data, true)
+		}
+
+		fn instantiate(
+			origin: AccountId,
+			value: Balance,
+			gas_limit: u64,
+			storage_deposit_limit: Option<Balance>,
+			code: pallet_contracts_primitives::Code<Hash>,
+			data: Vec<u8>,
+			salt: Vec<u8>,
+		) -> pallet_contracts_primitives::ContractInstantiateResult<AccountId, Balance> {
+			Contracts::bare_instantiate(origin, value, gas_limit, storage_deposit_limit, code, data, salt, true)
+		}
+
+		fn upload_code(
+			origin: AccountId,
+			code: Vec<u8>,
+			storage_deposit_limit: Option<Balance>,
+		) -> pallet_contracts_primitives::CodeUploadResult<Hash, Balance> {
+			Contracts::bare_upload_code(origin, code, storage_deposit_limit)
+		}
+
+		fn get_storage(
+			address: AccountId,
+			key: [u8; 32],
+		) -> pallet_contracts_primitives::GetStorageResult {
+			Contracts::get_storage(address, key)
 		}
 	}
 
@@ -1437,7 +2051,7 @@ impl_runtime_apis! {
 		fn on_runtime_upgrade() -> (Weight, Weight) {
 			// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
 			// have a backtrace here. If any of the pre/post migration checks fail, we shall stop
-			// right here and right now.
+			// right here and right now. This runs the checks of `Migrations` and of every pallet.
 			let weight = Executive::try_runtime_upgrade().unwrap();
 			(weight, RuntimeBlockWeights::get().max_block)
 		}
@@ -1475,12 +2089,23 @@ impl_runtime_apis! {
 			list_benchmark!(list,extra, pallet_proxy, Proxy);
 			list_benchmark!(list,extra, pallet_scheduler, Scheduler);
 			list_benchmark!(list, extra, pallet_staking, Staking);
+			list_benchmark!(list, extra, pallet_bags_list, BagsList);
+			list_benchmark!(list, extra, pallet_mmr, Mmr);
+			list_benchmark!(list, extra, pallet_contracts, Contracts);
 			list_benchmark!(list,extra, pallet_timestamp, Timestamp);
 			list_benchmark!(list,extra, pallet_treasury, Treasury);
 			list_benchmark!(list,extra, pallet_utility, Utility);
 
 			list_benchmark!(list,extra, pallet_election_provider_multi_phase, ElectionProviderMultiPhase);
 			list_benchmark!(list,extra,  pdex_migration, PDEXMigration);
+			list_benchmark!(list,extra, orderbook, Orderbook);
+			list_benchmark!(list,extra, amm, Amm);
+			list_benchmark!(list,extra, asset_registry, AssetRegistry);
+			list_benchmark!(list,extra, feeless, Feeless);
+			list_benchmark!(list,extra, asset_fees, AssetFees);
+			list_benchmark!(list,extra, tx_pause, TxPause);
+			list_benchmark!(list,extra, fee_distribution, FeeDistribution);
+			list_benchmark!(list,extra, fee_discounts, FeeDiscounts);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -1536,12 +2161,23 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_scheduler, Scheduler);
 	 //       add_benchmark!(params, batches, pallet_session, SessionBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_staking, Staking);
+			add_benchmark!(params, batches, pallet_bags_list, BagsList);
+			add_benchmark!(params, batches, pallet_mmr, Mmr);
+			add_benchmark!(params, batches, pallet_contracts, Contracts);
 			add_benchmark!(params, batches, pallet_democracy, Democracy);
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_treasury, Treasury);
 			add_benchmark!(params, batches, pallet_utility, Utility);
 			add_benchmark!(params, batches, pdex_migration, PDEXMigration);
+			add_benchmark!(params, batches, orderbook, Orderbook);
+			add_benchmark!(params, batches, amm, Amm);
+			add_benchmark!(params, batches, asset_registry, AssetRegistry);
+			add_benchmark!(params, batches, feeless, Feeless);
+			add_benchmark!(params, batches, asset_fees, AssetFees);
+			add_benchmark!(params, batches, tx_pause, TxPause);
+			add_benchmark!(params, batches, fee_distribution, FeeDistribution);
+			add_benchmark!(params, batches, fee_discounts, FeeDiscounts);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -1551,6 +2187,7 @@ impl_runtime_apis! {
 
 #[cfg(test)]
 mod tests {
+	use frame_support::assert_ok;
 	use frame_system::offchain::CreateSignedTransaction;
 
 	use super::*;
@@ -1565,4 +2202,473 @@ mod tests {
 
 		is_submit_signed_transaction::<Runtime>();
 	}
+
+	#[test]
+	fn block_production_can_not_be_paused() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			for (pallet_name, call_name) in [
+				(&b"Timestamp"[..], &b"set"[..]),
+				(b"Babe", b"plan_config_change"),
+				(b"Authorship", b"set_uncles"),
+				(b"Grandpa", b"note_stalled"),
+				(b"ImOnline", b"heartbeat"),
+				(b"ElectionProviderMultiPhase", b"submit_unsigned"),
+			] {
+				assert_eq!(
+					TxPause::pause_call(Origin::root(), pallet_name.to_vec(), call_name.to_vec()),
+					Err(tx_pause::Error::<Runtime>::Unpausable.into())
+				);
+			}
+			let set = Call::Timestamp(pallet_timestamp::Call::set { now: 0 });
+			assert!(BaseFilter::contains(&set));
+		});
+	}
+
+	#[test]
+	fn asset_fees_follow_the_fee_distribution() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let owner = AccountId::new([1; 32]);
+			assert_ok!(Assets::force_create(Origin::root(), 1, owner.into(), true, 1));
+			assert_ok!(FeeDistribution::set_fee_distribution(
+				Origin::root(),
+				fee_distribution::Distribution {
+					treasury: Percent::from_percent(70),
+					author: Percent::from_percent(20),
+					burn: Percent::from_percent(10),
+				}
+			));
+			let issue = |amount| <Assets as fungibles::Balanced<AccountId>>::issue(1, amount);
+			// Without a block author, the treasury also takes the author share.
+			DealWithAssetFees::on_unbalanced(issue(1_000));
+			assert_eq!(Assets::balance(1, TreasuryAccount::get()), 900);
+			assert_eq!(Assets::total_supply(1), 900);
+			// Tips follow the default tip distribution, everything to the treasury.
+			DealWithAssetTips::on_unbalanced(issue(100));
+			assert_eq!(Assets::balance(1, TreasuryAccount::get()), 1_000);
+			assert_eq!(Assets::total_supply(1), 1_000);
+		});
+	}
+
+	#[test]
+	fn assets_are_only_created_through_registry() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let create = Call::Assets(pallet_assets::Call::create {
+				id: 1,
+				admin: AccountId::new([0; 32]).into(),
+				min_balance: 1,
+			});
+			assert!(!BaseFilter::contains(&create));
+			let register = Call::AssetRegistry(asset_registry::Call::update_metadata {
+				asset: 1,
+				name: vec![],
+				symbol: vec![],
+				decimals: 0,
+			});
+			assert!(BaseFilter::contains(&register));
+		});
+	}
+
+	#[test]
+	fn amm_share_asset_ids_are_reserved() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let share = AmmShareAssetId::convert(1);
+			assert!(AmmShareAssetIds::contains(&share));
+			assert!(!AmmShareAssetIds::contains(&1));
+			let force_create = |id| {
+				Call::Assets(pallet_assets::Call::force_create {
+					id,
+					owner: AccountId::new([0; 32]).into(),
+					is_sufficient: false,
+					min_balance: 1,
+				})
+			};
+			assert!(!BaseFilter::contains(&force_create(share)));
+			assert!(BaseFilter::contains(&force_create(1)));
+			assert_eq!(
+				AssetRegistry::register_asset(
+					Origin::root(),
+					share,
+					AccountId::new([0; 32]),
+					true,
+					1,
+					vec![],
+					vec![],
+					0,
+					asset_registry::AssetOrigin::Polkadex,
+				),
+				Err(asset_registry::Error::<Runtime>::ReservedAssetId.into())
+			);
+		});
+	}
+}
+
+#[cfg(test)]
+mod proxy_type_tests {
+	use codec::Decode;
+	use frame_support::traits::InstanceFilter;
+	use sp_consensus_babe::{digests::NextConfigDescriptor, AllowedSlots};
+	use sp_core::{offchain::OpaqueNetworkState, OpaquePeerId, H256};
+
+	use super::*;
+
+	const ALL: [ProxyType; 8] = [
+		ProxyType::Any,
+		ProxyType::NonTransfer,
+		ProxyType::Governance,
+		ProxyType::Staking,
+		ProxyType::IdentityJudgement,
+		ProxyType::CancelProxy,
+		ProxyType::Bridge,
+		ProxyType::Treasury,
+	];
+
+	fn account() -> AccountId {
+		AccountId::new([1; 32])
+	}
+
+	fn remark() -> Call {
+		Call::System(frame_system::Call::remark { remark: vec![] })
+	}
+
+	/// One or more calls of every pallet, with the proxy types other than `Any` that may make
+	/// them.
+	fn calls() -> Vec<(Call, Vec<ProxyType>)> {
+		use ProxyType::*;
+		vec![
+			(remark(), vec![NonTransfer]),
+			(
+				Call::Utility(pallet_utility::Call::batch { calls: vec![] }),
+				vec![
+					NonTransfer,
+					Governance,
+					Staking,
+					IdentityJudgement,
+					CancelProxy,
+					Bridge,
+					Treasury,
+				],
+			),
+			(
+				Call::Babe(pallet_babe::Call::plan_config_change {
+					config: NextConfigDescriptor::V1 {
+						c: (1, 4),
+						allowed_slots: AllowedSlots::PrimarySlots,
+					},
+				}),
+				vec![NonTransfer],
+			),
+			(Call::Timestamp(pallet_timestamp::Call::set { now: 0 }), vec![NonTransfer]),
+			(
+				Call::Authorship(pallet_authorship::Call::set_uncles { new_uncles: vec![] }),
+				vec![NonTransfer],
+			),
+			(Call::Indices(pallet_indices::Call::claim { index: 0 }), vec![NonTransfer]),
+			(Call::Indices(pallet_indices::Call::transfer { new: account(), index: 0 }), vec![]),
+			(
+				Call::Balances(pallet_balances::Call::transfer {
+					dest: account().into(),
+					value: 1,
+				}),
+				vec![],
+			),
+			(
+				Call::ElectionProviderMultiPhase(
+					pallet_election_provider_multi_phase::Call::set_minimum_untrusted_score {
+						maybe_next_score: None,
+					},
+				),
+				vec![NonTransfer],
+			),
+			(Call::Staking(pallet_staking::Call::chill {}), vec![NonTransfer, Staking]),
+			(Call::Session(pallet_session::Call::purge_keys {}), vec![NonTransfer, Staking]),
+			(
+				Call::BagsList(pallet_bags_list::Call::rebag { dislocated: account() }),
+				vec![NonTransfer, Staking],
+			),
+			(
+				Call::Contracts(pallet_contracts::Call::remove_code { code_hash: Hash::default() }),
+				vec![],
+			),
+			(
+				Call::Council(pallet_collective::Call::set_members {
+					new_members: vec![],
+					prime: None,
+					old_count: 0,
+				}),
+				vec![NonTransfer, Governance],
+			),
+			(
+				Call::TechnicalCommittee(pallet_collective::Call::set_members {
+					new_members: vec![],
+					prime: None,
+					old_count: 0,
+				}),
+				vec![NonTransfer, Governance],
+			),
+			(
+				Call::Elections(pallet_elections_phragmen::Call::remove_voter {}),
+				vec![NonTransfer, Governance],
+			),
+			(
+				Call::TechnicalMembership(pallet_membership::Call::add_member { who: account() }),
+				vec![NonTransfer],
+			),
+			(
+				Call::Grandpa(pallet_grandpa::Call::note_stalled {
+					delay: 0,
+					best_finalized_block_number: 0,
+				}),
+				vec![NonTransfer],
+			),
+			(
+				Call::Treasury(pallet_treasury::Call::reject_proposal { proposal_id: 0 }),
+				vec![NonTransfer, Governance, Treasury],
+			),
+			(
+				Call::ImOnline(pallet_im_online::Call::heartbeat {
+					heartbeat: pallet_im_online::Heartbeat {
+						block_number: 0,
+						network_state: OpaqueNetworkState {
+							peer_id: OpaquePeerId(vec![]),
+							external_addresses: vec![],
+						},
+						session_index: 0,
+						authority_index: 0,
+						validators_len: 0,
+					},
+					signature: Decode::decode(&mut &[0u8; 64][..]).unwrap(),
+				}),
+				vec![NonTransfer],
+			),
+			(Call::Identity(pallet_identity::Call::clear_identity {}), vec![NonTransfer]),
+			(
+				Call::Identity(pallet_identity::Call::provide_judgement {
+					reg_index: 0,
+					target: account().into(),
+					judgement: pallet_identity::Judgement::Reasonable,
+				}),
+				vec![NonTransfer, IdentityJudgement],
+			),
+			(Call::Recovery(pallet_recovery::Call::remove_recovery {}), vec![]),
+			(
+				Call::Scheduler(pallet_scheduler::Call::cancel { when: 0, index: 0 }),
+				vec![NonTransfer],
+			),
+			(
+				Call::Proxy(pallet_proxy::Call::add_proxy {
+					delegate: account(),
+					proxy_type: ProxyType::Any,
+					delay: 0,
+				}),
+				vec![],
+			),
+			(
+				Call::Proxy(pallet_proxy::Call::reject_announcement {
+					delegate: account(),
+					call_hash: H256::zero(),
+				}),
+				vec![CancelProxy],
+			),
+			(
+				Call::Multisig(pallet_multisig::Call::as_multi_threshold_1 {
+					other_signatories: vec![],
+					call: Box::new(remark()),
+				}),
+				vec![],
+			),
+			(
+				Call::Bounties(pallet_bounties::Call::propose_bounty {
+					value: 0,
+					description: vec![],
+				}),
+				vec![NonTransfer, Governance, Treasury],
+			),
+			(
+				Call::Democracy(pallet_democracy::Call::clear_public_proposals {}),
+				vec![NonTransfer, Governance],
+			),
+			(
+				Call::Preimage(pallet_preimage::Call::note_preimage { bytes: vec![] }),
+				vec![NonTransfer],
+			),
+			(
+				Call::ChildBounties(pallet_child_bounties::Call::accept_curator {
+					parent_bounty_id: 0,
+					child_bounty_id: 0,
+				}),
+				vec![NonTransfer, Governance, Treasury],
+			),
+			(Call::OrmlVesting(orml_vesting::Call::claim {}), vec![]),
+			(
+				Call::PDEXMigration(pdex_migration::pallet::Call::mint {
+					beneficiary: account(),
+					amount: 1,
+					eth_tx: H256::zero(),
+				}),
+				vec![NonTransfer, Bridge],
+			),
+			(Call::PDEXMigration(pdex_migration::pallet::Call::unlock {}), vec![NonTransfer]),
+			(
+				Call::PDEXMigration(pdex_migration::pallet::Call::set_relayer_status {
+					relayer: account(),
+					status: true,
+				}),
+				vec![],
+			),
+			(
+				Call::Assets(pallet_assets::Call::transfer {
+					id: 1,
+					target: account().into(),
+					amount: 1,
+				}),
+				vec![],
+			),
+			(Call::Orderbook(orderbook::Call::cancel_order { order_id: 0 }), vec![]),
+			(
+				Call::Amm(amm::Call::remove_liquidity {
+					asset: 1,
+					shares: 1,
+					min_native_amount: 0,
+					min_asset_amount: 0,
+					deadline: 0,
+				}),
+				vec![],
+			),
+			(
+				Call::AssetRegistry(asset_registry::Call::update_metadata {
+					asset: 1,
+					name: vec![],
+					symbol: vec![],
+					decimals: 0,
+				}),
+				vec![NonTransfer],
+			),
+			(
+				Call::Feeless(feeless::Call::set_minimum_stake { minimum_stake: None }),
+				vec![NonTransfer],
+			),
+			(
+				Call::AssetFees(asset_fees::Call::set_fee_asset { asset: 1, rate: None }),
+				vec![NonTransfer],
+			),
+			(
+				Call::TxPause(tx_pause::Call::unpause_call {
+					pallet_name: vec![],
+					call_name: vec![],
+				}),
+				vec![NonTransfer],
+			),
+			(
+				Call::FeeDistribution(fee_distribution::Call::set_fee_distribution {
+					distribution: Default::default(),
+				}),
+				vec![NonTransfer],
+			),
+			(
+				Call::FeeDiscounts(fee_discounts::Call::set_tiers { tiers: vec![] }),
+				vec![NonTransfer],
+			),
+		]
+	}
+
+	/// Fails to compile when a pallet with calls is added to the runtime, so that its calls get
+	/// added to `calls` above.
+	#[allow(dead_code)]
+	fn every_pallet_is_covered(c: &Call) {
+		match c {
+			Call::System(..) |
+			Call::Utility(..) |
+			Call::Babe(..) |
+			Call::Timestamp(..) |
+			Call::Authorship(..) |
+			Call::Indices(..) |
+			Call::Balances(..) |
+			Call::ElectionProviderMultiPhase(..) |
+			Call::Staking(..) |
+			Call::Session(..) |
+			Call::Council(..) |
+			Call::TechnicalCommittee(..) |
+			Call::Elections(..) |
+			Call::TechnicalMembership(..) |
+			Call::Grandpa(..) |
+			Call::Treasury(..) |
+			Call::ImOnline(..) |
+			Call::Identity(..) |
+			Call::Recovery(..) |
+			Call::Scheduler(..) |
+			Call::Proxy(..) |
+			Call::Multisig(..) |
+			Call::Bounties(..) |
+			Call::Democracy(..) |
+			Call::Preimage(..) |
+			Call::ChildBounties(..) |
+			Call::OrmlVesting(..) |
+			Call::PDEXMigration(..) |
+			Call::Assets(..) |
+			Call::Orderbook(..) |
+			Call::Amm(..) |
+			Call::AssetRegistry(..) |
+			Call::Feeless(..) |
+			Call::AssetFees(..) |
+			Call::TxPause(..) |
+			Call::BagsList(..) |
+			Call::Contracts(..) |
+			Call::FeeDistribution(..) |
+			Call::FeeDiscounts(..) => (),
+		}
+	}
+
+	#[test]
+	fn proxy_types_filter_every_pallet() {
+		for (call, allowed) in calls() {
+			for proxy_type in ALL {
+				assert_eq!(
+					proxy_type.filter(&call),
+					proxy_type == ProxyType::Any || allowed.contains(&proxy_type),
+					"{:?} filtering {:?}",
+					proxy_type,
+					call
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn supersets_allow_every_call_of_their_subsets() {
+		let calls = calls();
+		for proxy_type in ALL {
+			for other in ALL {
+				if proxy_type.is_superset(&other) {
+					for (call, _) in &calls {
+						assert!(
+							!other.filter(call) || proxy_type.filter(call),
+							"{:?} is a superset of {:?} but does not allow {:?}",
+							proxy_type,
+							other,
+							call
+						);
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn supersets_are_declared() {
+		let calls = calls();
+		for proxy_type in ALL {
+			for other in ALL {
+				let allows_all =
+					calls.iter().all(|(call, _)| !other.filter(call) || proxy_type.filter(call));
+				if allows_all {
+					assert!(
+						proxy_type.is_superset(&other),
+						"{:?} allows every call of {:?}",
+						proxy_type,
+						other
+					);
+				}
+			}
+		}
+	}
 }
@@ -0,0 +1,156 @@
+//! Storage migrations of the runtime.
+//!
+//! Every migration is an `OnRuntimeUpgrade` registered in [`crate::Migrations`]:
+//!
+//! - It must be safe to run more than once. Migrations of pallet storage check the on-chain
+//!   `StorageVersion` of the pallet and set the new one, other migrations check that the storage
+//!   they change is still in the old layout.
+//! - It implements `pre_upgrade` and `post_upgrade` to check the storage before and after the
+//!   upgrade with `try-runtime on-runtime-upgrade`.
+//! - It is removed from `Migrations`, and from this module, once the runtime it was released with
+//!   is enacted on every network.
+
+use frame_election_provider_support::SortedListProvider;
+use frame_support::{
+	storage::unhashed,
+	traits::{Get, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_io::hashing::twox_128;
+use sp_std::boxed::Box;
+
+use crate::{AccountId, BagsList, Runtime, Staking};
+
+/// Removes the storage of `pallet_sudo` once it is no longer part of the runtime.
+///
+/// Every root only call is reachable through the council or the technical committee from the
+/// same runtime upgrade. On a chain without council members, the migration keeps the storage,
+/// and the sudo key it records, and runs again with a later upgrade once a council is elected.
+pub struct RemoveSudo;
+
+impl RemoveSudo {
+	const PALLET_NAME: &'static [u8] = b"Sudo";
+
+	fn has_storage() -> bool {
+		let prefix = twox_128(Self::PALLET_NAME);
+		sp_io::storage::next_key(&prefix).map_or(false, |key| key.starts_with(&prefix))
+	}
+}
+
+impl OnRuntimeUpgrade for RemoveSudo {
+	fn on_runtime_upgrade() -> Weight {
+		if !Self::has_storage() {
+			log::info!(target: "runtime::migrations", "Sudo storage already removed");
+			return <Runtime as frame_system::Config>::DbWeight::get().reads(1)
+		}
+		if crate::Council::members().is_empty() {
+			log::error!(
+				target: "runtime::migrations",
+				"Council has no members to take over from sudo, keeping Sudo storage"
+			);
+			return <Runtime as frame_system::Config>::DbWeight::get().reads(2)
+		}
+		let removed = match unhashed::kill_prefix(&twox_128(Self::PALLET_NAME), None) {
+			sp_io::KillStorageResult::AllRemoved(removed) => removed,
+			sp_io::KillStorageResult::SomeRemaining(removed) => removed,
+		};
+		log::info!(target: "runtime::migrations", "Removed {} Sudo storage items", removed);
+		<Runtime as frame_system::Config>::DbWeight::get().reads_writes(2, removed.into())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		frame_support::ensure!(
+			!Self::has_storage() || !crate::Council::members().is_empty(),
+			"Council must have members to take over from sudo"
+		);
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		frame_support::ensure!(!Self::has_storage(), "Sudo storage must be removed");
+		Ok(())
+	}
+}
+
+/// Inserts the nominators and validators of staking into the bags list, which replaces the
+/// nominators and validators maps as the voter list of staking.
+pub struct PopulateVoterList;
+
+impl PopulateVoterList {
+	fn voters() -> u32 {
+		pallet_staking::Nominators::<Runtime>::count()
+			.saturating_add(pallet_staking::Validators::<Runtime>::count())
+	}
+}
+
+impl OnRuntimeUpgrade for PopulateVoterList {
+	fn on_runtime_upgrade() -> Weight {
+		if BagsList::count() != 0 {
+			log::info!(target: "runtime::migrations", "Voter list already populated");
+			return <Runtime as frame_system::Config>::DbWeight::get().reads(1)
+		}
+		let voters = pallet_staking::Nominators::<Runtime>::iter_keys()
+			.chain(pallet_staking::Validators::<Runtime>::iter_keys());
+		let inserted = <BagsList as SortedListProvider<AccountId>>::unsafe_regenerate(
+			voters,
+			Box::new(|who| Staking::weight_of(who)),
+		);
+		log::info!(
+			target: "runtime::migrations",
+			"Inserted {} voters into the bags list",
+			inserted
+		);
+		// Reading the voter, its ledger and its bag, and writing the node and the bag.
+		<Runtime as frame_system::Config>::DbWeight::get()
+			.reads_writes(3, 2)
+			.saturating_mul(inserted.into())
+			.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads(1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		frame_support::ensure!(
+			BagsList::count() == 0 || BagsList::count() == Self::voters(),
+			"Bags list must be empty or contain every voter"
+		);
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		frame_support::ensure!(
+			BagsList::count() == Self::voters(),
+			"Bags list must contain every nominator and validator"
+		);
+		<BagsList as SortedListProvider<AccountId>>::sanity_check()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use frame_support::assert_ok;
+
+	use super::*;
+	use crate::{Council, Origin};
+
+	#[test]
+	fn sudo_storage_is_kept_until_there_is_a_council() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let key = [twox_128(RemoveSudo::PALLET_NAME), twox_128(b"Key")].concat();
+			unhashed::put(&key, &AccountId::new([1; 32]));
+			RemoveSudo::on_runtime_upgrade();
+			assert!(RemoveSudo::has_storage());
+
+			assert_ok!(Council::set_members(
+				Origin::root(),
+				vec![AccountId::new([2; 32])],
+				None,
+				0
+			));
+			RemoveSudo::on_runtime_upgrade();
+			assert!(!RemoveSudo::has_storage());
+		});
+	}
+}
@@ -0,0 +1,220 @@
+//! Autogenerated voter bag thresholds.
+//!
+//! Generated with `generate-bags` for the node runtime.
+//! Arguments
+//! Total issuance: 20_000_000 PDEX, 20000000000000000000 units
+//! Minimum balance: 1 PDEX, 1000000000000 units
+
+/// Existential weight for this runtime.
+#[cfg(any(test, feature = "std"))]
+#[allow(unused)]
+pub const EXISTENTIAL_WEIGHT: u64 = 1_000_000_000_000;
+
+/// Constant ratio between bags for this runtime.
+#[cfg(any(test, feature = "std"))]
+#[allow(unused)]
+pub const CONSTANT_RATIO: f64 = 1.0877075909235028;
+
+/// Upper thresholds delimiting the bag list.
+pub const THRESHOLDS: [u64; 200] = [
+	1_000_000_000_000,
+	1_087_707_590_924,
+	1_183_107_803_353,
+	1_286_875_338_588,
+	1_399_744_074_354,
+	1_522_512_255_025,
+	1_656_048_137_065,
+	1_801_296_129_620,
+	1_959_283_473_689,
+	2_131_127_507_102,
+	2_318_043_566_701,
+	2_521_353_583_592,
+	2_742_495_432_275,
+	2_983_033_099_759,
+	3_244_667_746_584,
+	3_529_249_737_984,
+	3_838_791_730_270,
+	4_175_482_904_989,
+	4_541_704_451_528,
+	4_940_046_407_658,
+	5_373_325_977_124,
+	5_844_607_453_824,
+	6_357_223_893_492,
+	6_914_800_686_152,
+	7_521_281_196_051,
+	8_180_954_650_415,
+	8_898_486_474_257,
+	9_678_951_285_779,
+	10_527_868_785_721,
+	11_451_242_794_475,
+	12_455_603_713_059,
+	13_548_054_708_229,
+	14_736_321_948_388,
+	16_028_809_245_554,
+	17_434_657_489_854,
+	18_963_809_296_866,
+	20_627_079_325_027,
+	22_436_230_760_413,
+	24_404_058_509_813,
+	26_544_479_690_465,
+	28_872_632_056_434,
+	31_404_981_057_725,
+	34_159_436_289_296,
+	37_155_478_153_535,
+	40_414_295_631_992,
+	43_958_936_140_744,
+	47_814_468_529_209,
+	52_008_160_375_194,
+	56_569_670_830_065,
+	61_531_260_377_906,
+	66_928_018_992_139,
+	72_798_114_303_222,
+	79_183_061_532_531,
+	86_128_017_101_497,
+	93_682_097_992_488,
+	101_898_729_120_069,
+	110_836_021_169_357,
+	120_557_181_573_668,
+	131_130_961_538_022,
+	142_632_142_270_004,
+	155_142_063_856_764,
+	168_749_200_528_541,
+	183_549_786_377_166,
+	199_648_495_954_831,
+	217_159_184_566_530,
+	236_205_693_491_773,
+	256_922_725_830_352,
+	279_456_799_166_432,
+	303_967_281_788_513,
+	330_627_519_793_749,
+	359_626_063_047_871,
+	391_167_998_671_104,
+	425_476_401_480_914,
+	462_793_911_649_606,
+	503_384_450_734_457,
+	547_535_088_216_727,
+	595_558_071_750_304,
+	647_793_035_478_570,
+	704_609_402_037_419,
+	766_408_995_232_171,
+	833_628_881_866_087,
+	906_744_462_818_815,
+	986_272_835_235_879,
+	1_072_776_449_607_711,
+	1_166_867_087_602_272,
+	1_269_210_188_783_791,
+	1_380_529_556_817_582,
+	1_501_612_478_444_743,
+	1_633_315_291_429_802,
+	1_776_569_440_859_629,
+	1_932_388_066_625_742,
+	2_101_873_168_678_811,
+	2_286_223_400_730_379,
+	2_486_742_547_521_379,
+	2_704_848_745_611_454,
+	2_942_084_512_901_493,
+	3_200_127_657_821_430,
+	3_480_803_145_336_619,
+	3_786_096_003_693_045,
+	4_118_165_363_182_063,
+	4_479_359_726_211_374,
+	4_872_233_576_677_136,
+	5_299_565_446_104_090,
+	5_764_377_564_323_318,
+	6_269_957_233_663_606,
+	6_819_880_077_821_632,
+	7_418_035_329_834_558,
+	8_068_653_337_999_778,
+	8_776_335_484_272_618,
+	9_546_086_726_734_622,
+	10_383_350_996_283_342,
+	11_294_049_697_880_506,
+	12_284_623_588_651_920,
+	13_362_078_329_014_616,
+	14_534_034_028_983_632,
+	15_808_779_140_065_998,
+	17_195_329_073_882_910,
+	18_703_489_962_090_048,
+	20_343_928_008_526_884,
+	22_128_244_924_075_952,
+	24_069_059_977_731_884,
+	26_180_099_244_172_044,
+	28_476_292_679_016_588,
+	30_973_879_708_325_712,
+	33_690_524_079_097_328,
+	36_645_438_783_025_216,
+	39_859_521_937_019_056,
+	43_355_504_581_477_512,
+	47_158_111_441_591_792,
+	51_294_235_788_635_880,
+	55_793_129_637_919_248,
+	60_686_610_628_543_824,
+	66_009_287_048_086_040,
+	71_798_802_593_651_640,
+	78_096_102_600_332_976,
+	84_945_723_619_922_880,
+	92_396_108_397_880_000,
+	100_499_948_476_164_880,
+	109_314_556_844_945_456,
+	118_902_273_278_685_920,
+	129_330_905_223_287_440,
+	140_674_207_352_377_840,
+	153_012_403_184_328_192,
+	166_432_752_449_041_312,
+	181_030_168_217_114_432,
+	196_907_888_155_913_984,
+	214_178_204_659_903_712,
+	232_963_259_018_944_800,
+	253_395_905_241_184_416,
+	275_620_649_639_768_896,
+	299_794_672_828_443_840,
+	326_088_941_353_926_336,
+	354_689_416_826_874_624,
+	385_798_371_102_821_888,
+	419_635_816_814_461_888,
+	456_441_063_372_474_688,
+	496_474_409_439_436_288,
+	540_018_983_846_537_984,
+	587_382_747_972_675_840,
+	638_900_673_747_386_240,
+	694_937_112_681_172_352,
+	755_888_372_677_772_800,
+	822_185_520_852_427_136,
+	894_297_432_178_578_816,
+	972_734_105_524_036_608,
+	1_058_050_270_528_678_144,
+	1_150_849_310_832_708_864,
+	1_251_787_531_401_819_136,
+	1_361_578_800_129_151_232,
+	1_480_999_596_540_992_512,
+	1_610_894_503_312_282_624,
+	1_752_182_179_429_715_456,
+	1_905_861_857_246_588_416,
+	2_073_020_409_378_679_552,
+	2_254_840_035_420_537_088,
+	2_452_606_622_845_137_920,
+	2_667_718_841_217_912_832,
+	2_901_698_034_042_374_144,
+	3_156_198_978_195_695_104,
+	3_433_021_587_048_460_800,
+	3_734_123_640_036_861_440,
+	4_061_634_628_714_995_712,
+	4_417_870_817_211_063_808,
+	4_805_351_623_599_892_480,
+	5_226_817_438_046_181_376,
+	5_685_249_003_734_166_528,
+	6_183_888_497_651_934_208,
+	6_726_262_460_320_543_744,
+	7_316_206_736_634_450_944,
+	7_957_893_604_202_960_896,
+	8_655_861_281_053_153_280,
+	9_415_046_021_382_350_848,
+	10_240_817_026_351_706_112,
+	11_139_014_416_821_403_648,
+	12_115_990_536_582_975_488,
+	13_178_654_878_198_626_304,
+	14_334_522_949_177_696_256,
+	15_591_769_424_087_736_320,
+	16_959_285_958_509_201_408,
+	18_446_744_073_709_551_615,
+];
@@ -0,0 +1,49 @@
+//! Autogenerated weights for `asset_registry`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-05-06, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/polkadex-node
+// benchmark
+// --chain
+// dev
+// --execution
+// wasm
+// --wasm-execution
+// compiled
+// --pallet=asset_registry
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output=benchout/asset_registry.rs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for asset_registry.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> asset_registry::WeightInfo for WeightInfo<T> {
+	// Storage: AssetRegistry RegisteredAssets (r:1 w:1)
+	// Storage: AssetRegistry Erc20Assets (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	fn register_asset() -> Weight {
+		(37_412_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: AssetRegistry RegisteredAssets (r:1 w:1)
+	fn update_metadata() -> Weight {
+		(18_655_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,50 @@
+//! Autogenerated weights for `feeless`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-05-06, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/polkadex-node
+// benchmark
+// --chain
+// dev
+// --execution
+// wasm
+// --wasm-execution
+// compiled
+// --pallet=feeless
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output=benchout/feeless.rs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for feeless.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> feeless::WeightInfo for WeightInfo<T> {
+	// Storage: Feeless FeelessCalls (r:0 w:1)
+	fn set_call_status() -> Weight {
+		(14_871_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Feeless Registered (r:0 w:1)
+	fn set_registration() -> Weight {
+		(12_634_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Feeless MinimumStake (r:0 w:1)
+	fn set_minimum_stake() -> Weight {
+		(10_902_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
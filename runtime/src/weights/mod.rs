@@ -14,6 +14,14 @@
 // limitations under the License.
 
 //! A list of the different weight modules for our runtime.
+pub mod amm;
+pub mod asset_fees;
+pub mod asset_registry;
+pub mod fee_discounts;
+pub mod fee_distribution;
+pub mod feeless;
+pub mod orderbook;
 pub mod pallet_babe;
 pub mod pallet_grandpa;
 pub mod pdex_migration;
+pub mod tx_pause;
@@ -0,0 +1,89 @@
+//! Autogenerated weights for `orderbook`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-05-02, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/polkadex-node
+// benchmark
+// --chain
+// dev
+// --execution
+// wasm
+// --wasm-execution
+// compiled
+// --pallet=orderbook
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output=benchout/orderbook.rs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for orderbook.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> orderbook::WeightInfo for WeightInfo<T> {
+	// Storage: Orderbook TradingPairs (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn register_trading_pair() -> Weight {
+		(51_384_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Orderbook TradingPairs (r:1 w:1)
+	fn set_trading_pair_status() -> Weight {
+		(15_902_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Orderbook TradingPairs (r:1 w:0)
+	// Storage: Orderbook NextOrderId (r:1 w:1)
+	// Storage: Orderbook PendingOrders (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: Orderbook Orders (r:0 w:1)
+	fn place_limit_order() -> Weight {
+		(60_417_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Orderbook Orders (r:1 w:1)
+	// Storage: Orderbook PendingOrders (r:1 w:1)
+	// Storage: Orderbook PriceLevels (r:1 w:1)
+	// Storage: Orderbook BidPrices (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	fn cancel_order() -> Weight {
+		(57_266_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Orderbook PendingOrders (r:1 w:1)
+	// Storage: Orderbook Orders (r:2 w:2)
+	// Storage: Orderbook TradingPairs (r:1 w:0)
+	// Storage: Orderbook AskPrices (r:1 w:1)
+	// Storage: Orderbook PriceLevels (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:3 w:3)
+	// Storage: System Account (r:3 w:3)
+	fn match_orders(f: u32, ) -> Weight {
+		(3_861_000 as Weight)
+			// Standard Error: 21_000
+			.saturating_add((96_512_000 as Weight).saturating_mul(f as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((12 as Weight).saturating_mul(f as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((11 as Weight).saturating_mul(f as Weight)))
+	}
+}
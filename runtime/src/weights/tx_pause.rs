@@ -0,0 +1,47 @@
+//! Autogenerated weights for `tx_pause`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-05-06, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/polkadex-node
+// benchmark
+// --chain
+// dev
+// --execution
+// wasm
+// --wasm-execution
+// compiled
+// --pallet=tx_pause
+// --extrinsic=*
+// --steps
+// 50
+// --repeat
+// 20
+// --output=benchout/tx_pause.rs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for tx_pause.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> tx_pause::WeightInfo for WeightInfo<T> {
+	// Storage: TxPause PausedCalls (r:1 w:1)
+	fn pause_call() -> Weight {
+		(17_624_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TxPause PausedCalls (r:1 w:1)
+	fn unpause_call() -> Weight {
+		(16_953_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,120 @@
+//! RPC interface for vesting schedules.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+pub use vesting_runtime_api::VestingApi as VestingRuntimeApi;
+
+/// Vesting schedule as returned over RPC.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVestingSchedule<BlockNumber> {
+	/// Block at which vesting starts.
+	pub start: BlockNumber,
+	/// Number of blocks between unlocks.
+	pub period: BlockNumber,
+	/// Number of unlocks.
+	pub period_count: u32,
+	/// Amount unlocked every period.
+	pub per_period: NumberOrHex,
+}
+
+/// Vesting state of an account as returned over RPC.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVestingInfo<BlockNumber> {
+	/// Vesting schedules of the account.
+	pub schedules: Vec<RpcVestingSchedule<BlockNumber>>,
+	/// Amount still locked by the schedules.
+	pub total_locked: NumberOrHex,
+	/// Amount unlocked by the schedules but not claimed yet.
+	pub claimable: NumberOrHex,
+	/// Block of the next unlock, if any schedule has not fully vested.
+	pub next_unlock: Option<BlockNumber>,
+}
+
+#[rpc]
+pub trait VestingApi<BlockHash, AccountId, BlockNumber> {
+	/// Vesting schedules of `who`, with the amounts they lock and unlock at the given block.
+	#[rpc(name = "vesting_info")]
+	fn vesting_info(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<RpcVestingInfo<BlockNumber>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// A struct that implements the [`VestingApi`].
+pub struct Vesting<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Vesting<C, B> {
+	/// Create new `Vesting` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber, Balance>
+	VestingApi<<Block as BlockT>::Hash, AccountId, BlockNumber> for Vesting<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: VestingRuntimeApi<Block, AccountId, BlockNumber, Balance>,
+	AccountId: Codec,
+	BlockNumber: Codec + Serialize + Send + Sync + 'static,
+	Balance: Codec + Into<NumberOrHex>,
+{
+	fn vesting_info(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<RpcVestingInfo<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let info = api.vesting_info(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query vesting schedules.".into(),
+			data: Some(e.to_string().into()),
+		})?;
+		Ok(RpcVestingInfo {
+			schedules: info
+				.schedules
+				.into_iter()
+				.map(|schedule| RpcVestingSchedule {
+					start: schedule.start,
+					period: schedule.period,
+					period_count: schedule.period_count,
+					per_period: schedule.per_period.into(),
+				})
+				.collect(),
+			total_locked: info.total_locked.into(),
+			claimable: info.claimable.into(),
+			next_unlock: info.next_unlock,
+		})
+	}
+}
@@ -0,0 +1,79 @@
+//! Runtime API definition for the vesting schedules of `orml_vesting`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+pub use orml_vesting::VestingSchedule;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AtLeast32Bit, AtLeast32BitUnsigned, One, Zero},
+	RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+/// Vesting state of an account.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VestingInfo<BlockNumber, Balance> {
+	/// Vesting schedules of the account.
+	pub schedules: Vec<VestingSchedule<BlockNumber, Balance>>,
+	/// Amount still locked by the schedules.
+	pub total_locked: Balance,
+	/// Amount unlocked by the schedules but not claimed yet.
+	pub claimable: Balance,
+	/// Block of the next unlock, if any schedule has not fully vested.
+	pub next_unlock: Option<BlockNumber>,
+}
+
+impl<BlockNumber, Balance> VestingInfo<BlockNumber, Balance>
+where
+	BlockNumber: AtLeast32Bit + Copy,
+	Balance: AtLeast32BitUnsigned + Copy,
+{
+	/// Vesting state at block `now` of an account with `schedules`, whose vesting lock is
+	/// `vesting_lock`.
+	pub fn new(
+		schedules: Vec<VestingSchedule<BlockNumber, Balance>>,
+		now: BlockNumber,
+		vesting_lock: Balance,
+	) -> Self {
+		let total_locked = schedules.iter().fold(Balance::zero(), |total, schedule| {
+			total.saturating_add(schedule.locked_amount(now))
+		});
+		let next_unlock =
+			schedules.iter().filter_map(|schedule| Self::next_unlock(schedule, now)).min();
+		Self {
+			schedules,
+			total_locked,
+			claimable: vesting_lock.saturating_sub(total_locked),
+			next_unlock,
+		}
+	}
+
+	/// First block after `now` at which `schedule` unlocks funds.
+	fn next_unlock(
+		schedule: &VestingSchedule<BlockNumber, Balance>,
+		now: BlockNumber,
+	) -> Option<BlockNumber> {
+		if schedule.period.is_zero() {
+			return None
+		}
+		let vested_periods = now.saturating_sub(schedule.start) / schedule.period;
+		if vested_periods >= schedule.period_count.into() {
+			return None
+		}
+		schedule
+			.start
+			.checked_add(&(vested_periods + One::one()).checked_mul(&schedule.period)?)
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait VestingApi<AccountId, BlockNumber, Balance> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// Vesting schedules of `who`, with the amounts they lock and unlock now.
+		fn vesting_info(who: AccountId) -> VestingInfo<BlockNumber, Balance>;
+	}
+}